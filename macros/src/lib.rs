@@ -0,0 +1,197 @@
+//! Procedural macros for `pkce-std`.
+//!
+//! This crate provides the [`verifier!`] and [`challenge!`] macros, which validate string
+//! literals at compile time, reporting invalid ones as compile errors pointing at the literals.
+//!
+//! The macros are re-exported by `pkce-std` when the `macros` feature is enabled,
+//! and should be used through it, since the expansions refer to `::pkce_std`.
+//!
+//! Literals are validated by `const` items in the expansions, which call into `pkce-std`,
+//! so that the rules, including lengths and characters, are defined in one place only.
+//!
+//! The `S384` and `S512` methods require the `extended-methods` feature,
+//! which is enabled by the feature of the same name in `pkce-std`.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::{
+    Error, LitStr, Token,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+};
+
+/// The `plain` method.
+const PLAIN: &str = "plain";
+
+/// The `S256` method.
+const SHA256: &str = "S256";
+
+/// The `S384` method.
+const SHA384: &str = "S384";
+
+/// The `S512` method.
+const SHA512: &str = "S512";
+
+/// Constructs `Verifier<'static>` from the string literal, validating it at compile time.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::{verifier, verifier::Verifier};
+///
+/// let verifier: Verifier<'static> = verifier!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+/// ```
+///
+/// Invalid verifiers fail to compile:
+///
+/// ```compile_fail
+/// use pkce_std::verifier;
+///
+/// let verifier = verifier!("too-short");
+/// ```
+///
+/// ```compile_fail
+/// use pkce_std::verifier;
+///
+/// let verifier = verifier!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ!");
+/// ```
+#[proc_macro]
+pub fn verifier(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+
+    let output = quote_spanned! { literal.span() =>
+        {
+            const _: () = ::pkce_std::macros::check_verifier(#literal);
+
+            // SAFETY: the literal is validated at compile time
+            unsafe { ::pkce_std::verifier::Verifier::borrowed_unchecked(#literal) }
+        }
+    };
+
+    output.into()
+}
+
+/// Represents `challenge!` arguments, that is, the secret and the optional method name.
+struct ChallengeInput {
+    secret: LitStr,
+    method: Option<LitStr>,
+}
+
+impl Parse for ChallengeInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let secret = input.parse()?;
+
+        let method = if input.is_empty() {
+            None
+        } else {
+            input.parse::<Token![,]>()?;
+
+            if input.is_empty() {
+                None
+            } else {
+                let method = input.parse()?;
+
+                input.parse::<Option<Token![,]>>()?;
+
+                Some(method)
+            }
+        };
+
+        Ok(Self { secret, method })
+    }
+}
+
+/// Constructs `Challenge` from the secret literal and the optional method name literal
+/// (defaulting to `S256`), validating them at compile time.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::{challenge, challenge::Challenge};
+///
+/// let challenge: Challenge = challenge!("i5ws67KoNWO9H0IZ1vpKrfzT4Zfy2BgrRswaSJHNEqk");
+///
+/// let plain = challenge!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ", "plain");
+/// ```
+///
+/// Secrets that do not match the method, as well as unknown methods, fail to compile:
+///
+/// ```compile_fail
+/// use pkce_std::challenge;
+///
+/// let challenge = challenge!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ-", "S256");
+/// ```
+///
+/// ```compile_fail
+/// use pkce_std::challenge;
+///
+/// let challenge = challenge!("i5ws67KoNWO9H0IZ1vpKrfzT4Zfy2BgrRswaSJHNEqk", "S1");
+/// ```
+#[cfg_attr(
+    not(feature = "extended-methods"),
+    doc = r#"
+Extended methods require the `extended-methods` feature:
+
+```compile_fail
+use pkce_std::challenge;
+
+let challenge = challenge!(
+    "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQdGhhbmtzIGZvciByZWFkaW5n",
+    "S384",
+);
+```
+"#
+)]
+#[proc_macro]
+pub fn challenge(input: TokenStream) -> TokenStream {
+    let ChallengeInput { secret, method } = parse_macro_input!(input as ChallengeInput);
+
+    let (name, span) = method.as_ref().map_or_else(
+        || (SHA256.to_owned(), Span::call_site()),
+        |method| (method.value(), method.span()),
+    );
+
+    let variant = match name.as_str() {
+        PLAIN => quote! { Plain },
+        SHA256 => quote! { Sha256 },
+        #[cfg(feature = "extended-methods")]
+        SHA384 => quote! { Sha384 },
+        #[cfg(feature = "extended-methods")]
+        SHA512 => quote! { Sha512 },
+        #[cfg(not(feature = "extended-methods"))]
+        SHA384 | SHA512 => {
+            let message = format!("method `{name}` requires the `extended-methods` feature");
+
+            return Error::new(span, message).to_compile_error().into();
+        }
+        _ => {
+            let message = format!(
+                "unknown method `{name}`, expected one of `{PLAIN}`, `{SHA256}`, `{SHA384}` or `{SHA512}`"
+            );
+
+            return Error::new(span, message).to_compile_error().into();
+        }
+    };
+
+    let output = quote_spanned! { secret.span() =>
+        {
+            const _: () = ::pkce_std::macros::check_challenge(
+                #secret,
+                &::pkce_std::method::Method::#variant,
+            );
+
+            // SAFETY: the secret is validated against the method at compile time
+            unsafe {
+                ::pkce_std::challenge::Challenge::new_unchecked(
+                    ::std::string::String::from(#secret),
+                    ::pkce_std::method::Method::#variant,
+                )
+            }
+        }
+    };
+
+    output.into()
+}
@@ -0,0 +1,476 @@
+//! Extracting and enforcing PKCE parameters in [`actix-web`] applications.
+//!
+//! - [`ChallengeQuery`] extracts the validated [`Challenge`] from `code_challenge` and
+//!   `code_challenge_method` query parameters of authorization requests;
+//! - [`VerifierForm`] extracts the validated [`Verifier`] from the `code_verifier` form
+//!   parameter of token requests;
+//! - [`Pkce`] is the middleware enforcing PKCE on token routes, see [`token`] for details.
+//!
+//! When deserializing, missing `code_challenge_method` defaults to [`Method::Plain`].
+//!
+//! Both extractors fail with [`Rejection`], which responds with `400 Bad Request`
+//! if the parameters are invalid. Both rejections and the middleware respond with
+//! the OAuth error response (see [`OAuthError::body`]).
+//!
+//! Note that [`VerifierForm`] consumes the request payload, so handlers that need other form
+//! parameters should extract their own forms using [`serde_support::code_verifier`] instead.
+//! The middleware buffers the payload and restores it, so it can still be extracted afterwards.
+//! Payloads are buffered up to the limit, [`LIMIT`] by default, which can be configured
+//! via [`Pkce::with_limit`]. Larger payloads are rejected with `413 Payload Too Large`.
+//!
+//! This module requires the `actix-web` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use actix_web::{App, web};
+//! use pkce_std::{
+//!     actix_web::{ChallengeQuery, Pkce, VerifierForm},
+//!     challenge::Challenge,
+//! };
+//!
+//! async fn authorize(ChallengeQuery(challenge): ChallengeQuery) -> String {
+//!     challenge.method().static_str().to_owned()
+//! }
+//!
+//! async fn token(VerifierForm(verifier): VerifierForm) -> String {
+//!     verifier.fingerprint().to_string()
+//! }
+//!
+//! async fn lookup(_code: String) -> Option<Challenge> {
+//!     None
+//! }
+//!
+//! let app = App::new()
+//!     .route("/authorize", web::get().to(authorize))
+//!     .service(
+//!         web::resource("/token")
+//!             .wrap(Pkce::new(lookup))
+//!             .route(web::post().to(token)),
+//!     );
+//! ```
+//!
+//! [`actix-web`]: https://docs.rs/actix-web
+//! [`Method::Plain`]: crate::method::Method::Plain
+//! [`serde_support::code_verifier`]: crate::serde_support::code_verifier
+//! [`LIMIT`]: crate::token::LIMIT
+//! [`OAuthError::body`]: crate::error::OAuthError::body
+
+use std::{
+    future::{Future, Ready, ready},
+    pin::Pin,
+    rc::Rc,
+};
+
+use ::actix_web::{
+    FromRequest, HttpRequest, HttpResponse, ResponseError,
+    body::EitherBody,
+    dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    error::QueryPayloadError,
+    http::{StatusCode, header},
+    web::{self, Form, Query},
+};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::{self, Challenge},
+    error::{self, OAuthError},
+    params::{AuthorizationParams, TokenParams},
+    token,
+    verifier::{self, Verifier},
+};
+
+/// Represents boxed local futures used by this module.
+pub type LocalBoxFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+/// Represents rejections of PKCE extractors.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Rejection {
+    /// The query could not be deserialized.
+    #[error("failed to deserialize query")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::actix_web::query),
+            help("make sure `code_challenge` is provided")
+        )
+    )]
+    Query(#[source] QueryPayloadError),
+    /// The form could not be deserialized.
+    #[error("failed to deserialize form")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::actix_web::form),
+            help("make sure `code_verifier` is provided")
+        )
+    )]
+    Form(#[source] ::actix_web::Error),
+    /// The challenge is invalid.
+    #[error("invalid challenge")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::actix_web::challenge), help("check the challenge"))
+    )]
+    Challenge(#[from] challenge::Error),
+    /// The verifier is invalid.
+    #[error("invalid verifier")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::actix_web::verifier), help("check the verifier"))
+    )]
+    Verifier(#[from] verifier::Error),
+}
+
+impl Rejection {
+    /// Returns the [`OAuthError`] to respond with.
+    pub const fn oauth_error(&self) -> OAuthError {
+        match self {
+            Self::Query(_) | Self::Form(_) => OAuthError::InvalidRequest,
+            Self::Challenge(error) => error.kind().oauth_error(),
+            Self::Verifier(error) => error.kind().oauth_error(),
+        }
+    }
+}
+
+impl ResponseError for Rejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Form(error) => error.as_response_error().status_code(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        reject(
+            self.status_code(),
+            self.oauth_error(),
+            Some(&self.to_string()),
+        )
+    }
+}
+
+/// Extracts [`Challenge`] from `code_challenge` and `code_challenge_method` query parameters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChallengeQuery(pub Challenge);
+
+impl FromRequest for ChallengeQuery {
+    type Error = Rejection;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = Query::<AuthorizationParams>::from_query(request.query_string())
+            .map_err(Rejection::Query)
+            .and_then(|Query(params)| Ok(Self(Challenge::try_from(params)?)));
+
+        ready(result)
+    }
+}
+
+/// Extracts [`Verifier`] from the `code_verifier` form parameter.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VerifierForm(pub Verifier<'static>);
+
+impl FromRequest for VerifierForm {
+    type Error = Rejection;
+    type Future = LocalBoxFuture<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let form = Form::<TokenParams<'static>>::from_request(request, payload);
+
+        Box::pin(async move {
+            let Form(params) = form.await.map_err(Rejection::Form)?;
+
+            let verifier = Verifier::try_from(params)?;
+
+            Ok(Self(verifier))
+        })
+    }
+}
+
+/// Represents middleware enforcing PKCE on token routes.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct Pkce<L> {
+    lookup: L,
+    limit: usize,
+}
+
+impl<L> Pkce<L> {
+    /// Constructs [`Self`] with the given challenge lookup callback.
+    ///
+    /// The callback is called with the authorization `code` and should return the challenge
+    /// stored for it, if any.
+    pub const fn new(lookup: L) -> Self {
+        Self {
+            lookup,
+            limit: token::LIMIT,
+        }
+    }
+
+    /// Sets the limit of request payloads, in bytes.
+    #[must_use]
+    pub const fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+
+        self
+    }
+
+    /// Returns the limit of request payloads, in bytes.
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl<S, B, L, F> Transform<S, ServiceRequest> for Pkce<L>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ::actix_web::Error> + 'static,
+    L: Fn(String) -> F + Clone + 'static,
+    F: Future<Output = Option<Challenge>> + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ::actix_web::Error;
+    type Transform = PkceMiddleware<S, L>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(PkceMiddleware::new(
+            service,
+            self.lookup.clone(),
+            self.limit,
+        )))
+    }
+}
+
+/// Represents services produced by the [`Pkce`] middleware.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug)]
+pub struct PkceMiddleware<S, L> {
+    service: Rc<S>,
+    lookup: L,
+    limit: usize,
+}
+
+impl<S, L> PkceMiddleware<S, L> {
+    fn new(service: S, lookup: L, limit: usize) -> Self {
+        Self {
+            service: Rc::new(service),
+            lookup,
+            limit,
+        }
+    }
+}
+
+/// Constructs the OAuth error response with the given status, error code and description.
+fn reject(status: StatusCode, error: OAuthError, description: Option<&str>) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type(error::CONTENT_TYPE)
+        .insert_header((header::CACHE_CONTROL, error::CACHE_CONTROL))
+        .body(error.body(description))
+}
+
+impl<S, B, L, F> Service<ServiceRequest> for PkceMiddleware<S, L>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ::actix_web::Error> + 'static,
+    L: Fn(String) -> F + Clone + 'static,
+    F: Future<Output = Option<Challenge>> + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ::actix_web::Error;
+    type Future = LocalBoxFuture<Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut request: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let lookup = self.lookup.clone();
+        let limit = self.limit;
+
+        Box::pin(async move {
+            let payload = request.extract::<web::Payload>().await?;
+
+            let bytes = match payload.to_bytes_limited(limit).await {
+                Ok(Ok(bytes)) => bytes,
+                result => {
+                    let status = if result.is_err() {
+                        StatusCode::PAYLOAD_TOO_LARGE
+                    } else {
+                        StatusCode::BAD_REQUEST
+                    };
+
+                    let response =
+                        request.into_response(reject(status, OAuthError::InvalidRequest, None));
+
+                    return Ok(response.map_into_right_body());
+                }
+            };
+
+            if let Err(error) = token::enforce(lookup, &bytes).await {
+                let response = request.into_response(reject(StatusCode::BAD_REQUEST, error, None));
+
+                return Ok(response.map_into_right_body());
+            }
+
+            // restore the buffered payload for the wrapped service
+            request.set_payload(Payload::from(bytes));
+
+            let response = service.call(request).await?;
+
+            Ok(response.map_into_left_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        App, FromRequest,
+        http::{StatusCode, header},
+        test::{self, TestRequest},
+        web,
+    };
+
+    use crate::{
+        actix_web::{ChallengeQuery, Pkce, Rejection, VerifierForm},
+        challenge::Challenge,
+        method::Method,
+        verifier::Verifier,
+    };
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    fn form(body: String) -> TestRequest {
+        TestRequest::post()
+            .uri("/token")
+            .insert_header((header::CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload(body)
+    }
+
+    #[tokio::test]
+    async fn challenge_query() {
+        let request = TestRequest::get()
+            .uri(&format!("/?code_challenge={VERIFIER}"))
+            .to_http_request();
+
+        let ChallengeQuery(challenge) = ChallengeQuery::extract(&request).await.unwrap();
+
+        assert_eq!(challenge.secret(), VERIFIER);
+        assert_eq!(challenge.method(), Method::Plain);
+    }
+
+    #[tokio::test]
+    async fn challenge_query_invalid() {
+        let request = TestRequest::get()
+            .uri("/?code_challenge=invalid&code_challenge_method=S256")
+            .to_http_request();
+
+        let result = ChallengeQuery::extract(&request).await;
+
+        assert!(matches!(result, Err(Rejection::Challenge(_))));
+    }
+
+    #[tokio::test]
+    async fn challenge_query_unknown_method() {
+        for method in ["foo", "s256"] {
+            let request = TestRequest::get()
+                .uri(&format!(
+                    "/?code_challenge={VERIFIER}&code_challenge_method={method}"
+                ))
+                .to_http_request();
+
+            let result = ChallengeQuery::extract(&request).await;
+
+            assert!(matches!(result, Err(Rejection::Challenge(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn verifier_form() {
+        let (request, mut payload) =
+            form(format!("code=code&code_verifier={VERIFIER}")).to_http_parts();
+
+        let VerifierForm(verifier) = VerifierForm::from_request(&request, &mut payload)
+            .await
+            .unwrap();
+
+        assert_eq!(verifier.get(), VERIFIER);
+    }
+
+    async fn lookup(code: String) -> Option<Challenge> {
+        (code == "code").then(|| Verifier::borrowed(VERIFIER).unwrap().challenge())
+    }
+
+    async fn token(VerifierForm(verifier): VerifierForm) -> String {
+        verifier.get().to_owned()
+    }
+
+    #[tokio::test]
+    async fn middleware() {
+        let app = test::init_service(
+            App::new().service(
+                web::resource("/token")
+                    .wrap(Pkce::new(lookup))
+                    .route(web::post().to(token)),
+            ),
+        )
+        .await;
+
+        let request = form(format!("code=code&code_verifier={VERIFIER}")).to_request();
+
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let other = Verifier::generate_default();
+
+        let request = form(format!("code=code&code_verifier={other}")).to_request();
+
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let request = form(format!("code=other&code_verifier={VERIFIER}")).to_request();
+
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn middleware_limit() {
+        let app = test::init_service(
+            App::new().service(
+                web::resource("/token")
+                    .wrap(Pkce::new(lookup).with_limit(16))
+                    .route(web::post().to(token)),
+            ),
+        )
+        .await;
+
+        let request = form(format!("code=code&code_verifier={VERIFIER}")).to_request();
+
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -0,0 +1,113 @@
+//! String-only API.
+//!
+//! Bindings (such as [`capi`], [`node`] and [`wasm`]) and command-line tools usually deal
+//! with plain strings rather than the typed API of this crate. This module provides
+//! the simplified surface they share, so that each of them does not have to re-wrap it:
+//!
+//! - [`generate_pair`] generates the `code_verifier` and the `code_challenge`;
+//! - [`derive_challenge`] derives the `code_challenge` from the `code_verifier`;
+//! - [`verify`] verifies the `code_verifier` against the `code_challenge`.
+//!
+//! Signatures of these functions are considered stable, and none of them panics;
+//! invalid arguments are reported via the unified [`enum@Error`] instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::api;
+//!
+//! let pair = api::generate_pair().unwrap();
+//!
+//! let challenge = api::derive_challenge(&pair.verifier, &pair.method).unwrap();
+//!
+//! assert_eq!(challenge, pair.challenge);
+//!
+//! assert!(api::verify(&pair.verifier, &pair.challenge, &pair.method).unwrap());
+//!
+//! assert!(api::verify(&pair.verifier, &pair.challenge, "S1024").is_err());
+//! ```
+//!
+//! [`capi`]: crate::capi
+//! [`node`]: crate::node
+//! [`wasm`]: crate::wasm
+
+use crate::{
+    challenge::Challenge, error::Error, length::Length, method::Method, verifier::Verifier,
+};
+
+/// Represents generated `(verifier, challenge)` pairs of strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pair {
+    /// The `code_verifier`.
+    pub verifier: String,
+    /// The `code_challenge`.
+    pub challenge: String,
+    /// The `code_challenge_method`.
+    pub method: String,
+}
+
+/// Generates the `(verifier, challenge)` pair using the default method and length.
+///
+/// Entropy is drawn directly from the operating system.
+///
+/// # Errors
+///
+/// Returns [`enum@Error`] if the operating system fails to provide entropy.
+pub fn generate_pair() -> Result<Pair, Error> {
+    generate_pair_using(Method::default(), Length::default())
+}
+
+/// Generates the `(verifier, challenge)` pair using the given method and length.
+///
+/// Entropy is drawn directly from the operating system.
+///
+/// # Errors
+///
+/// Returns [`enum@Error`] if the method is unsupported
+/// or the operating system fails to provide entropy.
+pub fn generate_pair_using(method: Method, length: Length) -> Result<Pair, Error> {
+    let verifier = Verifier::try_generate(length)?;
+
+    let challenge = verifier.try_challenge_using(method)?;
+
+    let (secret, method) = challenge.into_parts();
+
+    let pair = Pair {
+        verifier: verifier.get().to_owned(),
+        challenge: secret,
+        method: method.static_str().to_owned(),
+    };
+
+    Ok(pair)
+}
+
+/// Derives the `code_challenge` from the `code_verifier` using the `method`.
+///
+/// # Errors
+///
+/// Returns [`enum@Error`] if the verifier is invalid or the method is unknown.
+pub fn derive_challenge(verifier: &str, method: &str) -> Result<String, Error> {
+    let verifier = Verifier::borrowed(verifier)?;
+
+    let method: Method = method.parse()?;
+
+    // parsed methods are always supported, so this never panics
+    let (secret, _) = verifier.challenge_using(method).into_parts();
+
+    Ok(secret)
+}
+
+/// Verifies the given `code_verifier` against the `code_challenge` using the `method`.
+///
+/// # Errors
+///
+/// Returns [`enum@Error`] if any of the arguments is invalid or the method is unknown.
+pub fn verify(verifier: &str, challenge: &str, method: &str) -> Result<bool, Error> {
+    let verifier = Verifier::borrowed(verifier)?;
+
+    let method: Method = method.parse()?;
+
+    let challenge = Challenge::new(challenge.to_owned(), method)?;
+
+    Ok(verifier.verify(&challenge))
+}
@@ -0,0 +1,133 @@
+//! Auditing PKCE code verifiers for weak patterns.
+//!
+//! Verifiers are valid as long as they have valid lengths and consist of valid characters,
+//! which includes pathological values like `AAA...A` or `ABC...Z`. Such values are
+//! astronomically unlikely to be generated randomly, so encountering them usually means
+//! that the client implementation is broken.
+//!
+//! The [`check_weak`] function rejects the following patterns:
+//!
+//! - single repeated character (see [`Error::Repeated`]);
+//! - sequences of consecutive characters from [`CHARS`] in either direction,
+//!   possibly wrapping around (see [`Error::Sequential`]).
+//!
+//! This module also provides [`generate`] and [`generate_encode`], which regenerate verifiers
+//! until they pass the audit.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{audit::check_weak, verifier::Verifier};
+//!
+//! let repeated = Verifier::borrowed("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+//!
+//! assert!(check_weak(&repeated).is_err());
+//!
+//! let sequential = Verifier::borrowed("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopq").unwrap();
+//!
+//! assert!(check_weak(&sequential).is_err());
+//!
+//! let generated = Verifier::generate_default();
+//!
+//! assert!(check_weak(&generated).is_ok());
+//! ```
+//!
+//! [`CHARS`]: crate::charset::CHARS
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    charset::{LENGTH, STRING},
+    count::Count,
+    length::Length,
+    verifier::Verifier,
+};
+
+/// Represents weak patterns found in verifiers.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The verifier consists of the single repeated character.
+    #[error("verifier consists of the single repeated character")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::audit::repeated),
+            help("make sure the verifier is generated randomly")
+        )
+    )]
+    Repeated,
+
+    /// The verifier is the sequence of consecutive characters.
+    #[error("verifier is the sequence of consecutive characters")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::audit::sequential),
+            help("make sure the verifier is generated randomly")
+        )
+    )]
+    Sequential,
+}
+
+fn index(byte: u8) -> Option<usize> {
+    STRING.bytes().position(|valid| valid == byte)
+}
+
+fn is_repeated(bytes: &[u8]) -> bool {
+    bytes.windows(2).all(|window| window[0] == window[1])
+}
+
+fn is_sequential(bytes: &[u8], step: usize) -> bool {
+    bytes
+        .windows(2)
+        .all(|window| match (index(window[0]), index(window[1])) {
+            (Some(current), Some(next)) => (current + step) % LENGTH == next,
+            _ => false,
+        })
+}
+
+/// Checks the given verifier for weak patterns.
+///
+/// # Errors
+///
+/// Returns [`Error::Repeated`] if the verifier consists of the single repeated character,
+/// and [`Error::Sequential`] if it is the sequence of consecutive characters.
+pub fn check_weak(verifier: &Verifier<'_>) -> Result<(), Error> {
+    let bytes = verifier.get().as_bytes();
+
+    if is_repeated(bytes) {
+        return Err(Error::Repeated);
+    }
+
+    if is_sequential(bytes, 1) || is_sequential(bytes, LENGTH - 1) {
+        return Err(Error::Sequential);
+    }
+
+    Ok(())
+}
+
+/// Generates random verifiers with specified length until one passes [`check_weak`].
+pub fn generate(length: Length) -> Verifier<'static> {
+    loop {
+        let verifier = Verifier::generate(length);
+
+        if check_weak(&verifier).is_ok() {
+            return verifier;
+        }
+    }
+}
+
+/// Generates verifiers from `count` random bytes until one passes [`check_weak`].
+pub fn generate_encode(count: Count) -> Verifier<'static> {
+    loop {
+        let verifier = Verifier::generate_encode(count);
+
+        if check_weak(&verifier).is_ok() {
+            return verifier;
+        }
+    }
+}
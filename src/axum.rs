@@ -0,0 +1,287 @@
+//! Extracting PKCE parameters in [`axum`] handlers.
+//!
+//! - [`ChallengeQuery`] extracts the validated [`Challenge`] from `code_challenge` and
+//!   `code_challenge_method` query parameters of authorization requests;
+//! - [`VerifierForm`] extracts the validated [`Verifier`] from the `code_verifier` form
+//!   parameter of token requests.
+//!
+//! When deserializing, missing `code_challenge_method` defaults to [`Method::Plain`].
+//!
+//! Both extractors reject requests with [`Rejection`], which responds with
+//! `400 Bad Request` if the parameters are invalid, using the OAuth error response
+//! (see [`OAuthError::body`]).
+//!
+//! Note that [`VerifierForm`] consumes the request body, so handlers that need other form
+//! parameters should extract their own forms using [`serde_support::code_verifier`] instead.
+//!
+//! This module requires the `axum` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use axum::{Router, routing::{get, post}};
+//! use pkce_std::axum::{ChallengeQuery, VerifierForm};
+//!
+//! async fn authorize(ChallengeQuery(challenge): ChallengeQuery) -> String {
+//!     challenge.method().static_str().to_owned()
+//! }
+//!
+//! async fn token(VerifierForm(verifier): VerifierForm) -> String {
+//!     verifier.fingerprint().to_string()
+//! }
+//!
+//! let router: Router = Router::new()
+//!     .route("/authorize", get(authorize))
+//!     .route("/token", post(token));
+//! ```
+//!
+//! [`axum`]: https://docs.rs/axum
+//! [`Method::Plain`]: crate::method::Method::Plain
+//! [`OAuthError::body`]: crate::error::OAuthError::body
+//! [`serde_support::code_verifier`]: crate::serde_support::code_verifier
+
+use ::axum::{
+    extract::{
+        Form, FromRequest, FromRequestParts, Query, Request,
+        rejection::{FormRejection, QueryRejection},
+    },
+    http::{StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::{self, Challenge},
+    error::{self, OAuthError},
+    params::{AuthorizationParams, TokenParams},
+    verifier::{self, Verifier},
+};
+
+/// Represents rejections of PKCE extractors.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Rejection {
+    /// The query could not be deserialized.
+    #[error("failed to deserialize query")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::axum::query),
+            help("make sure `code_challenge` is provided")
+        )
+    )]
+    Query(#[source] QueryRejection),
+    /// The form could not be deserialized.
+    #[error("failed to deserialize form")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::axum::form),
+            help("make sure `code_verifier` is provided")
+        )
+    )]
+    Form(#[source] FormRejection),
+    /// The challenge is invalid.
+    #[error("invalid challenge")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::axum::challenge), help("check the challenge"))
+    )]
+    Challenge(#[from] challenge::Error),
+    /// The verifier is invalid.
+    #[error("invalid verifier")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::axum::verifier), help("check the verifier"))
+    )]
+    Verifier(#[from] verifier::Error),
+}
+
+impl Rejection {
+    /// Returns the [`OAuthError`] to respond with.
+    pub const fn oauth_error(&self) -> OAuthError {
+        match self {
+            Self::Query(_) | Self::Form(_) => OAuthError::InvalidRequest,
+            Self::Challenge(error) => error.kind().oauth_error(),
+            Self::Verifier(error) => error.kind().oauth_error(),
+        }
+    }
+
+    /// Returns the status code to respond with.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Query(rejection) => rejection.status(),
+            Self::Form(rejection) => rejection.status(),
+            Self::Challenge(_) | Self::Verifier(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        let body = self.oauth_error().body(Some(&self.to_string()));
+
+        let headers = [
+            (header::CONTENT_TYPE, error::CONTENT_TYPE),
+            (header::CACHE_CONTROL, error::CACHE_CONTROL),
+        ];
+
+        (self.status(), headers, body).into_response()
+    }
+}
+
+/// Extracts [`Challenge`] from `code_challenge` and `code_challenge_method` query parameters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChallengeQuery(pub Challenge);
+
+impl<S: Send + Sync> FromRequestParts<S> for ChallengeQuery {
+    type Rejection = Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<AuthorizationParams>::from_request_parts(parts, state)
+            .await
+            .map_err(Rejection::Query)?;
+
+        let challenge = Challenge::try_from(params)?;
+
+        Ok(Self(challenge))
+    }
+}
+
+/// Extracts [`Verifier`] from the `code_verifier` form parameter.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VerifierForm(pub Verifier<'static>);
+
+impl<S: Send + Sync> FromRequest<S> for VerifierForm {
+    type Rejection = Rejection;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Form(params) = Form::<TokenParams<'static>>::from_request(request, state)
+            .await
+            .map_err(Rejection::Form)?;
+
+        let verifier = Verifier::try_from(params)?;
+
+        Ok(Self(verifier))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{self, Body},
+        extract::{FromRequest, FromRequestParts, Request},
+        http::{StatusCode, header},
+        response::IntoResponse,
+    };
+
+    use crate::{
+        axum::{ChallengeQuery, Rejection, VerifierForm},
+        method::Method,
+    };
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    fn form(body: String) -> Request {
+        Request::builder()
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn challenge_query() {
+        let request = Request::builder().uri(format!("/?code_challenge={VERIFIER}"));
+
+        let (mut parts, ()) = request.body(()).unwrap().into_parts();
+
+        let ChallengeQuery(challenge) = ChallengeQuery::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+
+        assert_eq!(challenge.secret(), VERIFIER);
+        assert_eq!(challenge.method(), Method::Plain);
+    }
+
+    #[tokio::test]
+    async fn challenge_query_invalid() {
+        let request = Request::builder().uri("/?code_challenge=invalid&code_challenge_method=S256");
+
+        let (mut parts, ()) = request.body(()).unwrap().into_parts();
+
+        let result = ChallengeQuery::from_request_parts(&mut parts, &()).await;
+
+        assert!(matches!(result, Err(Rejection::Challenge(_))));
+    }
+
+    #[tokio::test]
+    async fn rejection_response() {
+        let request = Request::builder().uri("/?code_challenge=invalid&code_challenge_method=S256");
+
+        let (mut parts, ()) = request.body(()).unwrap().into_parts();
+
+        let rejection = ChallengeQuery::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+
+        let response = rejection.into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "no-store");
+
+        let bytes = body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            &bytes[..],
+            br#"{"error":"invalid_request","error_description":"invalid challenge"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn challenge_query_unknown_method() {
+        for method in ["foo", "s256"] {
+            let request = Request::builder().uri(format!(
+                "/?code_challenge={VERIFIER}&code_challenge_method={method}"
+            ));
+
+            let (mut parts, ()) = request.body(()).unwrap().into_parts();
+
+            let result = ChallengeQuery::from_request_parts(&mut parts, &()).await;
+
+            assert!(matches!(result, Err(Rejection::Challenge(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn verifier_form() {
+        let request = form(format!("code=code&code_verifier={VERIFIER}"));
+
+        let VerifierForm(verifier) = VerifierForm::from_request(request, &()).await.unwrap();
+
+        assert_eq!(verifier.get(), VERIFIER);
+    }
+
+    #[tokio::test]
+    async fn verifier_form_missing() {
+        let request = form("code=code".to_owned());
+
+        let result = VerifierForm::from_request(request, &()).await;
+
+        assert!(matches!(result, Err(Rejection::Form(_))));
+    }
+}
@@ -0,0 +1,257 @@
+//! The `pkce` command-line tool.
+//!
+//! Generates verifiers, derives challenges and verifies them, so that OAuth integrations
+//! can be tested from scripts and CI pipelines.
+//!
+//! Verifiers are read from standard input when omitted or given as `-`, and `--json`
+//! emits objects with the wire-format field names, for instance, `code_challenge`.
+//!
+//! Shell completions are generated via the `completions` subcommand.
+
+use std::{
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use clap_complete::Shell;
+use pkce_std::{
+    api,
+    challenge::{CODE_CHALLENGE, CODE_CHALLENGE_METHOD},
+    length::Length,
+    method::Method,
+    verifier::CODE_VERIFIER,
+};
+
+const NAME: &str = "pkce";
+
+const STDIN: &str = "-";
+
+const METHOD: &str = "method";
+const LENGTH: &str = "length";
+const JSON: &str = "json";
+const VERIFIER: &str = "verifier";
+const CHALLENGE: &str = "challenge";
+const SHELL: &str = "shell";
+
+const GENERATE: &str = "generate";
+const DERIVE: &str = "challenge";
+const VERIFY: &str = "verify";
+const COMPLETIONS: &str = "completions";
+
+fn method() -> Arg {
+    Arg::new(METHOD)
+        .long(METHOD)
+        .short('m')
+        .help("The challenge method")
+        .value_parser(value_parser!(Method))
+        .default_value("S256")
+}
+
+fn json() -> Arg {
+    Arg::new(JSON)
+        .long(JSON)
+        .help("Output JSON using wire-format field names")
+        .action(ArgAction::SetTrue)
+}
+
+fn verifier() -> Arg {
+    Arg::new(VERIFIER)
+        .help("The verifier; read from standard input if omitted or `-`")
+        .default_value(STDIN)
+}
+
+fn command() -> Command {
+    Command::new(NAME)
+        .about("Handling Proof Key for Code Exchange")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(true)
+        .subcommand(
+            Command::new(GENERATE)
+                .about("Generates verifiers along with their challenges")
+                .arg(method())
+                .arg(
+                    Arg::new(LENGTH)
+                        .long(LENGTH)
+                        .short('l')
+                        .help("The verifier length")
+                        .value_parser(value_parser!(Length)),
+                )
+                .arg(json()),
+        )
+        .subcommand(
+            Command::new(DERIVE)
+                .about("Derives challenges from verifiers")
+                .arg(method())
+                .arg(verifier())
+                .arg(json()),
+        )
+        .subcommand(
+            Command::new(VERIFY)
+                .about("Verifies challenges against verifiers, exiting with 1 on mismatch")
+                .arg(method())
+                .arg(
+                    Arg::new(CHALLENGE)
+                        .long(CHALLENGE)
+                        .short('c')
+                        .help("The challenge")
+                        .required(true),
+                )
+                .arg(verifier())
+                .arg(json()),
+        )
+        .subcommand(
+            Command::new(COMPLETIONS)
+                .about("Generates shell completions")
+                .arg(
+                    Arg::new(SHELL)
+                        .help("The shell to generate completions for")
+                        .value_parser(value_parser!(Shell))
+                        .required(true),
+                ),
+        )
+}
+
+/// Represents JSON values emitted by the tool.
+enum Value<'v> {
+    String(&'v str),
+    Bool(bool),
+}
+
+/// Formats the JSON object with the given fields.
+///
+/// Strings are never escaped, since all of them consist of unreserved characters.
+fn object(fields: &[(&str, Value<'_>)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| match value {
+            Value::String(string) => format!(r#""{name}":"{string}""#),
+            Value::Bool(flag) => format!(r#""{name}":{flag}"#),
+        })
+        .collect();
+
+    format!("{{{}}}", fields.join(","))
+}
+
+fn print_challenge(challenge: &str, method: &str, verifier: Option<&str>, json: bool) {
+    let mut fields = Vec::new();
+
+    if let Some(verifier) = verifier {
+        fields.push((CODE_VERIFIER, Value::String(verifier)));
+    }
+
+    fields.push((CODE_CHALLENGE, Value::String(challenge)));
+    fields.push((CODE_CHALLENGE_METHOD, Value::String(method)));
+
+    if json {
+        println!("{}", object(&fields));
+    } else {
+        for (name, value) in fields {
+            if let Value::String(string) = value {
+                println!("{name}: {string}");
+            }
+        }
+    }
+}
+
+fn read_verifier(matches: &ArgMatches) -> Result<String, String> {
+    let argument = matches
+        .get_one::<String>(VERIFIER)
+        .map_or(STDIN, String::as_str);
+
+    if argument != STDIN {
+        return Ok(argument.to_owned());
+    }
+
+    let mut input = String::new();
+
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|error| format!("failed to read standard input: {error}"))?;
+
+    Ok(input.trim().to_owned())
+}
+
+fn get_method(matches: &ArgMatches) -> Method {
+    matches
+        .get_one::<Method>(METHOD)
+        .copied()
+        .unwrap_or_default()
+}
+
+fn run(matches: &ArgMatches) -> Result<ExitCode, String> {
+    match matches.subcommand() {
+        Some((GENERATE, matches)) => {
+            let method = get_method(matches);
+
+            let length = matches
+                .get_one::<Length>(LENGTH)
+                .copied()
+                .unwrap_or_default();
+
+            let pair =
+                api::generate_pair_using(method, length).map_err(|error| error.to_string())?;
+
+            print_challenge(
+                &pair.challenge,
+                &pair.method,
+                Some(&pair.verifier),
+                matches.get_flag(JSON),
+            );
+        }
+        Some((DERIVE, matches)) => {
+            let verifier = read_verifier(matches)?;
+
+            let method = get_method(matches);
+
+            let challenge = api::derive_challenge(&verifier, method.static_str())
+                .map_err(|error| error.to_string())?;
+
+            print_challenge(
+                &challenge,
+                method.static_str(),
+                None,
+                matches.get_flag(JSON),
+            );
+        }
+        Some((VERIFY, matches)) => {
+            let verifier = read_verifier(matches)?;
+
+            let secret = matches
+                .get_one::<String>(CHALLENGE)
+                .cloned()
+                .unwrap_or_default();
+
+            let valid = api::verify(&verifier, &secret, get_method(matches).static_str())
+                .map_err(|error| error.to_string())?;
+
+            if matches.get_flag(JSON) {
+                println!("{}", object(&[("valid", Value::Bool(valid))]));
+            } else {
+                println!("{}", if valid { "valid" } else { "invalid" });
+            }
+
+            if !valid {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Some((COMPLETIONS, matches)) => {
+            if let Some(shell) = matches.get_one::<Shell>(SHELL) {
+                clap_complete::generate(*shell, &mut command(), NAME, &mut io::stdout());
+            }
+        }
+        _ => unreachable!("subcommands are required"),
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+
+    run(&matches).unwrap_or_else(|error| {
+        eprintln!("error: {error}");
+
+        ExitCode::from(2)
+    })
+}
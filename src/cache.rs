@@ -0,0 +1,167 @@
+//! Caching derived PKCE code challenges.
+//!
+//! Test harnesses and proxies often verify the same verifier-challenge pairs repeatedly,
+//! deriving the challenge every time. The [`ChallengeCache`] type memoizes derived challenges,
+//! keyed by the SHA-256 digest of the verifier and the method, so that verifiers themselves
+//! are never kept in the cache.
+//!
+//! Note that [`Method::Plain`] challenges are the verifiers themselves, so they are never
+//! cached, being derived every time instead (which is free anyway). Custom methods are
+//! expected to derive challenges that do not reveal verifiers.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{cache::ChallengeCache, code::Code};
+//!
+//! let cache = ChallengeCache::new();
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! assert!(cache.verify(&verifier, &challenge));
+//! assert!(cache.verify(&verifier, &challenge)); // cached
+//!
+//! assert_eq!(cache.len(), 1);
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    challenge::{self, Challenge, INVALID},
+    hash::{SHA256_LENGTH, sha256_into},
+    method::Method,
+    verifier::Verifier,
+};
+
+type Digest = [u8; SHA256_LENGTH];
+
+type Entries = HashMap<Method, HashMap<Digest, Challenge>>;
+
+fn digest(verifier: &Verifier<'_>) -> Digest {
+    let mut digest = [0; SHA256_LENGTH];
+
+    sha256_into(verifier.get(), &mut digest);
+
+    digest
+}
+
+/// Represents caches of derived challenges.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Default)]
+pub struct ChallengeCache {
+    entries: Mutex<Entries>,
+}
+
+impl ChallengeCache {
+    /// Constructs empty [`Self`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the cached [`Challenge`] of the verifier, deriving it if needed.
+    ///
+    /// [`Method::Plain`] challenges are derived without being cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`challenge::Error`] if the method fails to derive valid challenge,
+    /// see [`Challenge::try_create_using`].
+    pub fn try_challenge_using(
+        &self,
+        verifier: &Verifier<'_>,
+        method: Method,
+    ) -> Result<Challenge, challenge::Error> {
+        if method == Method::Plain {
+            return verifier.try_challenge_using(method);
+        }
+
+        let digest = digest(verifier);
+
+        let cached = self
+            .entries()
+            .get(&method)
+            .and_then(|challenges| challenges.get(&digest))
+            .cloned();
+
+        if let Some(challenge) = cached {
+            return Ok(challenge);
+        }
+
+        let challenge = verifier.try_challenge_using(method)?;
+
+        self.entries()
+            .entry(method)
+            .or_default()
+            .insert(digest, challenge.clone());
+
+        Ok(challenge)
+    }
+
+    /// Returns the cached [`Challenge`] of the verifier, deriving it if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge, see [`try_challenge_using`].
+    ///
+    /// [`try_challenge_using`]: Self::try_challenge_using
+    pub fn challenge_using(&self, verifier: &Verifier<'_>, method: Method) -> Challenge {
+        self.try_challenge_using(verifier, method).expect(INVALID)
+    }
+
+    /// Verifies the given [`Challenge`] against the verifier using the cache.
+    ///
+    /// Challenges using methods that fail to derive valid challenges are never verified.
+    pub fn verify(&self, verifier: &Verifier<'_>, challenge: &Challenge) -> bool {
+        self.try_challenge_using(verifier, challenge.method())
+            .is_ok_and(|expected| challenge == &expected)
+    }
+
+    /// Returns the number of cached challenges.
+    pub fn len(&self) -> usize {
+        self.entries().values().map(HashMap::len).sum()
+    }
+
+    /// Checks whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached challenges.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cache::ChallengeCache, method::Method, verifier::Verifier};
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    #[test]
+    fn plain_not_cached() {
+        let cache = ChallengeCache::new();
+
+        let verifier = Verifier::borrowed(VERIFIER).unwrap();
+
+        let challenge = cache.challenge_using(&verifier, Method::Plain);
+
+        assert_eq!(challenge.secret(), VERIFIER);
+
+        assert!(cache.is_empty());
+
+        cache.challenge_using(&verifier, Method::Sha256);
+
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,261 @@
+//! Parsing authorization callbacks.
+//!
+//! After the user authorizes the client, the *server* redirects back to the client with either
+//! the authorization `code` or the `error` in the query, along with the `state` sent initially.
+//!
+//! The [`parse`] function accepts either the full redirect URL or its query,
+//! returning [`Callback`] values. The `state` can then be used to look up the stored verifier.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::callback::{self, Callback};
+//!
+//! let url = "https://client.example/callback?code=SplxlOBeZQQYbYS6WxSbIA&state=xyz";
+//!
+//! let callback = callback::parse(url).unwrap();
+//!
+//! assert!(callback.matches_state("xyz"));
+//!
+//! let Callback::Authorized(authorized) = callback else {
+//!     panic!("expected code");
+//! };
+//!
+//! assert_eq!(authorized.code, "SplxlOBeZQQYbYS6WxSbIA");
+//! ```
+//!
+//! Handling errors:
+//!
+//! ```
+//! use pkce_std::callback;
+//!
+//! let query = "error=access_denied&error_description=User+denied+access&state=xyz";
+//!
+//! let denied = callback::parse(query).unwrap().into_result().unwrap_err();
+//!
+//! assert_eq!(denied.error, "access_denied");
+//! assert_eq!(denied.description.as_deref(), Some("User denied access"));
+//! ```
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::ct;
+
+/// The `code` parameter name.
+pub const CODE: &str = "code";
+
+/// The `state` parameter name.
+pub const STATE: &str = "state";
+
+/// The `error` parameter name.
+pub const ERROR: &str = "error";
+
+/// The `error_description` parameter name.
+pub const ERROR_DESCRIPTION: &str = "error_description";
+
+/// The `error_uri` parameter name.
+pub const ERROR_URI: &str = "error_uri";
+
+/// Represents errors that can occur when parsing callbacks.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// Neither `code` nor `error` is present.
+    #[error("expected either `code` or `error` parameter")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::callback::missing),
+            help("make sure the callback query is passed")
+        )
+    )]
+    Missing,
+
+    /// The parameter is present more than once.
+    #[error("duplicate `{name}` parameter")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::callback::duplicate),
+            help("make sure each parameter is present at most once")
+        )
+    )]
+    Duplicate {
+        /// The parameter name.
+        name: String,
+    },
+
+    /// The percent-encoding is invalid.
+    #[error("invalid percent-encoding")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::callback::encoding),
+            help("make sure the query is properly percent-encoded")
+        )
+    )]
+    Encoding,
+}
+
+impl Error {
+    /// Constructs [`Self::Duplicate`].
+    pub fn duplicate<N: Into<String>>(name: N) -> Self {
+        Self::Duplicate { name: name.into() }
+    }
+}
+
+/// Represents successful authorization callbacks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorized {
+    /// The authorization code.
+    pub code: String,
+    /// The state, if present.
+    pub state: Option<String>,
+}
+
+/// Represents denied authorization callbacks.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("authorization denied: `{error}`")]
+pub struct Denied {
+    /// The error code, such as `access_denied`.
+    pub error: String,
+    /// The human-readable error description, if present.
+    pub description: Option<String>,
+    /// The error page URI, if present.
+    pub uri: Option<String>,
+    /// The state, if present.
+    pub state: Option<String>,
+}
+
+/// Represents parsed authorization callbacks.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Callback {
+    /// The authorization succeeded.
+    Authorized(Authorized),
+    /// The authorization was denied.
+    Denied(Denied),
+}
+
+impl Callback {
+    /// Returns the state, if present.
+    pub fn state(&self) -> Option<&str> {
+        match self {
+            Self::Authorized(authorized) => authorized.state.as_deref(),
+            Self::Denied(denied) => denied.state.as_deref(),
+        }
+    }
+
+    /// Checks whether the state matches the expected one in constant time.
+    ///
+    /// Missing states never match.
+    pub fn matches_state<S: AsRef<str>>(&self, expected: S) -> bool {
+        self.state()
+            .is_some_and(|state| ct::eq(state, expected.as_ref()))
+    }
+
+    /// Converts [`Self`] into [`Result`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Denied`] if the authorization was denied.
+    pub fn into_result(self) -> Result<Authorized, Denied> {
+        match self {
+            Self::Authorized(authorized) => Ok(authorized),
+            Self::Denied(denied) => Err(denied),
+        }
+    }
+}
+
+/// Parses the given redirect URL or query into [`Callback`].
+///
+/// Everything up to and including the first `?` is skipped, as is the fragment, if any.
+/// Unknown parameters are ignored.
+///
+/// # Errors
+///
+/// Returns [`Error::Missing`] if neither `code` nor `error` is present,
+/// [`Error::Duplicate`] if any of the known parameters is repeated,
+/// and [`Error::Encoding`] if the percent-encoding is invalid.
+pub fn parse(string: &str) -> Result<Callback, Error> {
+    let query = string.split_once('?').map_or(string, |(_, query)| query);
+
+    let query = query.split_once('#').map_or(query, |(query, _)| query);
+
+    let mut code = None;
+    let mut state = None;
+    let mut error = None;
+    let mut description = None;
+    let mut uri = None;
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+        let name = decode(name)?;
+
+        let slot = match name.as_str() {
+            CODE => &mut code,
+            STATE => &mut state,
+            ERROR => &mut error,
+            ERROR_DESCRIPTION => &mut description,
+            ERROR_URI => &mut uri,
+            _ => continue,
+        };
+
+        if slot.replace(decode(value)?).is_some() {
+            return Err(Error::duplicate(name));
+        }
+    }
+
+    if let Some(error) = error {
+        let denied = Denied {
+            error,
+            description,
+            uri,
+            state,
+        };
+
+        return Ok(Callback::Denied(denied));
+    }
+
+    let code = code.ok_or(Error::Missing)?;
+
+    Ok(Callback::Authorized(Authorized { code, state }))
+}
+
+const fn hex(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn decode(string: &str) -> Result<String, Error> {
+    let mut bytes = string.bytes();
+    let mut output = Vec::with_capacity(string.len());
+
+    while let Some(byte) = bytes.next() {
+        let decoded = match byte {
+            b'+' => b' ',
+            b'%' => {
+                let high = bytes.next().and_then(hex).ok_or(Error::Encoding)?;
+                let low = bytes.next().and_then(hex).ok_or(Error::Encoding)?;
+
+                (high << 4) | low
+            }
+            _ => byte,
+        };
+
+        output.push(decoded);
+    }
+
+    String::from_utf8(output).map_err(|_| Error::Encoding)
+}
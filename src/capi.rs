@@ -0,0 +1,174 @@
+//! C API.
+//!
+//! This module exposes `extern "C"` functions and `repr(C)` types, so that this crate
+//! can be consumed from C and C++. The header can be generated using [`cbindgen`]
+//! with the `cbindgen.toml` configuration found in the repository root:
+//!
+//! ```console
+//! $ cbindgen --config cbindgen.toml --output pkce.h
+//! ```
+//!
+//! The library itself is built with `cargo rustc --features capi --crate-type cdylib`.
+//!
+//! Strings returned by this module are owned by the caller and must be released
+//! using [`pkce_free_string`] (or [`pkce_free_pair`] for pairs).
+//!
+//! [`cbindgen`]: https://github.com/mozilla/cbindgen
+
+use std::{
+    ffi::{CStr, CString, c_char},
+    ptr,
+};
+
+use crate::{api, error::Error};
+
+/// Represents status codes returned by the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PkceStatus {
+    /// The operation succeeded.
+    Ok = 0,
+    /// The verifier does not match the challenge.
+    Mismatch = 1,
+    /// The required pointer is null.
+    NullPointer = 2,
+    /// The string is not valid UTF-8.
+    Encoding = 3,
+    /// The verifier is invalid.
+    InvalidVerifier = 4,
+    /// The challenge is invalid.
+    InvalidChallenge = 5,
+    /// The method is unknown.
+    UnknownMethod = 6,
+    /// The operating system failed to provide entropy.
+    Entropy = 7,
+}
+
+/// Represents generated pairs of NUL-terminated strings.
+#[repr(C)]
+#[derive(Debug)]
+pub struct PkcePair {
+    /// The `code_verifier`.
+    pub verifier: *mut c_char,
+    /// The `code_challenge`.
+    pub challenge: *mut c_char,
+    /// The `code_challenge_method`.
+    pub method: *mut c_char,
+}
+
+fn into_raw(string: &str) -> *mut c_char {
+    // verifiers, challenges and methods never contain NUL bytes
+    CString::new(string).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Borrows the NUL-terminated string.
+///
+/// # Safety
+///
+/// The pointer must be either null or point to the valid NUL-terminated string.
+unsafe fn borrow<'s>(string: *const c_char) -> Result<&'s str, PkceStatus> {
+    if string.is_null() {
+        return Err(PkceStatus::NullPointer);
+    }
+
+    // SAFETY: the caller must ensure that `string` is NUL-terminated
+    let string = unsafe { CStr::from_ptr(string) };
+
+    string.to_str().map_err(|_| PkceStatus::Encoding)
+}
+
+/// Generates the pair using the default method and length, writing it into `output`.
+///
+/// # Safety
+///
+/// The `output` pointer must be either null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pkce_generate_pair(output: *mut PkcePair) -> PkceStatus {
+    if output.is_null() {
+        return PkceStatus::NullPointer;
+    }
+
+    let Ok(api::Pair {
+        verifier,
+        challenge,
+        method,
+    }) = api::generate_pair()
+    else {
+        return PkceStatus::Entropy;
+    };
+
+    let pair = PkcePair {
+        verifier: into_raw(&verifier),
+        challenge: into_raw(&challenge),
+        method: into_raw(&method),
+    };
+
+    // SAFETY: the caller must ensure that `output` is valid for writes
+    unsafe { output.write(pair) };
+
+    PkceStatus::Ok
+}
+
+/// Verifies the given verifier against the challenge using the method.
+///
+/// Returns [`PkceStatus::Ok`] if the verifier matches, and [`PkceStatus::Mismatch`] otherwise,
+/// unless any of the arguments is invalid.
+///
+/// # Safety
+///
+/// Each pointer must be either null or point to the valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pkce_verify(
+    verifier: *const c_char,
+    challenge: *const c_char,
+    method: *const c_char,
+) -> PkceStatus {
+    // SAFETY: the caller must ensure that the pointers are valid
+    let strings = unsafe { (borrow(verifier), borrow(challenge), borrow(method)) };
+
+    let (verifier, challenge, method) = match strings {
+        (Ok(verifier), Ok(challenge), Ok(method)) => (verifier, challenge, method),
+        (Err(status), _, _) | (_, Err(status), _) | (_, _, Err(status)) => return status,
+    };
+
+    match api::verify(verifier, challenge, method) {
+        Ok(true) => PkceStatus::Ok,
+        Ok(false) => PkceStatus::Mismatch,
+        Err(Error::Method(_)) => PkceStatus::UnknownMethod,
+        Err(Error::Challenge(_)) => PkceStatus::InvalidChallenge,
+        Err(_) => PkceStatus::InvalidVerifier,
+    }
+}
+
+/// Releases the string returned by this library.
+///
+/// # Safety
+///
+/// The pointer must be either null or returned by this library and not released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pkce_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the caller must ensure that `string` was returned by this library
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Releases the strings of the given pair, setting them to null.
+///
+/// # Safety
+///
+/// The pointer must be either null or point to the pair written by [`pkce_generate_pair`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pkce_free_pair(pair: *mut PkcePair) {
+    // SAFETY: the caller must ensure that `pair` is either null or valid
+    let Some(pair) = (unsafe { pair.as_mut() }) else {
+        return;
+    };
+
+    for string in [&mut pair.verifier, &mut pair.challenge, &mut pair.method] {
+        // SAFETY: the strings were returned by this library
+        unsafe { pkce_free_string(*string) };
+
+        *string = ptr::null_mut();
+    }
+}
@@ -4,53 +4,339 @@
 //!
 //! - [`Method::Plain`] uses the string as-is.
 //! - [`Method::Sha256`] hashes the string with SHA-256 and encodes the result.
+//! - [`Method::Custom`] delegates to the [`ChallengeMethod`] implementation.
 //!
 //! The string usually comes from the [`Verifier`], which creates the appropriate
 //! [`Challenge`] using the [`challenge`] method.
 //!
+//! Challenges received from clients can be constructed using [`Challenge::new`],
+//! which checks that the secret consists of valid characters and has valid length.
+//!
+//! Challenges are redacted when formatted with [`Debug`], unless the `expose-secrets` feature
+//! is enabled, since secrets of [`Method::Plain`] challenges are verifiers themselves.
+//!
+//! ```
+//! # #[cfg(not(feature = "expose-secrets"))]
+//! # {
+//! use pkce_std::{code::Code, length::Length, method::Method};
+//!
+//! let (_, challenge) = Code::generate_using(Method::Plain, Length::default()).into_pair();
+//!
+//! assert!(!format!("{challenge:?}").contains(challenge.secret()));
+//! # }
+//! ```
+//!
+//! With the `zeroize` feature enabled, challenge secrets are zeroed out on drop.
+//!
 //! [`challenge`]: Verifier::challenge
+//! [`ChallengeMethod`]: crate::method::ChallengeMethod
+
+#[cfg(feature = "lenient")]
+use std::borrow::Cow;
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+};
 
-use std::fmt;
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use ::serde::{Deserialize, Serialize};
+
+use thiserror::Error;
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[cfg(feature = "urlencoded")]
+use crate::query;
+
+use crate::{
+    check::string,
+    ct, encoding,
+    error::ErrorKind,
+    hash::{SHA256_LENGTH, const_sha256},
+    length::{self, Length},
+    method::{Method, UnsupportedError, challenge_length},
+    tagged::{self, SEPARATOR},
+    verifier::Verifier,
+};
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+/// Represents errors that can occur when constructing challenges.
+///
+/// There are four cases when constructing can fail:
+///
+/// - [`Length::check`] fails, which means that the length of the secret is invalid;
+/// - [`string::check`] fails, which means the secret contains invalid characters;
+/// - the length of the secret does not match the [`challenge_length`] of the method;
+/// - the method is unsupported, see [`MethodName::method`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// Invalid secret length.
+    #[error("invalid secret length")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::challenge::length),
+            help("check the length of the secret"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.2")
+        )
+    )]
+    Length(#[from] length::Error),
+
+    /// Invalid character(s) in secret.
+    #[error("secret contains invalid character(s)")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    String(#[from] string::Error),
 
-use crate::{encoding::encode, hash::sha256, method::Method, verifier::Verifier};
+    /// Secret length does not match the method.
+    #[error("expected secret of length `{expected}` for the method, got `{actual}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::challenge::mismatch),
+            help("make sure the secret was derived using the given method"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.2")
+        )
+    )]
+    Mismatch {
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        actual: usize,
+    },
+
+    /// The method is unsupported.
+    #[error("unsupported method")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Unsupported(#[from] UnsupportedError),
+}
+
+impl Error {
+    /// Constructs [`Self::Mismatch`].
+    pub const fn mismatch(expected: usize, actual: usize) -> Self {
+        Self::Mismatch { expected, actual }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Length(error) => error.kind(),
+            Self::String(error) => error.kind(),
+            Self::Mismatch { .. } => ErrorKind::Mismatch,
+            Self::Unsupported(error) => error.kind(),
+        }
+    }
+}
+
+/// The message used when panicking on methods that fail to derive valid challenges.
+pub const INVALID: &str = "the method failed to derive valid challenge";
+
+/// The `code_challenge` parameter name.
+pub const CODE_CHALLENGE: &str = "code_challenge";
+
+/// The `code_challenge_method` parameter name.
+pub const CODE_CHALLENGE_METHOD: &str = "code_challenge_method";
 
 /// Represents PKCE code challenges.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+///
+/// Challenges are compared in constant time.
+///
+/// Deserialized challenges are checked using [`Challenge::new`].
+#[derive(Clone)]
+#[cfg_attr(feature = "expose-secrets", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Unchecked")
+)]
 pub struct Challenge {
     secret: String,
     method: Method,
 }
 
+/// Represents challenges that are yet to be checked when deserializing.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(rename = "Challenge")]
+struct Unchecked {
+    secret: String,
+    method: Method,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Unchecked> for Challenge {
+    type Error = Error;
+
+    fn try_from(unchecked: Unchecked) -> Result<Self, Self::Error> {
+        Self::new(unchecked.secret, unchecked.method)
+    }
+}
+
 /// Represents PKCE code challenge parts.
 pub type Parts = (String, Method);
 
+#[cfg(not(feature = "expose-secrets"))]
+impl fmt::Debug for Challenge {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Challenge")
+            .field("length", &self.secret.len())
+            .field("method", &self.method)
+            .finish_non_exhaustive()
+    }
+}
+
 impl fmt::Display for Challenge {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.secret().fmt(formatter)
     }
 }
 
+impl PartialEq for Challenge {
+    fn eq(&self, other: &Self) -> bool {
+        let secret = ct::eq(self.secret(), other.secret());
+
+        secret & (self.method() == other.method())
+    }
+}
+
+impl Eq for Challenge {}
+
+impl AsRef<Self> for Challenge {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl Hash for Challenge {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.secret().hash(hasher);
+        self.method().hash(hasher);
+    }
+}
+
 impl Challenge {
     /// Returns the borrowed secret.
     pub const fn secret(&self) -> &str {
         self.secret.as_str()
     }
 
+    /// Writes the secret into the given writer without allocating.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`fmt::Error`] if the writer fails.
+    pub fn write_secret<W: fmt::Write + ?Sized>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str(self.secret())
+    }
+
     /// Returns the method used to generate the challenge.
     pub const fn method(&self) -> Method {
         self.method
     }
 
+    /// Returns the `code_challenge` and `code_challenge_method` query pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{challenge::Challenge, method::Method};
+    ///
+    /// let secret = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+    ///
+    /// let challenge = Challenge::new(secret.to_owned(), Method::Plain).unwrap();
+    ///
+    /// assert_eq!(
+    ///     challenge.query_pairs(),
+    ///     [("code_challenge", secret), ("code_challenge_method", "plain")],
+    /// );
+    /// ```
+    pub fn query_pairs(&self) -> [(&'static str, &str); 2] {
+        [
+            (CODE_CHALLENGE, self.secret()),
+            (CODE_CHALLENGE_METHOD, self.method().static_str()),
+        ]
+    }
+
+    /// Parses [`Self`] from `code_challenge` and `code_challenge_method` query parameters.
+    ///
+    /// Missing `code_challenge_method` defaults to [`Method::Plain`].
+    ///
+    /// Refer to the [`query`] module documentation for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`query::Error`] naming the parameter that is missing, duplicated or invalid.
+    ///
+    /// [`query`]: crate::query
+    #[cfg(feature = "urlencoded")]
+    pub fn from_query(query: &str) -> Result<Self, query::Error> {
+        let [secret, method] = query::find(query, [CODE_CHALLENGE, CODE_CHALLENGE_METHOD])?;
+
+        let secret = secret.ok_or(query::Error::missing(CODE_CHALLENGE))?;
+
+        let method = method.map_or(Ok(Method::Plain), |method| method.parse())?;
+
+        let challenge = Self::new(secret.into_owned(), method)?;
+
+        Ok(challenge)
+    }
+
     /// Consumes [`Self`] and returns its `(secret, method)` parts.
-    pub fn into_parts(self) -> Parts {
-        (self.secret, self.method)
+    pub fn into_parts(mut self) -> Parts {
+        (mem::take(&mut self.secret), self.method)
+    }
+
+    /// Formats [`Self`] as the `method:secret` string.
+    ///
+    /// See [`tagged`] for more information.
+    ///
+    /// [`tagged`]: crate::tagged
+    pub fn to_tagged_string(&self) -> String {
+        format!(
+            "{method}{SEPARATOR}{secret}",
+            method = self.method().static_str(),
+            secret = self.secret()
+        )
+    }
+
+    /// Parses [`Self`] from the `method:secret` string.
+    ///
+    /// See [`tagged`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tagged::Error::Format`] if the separator is missing, [`tagged::Error::Method`]
+    /// if the method is unknown, and [`tagged::Error::Challenge`] if the secret is invalid.
+    pub fn from_tagged_string(string: &str) -> Result<Self, tagged::Error> {
+        let (method, secret) = string.split_once(SEPARATOR).ok_or(tagged::Error::Format)?;
+
+        let method = method.parse()?;
+
+        let challenge = Self::new(secret.to_owned(), method)?;
+
+        Ok(challenge)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Challenge {
+    fn drop(&mut self) {
+        self.secret.zeroize();
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Challenge {}
+
 impl From<Challenge> for Parts {
     fn from(challenge: Challenge) -> Self {
         challenge.into_parts()
@@ -58,22 +344,140 @@ impl From<Challenge> for Parts {
 }
 
 impl Challenge {
-    const fn new(secret: String, method: Method) -> Self {
+    /// Constructs [`Self`], provided that the given secret is valid for the method.
+    ///
+    /// # Errors
+    ///
+    /// See [`check_secret`] for more information. In addition, returns [`Error::Mismatch`]
+    /// if the length of the secret does not match the [`challenge_length`] of the method.
+    ///
+    /// [`check_secret`]: Self::check_secret
+    pub fn new(secret: String, method: Method) -> Result<Self, Error> {
+        Self::check_secret(&secret)?;
+
+        if let Some(expected) = challenge_length(&method) {
+            let actual = secret.len();
+
+            if actual != expected {
+                return Err(Error::mismatch(expected, actual));
+            }
+        }
+
+        // SAFETY: `secret` is valid here
+        Ok(unsafe { Self::new_unchecked(secret, method) })
+    }
+
+    /// Constructs [`Self`] without checking the secret.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `secret` is valid and `method` is supported.
+    ///
+    /// The secret can be checked using [`Self::check_secret`].
+    pub const unsafe fn new_unchecked(secret: String, method: Method) -> Self {
         Self { secret, method }
     }
+
+    /// Checks if the given secret is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`enum@Error`] if the secret is invalid, which means either:
+    ///
+    /// - the length of the secret is invalid (see [`Length::check`]);
+    /// - the secret contains invalid character(s) (see [`string::check`]).
+    pub fn check_secret(secret: &str) -> Result<(), Error> {
+        Length::check(secret.len())?;
+
+        string::check_str(secret)?;
+
+        Ok(())
+    }
 }
 
+#[cfg(feature = "lenient")]
 impl Challenge {
-    /// Creates code challenges from the given verifier using the given method.
-    pub fn create_using(method: Method, verifier: &Verifier<'_>) -> Self {
-        let string = verifier.get();
+    /// Normalizes the given secret produced by non-conformant clients.
+    ///
+    /// This function strips trailing Base64 padding (`=`) and maps the standard alphabet
+    /// characters (`+` and `/`) to their URL-safe equivalents (`-` and `_`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::challenge::Challenge;
+    ///
+    /// assert_eq!(Challenge::normalize("a+b/c=="), "a-b_c");
+    /// ```
+    pub fn normalize(secret: &str) -> Cow<'_, str> {
+        let trimmed = secret.trim_end_matches('=');
 
-        let secret = match method {
-            Method::Plain => string.to_owned(),
-            Method::Sha256 => encode(sha256(string)),
+        if trimmed.contains(['+', '/']) {
+            let normalized = trimmed
+                .chars()
+                .map(|character| match character {
+                    '+' => '-',
+                    '/' => '_',
+                    other => other,
+                })
+                .collect();
+
+            Cow::Owned(normalized)
+        } else {
+            Cow::Borrowed(trimmed)
+        }
+    }
+
+    /// Similar to [`new`], except the secret is [`normalize`]d first,
+    /// unless the method is [`Method::Plain`].
+    ///
+    /// # Errors
+    ///
+    /// See [`new`] for more information.
+    ///
+    /// [`new`]: Self::new
+    /// [`normalize`]: Self::normalize
+    pub fn new_lenient(secret: &str, method: Method) -> Result<Self, Error> {
+        let secret = match &method {
+            Method::Plain => Cow::Borrowed(secret),
+            _ => Self::normalize(secret),
         };
 
-        Self::new(secret, method)
+        Self::new(secret.into_owned(), method)
+    }
+}
+
+impl Challenge {
+    /// Creates code challenges from the given verifier using the given method.
+    ///
+    /// Secrets derived by [`Method::Custom`] methods are checked using [`new`],
+    /// since [`ChallengeMethod`] implementations can not be trusted to derive valid ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`enum@Error`] if the custom method derives invalid secret.
+    ///
+    /// [`new`]: Self::new
+    /// [`ChallengeMethod`]: crate::method::ChallengeMethod
+    pub fn try_create_using(method: Method, verifier: &Verifier<'_>) -> Result<Self, Error> {
+        let secret = method.transform(verifier);
+
+        match method {
+            Method::Custom(_) => Self::new(secret, method),
+            // SAFETY: built-in methods derive valid secrets from verifiers
+            _ => Ok(unsafe { Self::new_unchecked(secret, method) }),
+        }
+    }
+
+    /// Creates code challenges from the given verifier using the given method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the custom method derives invalid secret, see [`try_create_using`].
+    ///
+    /// [`try_create_using`]: Self::try_create_using
+    pub fn create_using(method: Method, verifier: &Verifier<'_>) -> Self {
+        Self::try_create_using(method, verifier).expect(INVALID)
     }
 
     /// Creates code challenges from the given verifier using the default method.
@@ -81,3 +485,89 @@ impl Challenge {
         Self::create_using(Method::default(), verifier)
     }
 }
+
+/// The length of [`Method::Sha256`] challenge secrets.
+pub const SHA256_SECRET_LENGTH: usize = encoding::length(SHA256_LENGTH);
+
+/// Represents [`Method::Sha256`] challenges derived in `const` contexts.
+///
+/// Since [`Challenge`] owns its secret, it can not be constructed at compile time.
+/// This type stores the secret inline instead, and can be converted into [`Challenge`]
+/// at runtime via [`to_challenge`].
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::{challenge::ConstChallenge, const_borrowed_verifier, verifier::Verifier};
+///
+/// static VERIFIER: Verifier<'static> =
+///     const_borrowed_verifier!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+///
+/// const CHALLENGE: ConstChallenge = ConstChallenge::derive(&VERIFIER);
+///
+/// assert_eq!(CHALLENGE.to_challenge(), VERIFIER.challenge());
+/// ```
+///
+/// [`to_challenge`]: Self::to_challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstChallenge {
+    secret: [u8; SHA256_SECRET_LENGTH],
+}
+
+impl ConstChallenge {
+    /// Derives [`Self`] from the given verifier using [`Method::Sha256`].
+    pub const fn derive(verifier: &Verifier<'_>) -> Self {
+        let digest = const_sha256(verifier.const_get().as_bytes());
+
+        Self {
+            secret: encoding::const_encode(&digest),
+        }
+    }
+
+    /// Returns the borrowed secret.
+    pub const fn secret(&self) -> &str {
+        // SAFETY: the secret is the Base64 encoding, which is ASCII
+        unsafe { str::from_utf8_unchecked(&self.secret) }
+    }
+
+    /// Returns the method used to derive the challenge, which is always [`Method::Sha256`].
+    pub const fn method(&self) -> Method {
+        Method::Sha256
+    }
+
+    /// Converts [`Self`] into [`Challenge`].
+    pub fn to_challenge(&self) -> Challenge {
+        // SAFETY: the secret is derived from the verifier using the method
+        unsafe { Challenge::new_unchecked(self.secret().to_owned(), self.method()) }
+    }
+}
+
+impl From<ConstChallenge> for Challenge {
+    fn from(challenge: ConstChallenge) -> Self {
+        challenge.to_challenge()
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use crate::challenge::Challenge;
+
+    const SECRET: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    #[test]
+    fn deserialize_checked() {
+        let valid = format!(r#"{{"secret": "{SECRET}", "method": "plain"}}"#);
+
+        let challenge: Challenge = serde_json::from_str(&valid).unwrap();
+
+        assert_eq!(challenge.secret(), SECRET);
+
+        let invalid = r#"{"secret": "invalid", "method": "plain"}"#;
+
+        assert!(serde_json::from_str::<Challenge>(invalid).is_err());
+
+        let unknown = format!(r#"{{"secret": "{SECRET}", "method": "S3-512"}}"#);
+
+        assert!(serde_json::from_str::<Challenge>(&unknown).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! Serde adapters for embedding challenges into user-defined types.
+//!
+//! Refer to the [`flattened_params`] module for RFC parameter names.
+//!
+//! With the `serde-redacted` feature enabled, the [`redacted`] module serializes
+//! challenge secrets as their fingerprints.
+
+pub mod flattened_params {
+    //! Handling challenges as `code_challenge` and `code_challenge_method` parameters.
+    //!
+    //! This module is meant to be used along with `#[serde(flatten)]`, so that challenges
+    //! can be embedded into authorization requests as defined in the [standard].
+    //!
+    //! When deserializing, missing `code_challenge_method` defaults to [`Method::Plain`],
+    //! and the secret is validated using [`Challenge::new`].
+    //!
+    //! # Examples
+    //!
+    //! ```
+    //! use pkce_std::{
+    //!     Code,
+    //!     challenge::{self, Challenge},
+    //!     method::Method,
+    //! };
+    //! use serde::{Deserialize, Serialize};
+    //!
+    //! #[derive(Serialize, Deserialize)]
+    //! struct AuthorizationRequest {
+    //!     client_id: String,
+    //!     #[serde(flatten, with = "challenge::serde::flattened_params")]
+    //!     challenge: Challenge,
+    //! }
+    //!
+    //! let challenge = Code::generate_default().into_pair().1;
+    //!
+    //! let request = AuthorizationRequest {
+    //!     client_id: "client".to_owned(),
+    //!     challenge: challenge.clone(),
+    //! };
+    //!
+    //! let string = serde_json::to_string(&request).unwrap();
+    //!
+    //! assert!(string.contains(r#""code_challenge_method":"S256""#));
+    //!
+    //! let parsed: AuthorizationRequest = serde_json::from_str(&string).unwrap();
+    //!
+    //! assert_eq!(parsed.challenge, challenge);
+    //! ```
+    //!
+    //! [standard]: https://datatracker.ietf.org/doc/html/rfc7636#section-4.3
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use crate::{challenge::Challenge, method::Method};
+
+    #[derive(Serialize)]
+    struct Params<'p> {
+        code_challenge: &'p str,
+        code_challenge_method: Method,
+    }
+
+    #[derive(Deserialize)]
+    struct OwnedParams {
+        code_challenge: String,
+        #[serde(default = "plain")]
+        code_challenge_method: Method,
+    }
+
+    const fn plain() -> Method {
+        Method::Plain
+    }
+
+    /// Serializes the challenge as `code_challenge` and `code_challenge_method` parameters.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(
+        challenge: &Challenge,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let params = Params {
+            code_challenge: challenge.secret(),
+            code_challenge_method: challenge.method(),
+        };
+
+        params.serialize(serializer)
+    }
+
+    /// Deserializes the challenge from `code_challenge` and `code_challenge_method` parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer error if the parameters are missing or the secret is invalid.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Challenge, D::Error> {
+        let params = OwnedParams::deserialize(deserializer)?;
+
+        Challenge::new(params.code_challenge, params.code_challenge_method)
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde-redacted")]
+pub mod redacted {
+    //! Serializing challenges with their secrets replaced by fingerprints.
+    //!
+    //! Challenges derived using [`Method::Plain`] are the verifiers themselves, so they
+    //! must be redacted just like verifiers in debug snapshots and telemetry payloads.
+    //!
+    //! The output has the same shape as regular serialization, except that the secret
+    //! is the [`Fingerprint`] of it. Since fingerprints are not reversible, this module
+    //! only provides serialization.
+    //!
+    //! # Examples
+    //!
+    //! ```
+    //! use pkce_std::{
+    //!     challenge::{self, Challenge},
+    //!     fingerprint::Fingerprint,
+    //!     method::Method,
+    //! };
+    //! use serde::Serialize;
+    //!
+    //! #[derive(Serialize)]
+    //! struct Event {
+    //!     #[serde(with = "challenge::serde::redacted")]
+    //!     challenge: Challenge,
+    //! }
+    //!
+    //! let secret = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+    //!
+    //! let challenge = Challenge::new(secret.to_owned(), Method::Plain).unwrap();
+    //!
+    //! let string = serde_json::to_string(&Event { challenge }).unwrap();
+    //!
+    //! assert!(!string.contains(secret));
+    //! assert!(string.contains(&Fingerprint::compute(secret).to_string()));
+    //! ```
+    //!
+    //! [`Method::Plain`]: crate::method::Method::Plain
+    //! [`Fingerprint`]: crate::fingerprint::Fingerprint
+
+    use serde::{Serialize, Serializer};
+
+    use crate::{challenge::Challenge, fingerprint::Fingerprint, method::Method};
+
+    #[derive(Serialize)]
+    #[serde(rename = "Challenge")]
+    struct Redacted {
+        secret: Fingerprint,
+        method: Method,
+    }
+
+    /// Serializes the challenge, replacing the secret with its fingerprint.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(
+        challenge: &Challenge,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let redacted = Redacted {
+            secret: Fingerprint::compute(challenge.secret()),
+            method: challenge.method(),
+        };
+
+        redacted.serialize(serializer)
+    }
+}
@@ -0,0 +1,134 @@
+//! Character set of PKCE code verifiers.
+//!
+//! As per the [standard](https://datatracker.ietf.org/doc/html/rfc7636#section-4.1), code verifiers
+//! consist of alphanumeric characters and the following special characters: `-`, `.`, `_`, and `~`.
+//!
+//! The full character set is `ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~`.
+//!
+//! This module provides the set as characters ([`CHARS`]), bytes ([`BYTES`]) and the string
+//! ([`STRING`]), along with the [`is_valid`] and [`is_valid_char`] predicates, which are backed
+//! by the [`TABLE`] lookup table.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::charset::{self, BYTES, CHARS, STRING};
+//!
+//! assert_eq!(CHARS.len(), charset::LENGTH);
+//! assert_eq!(STRING.as_bytes(), BYTES);
+//!
+//! assert!(charset::is_valid(b'~'));
+//! assert!(!charset::is_valid_char('+'));
+//! ```
+
+use std::str::from_utf8;
+
+use const_macros::{const_assert_eq, const_assert_ne};
+
+/// The amount of valid characters in PKCE code verifiers.
+pub const LENGTH: usize = 66;
+
+// constantly assert that the length is non-zero (required for `generate::string` to be safe)
+const_assert_ne!(LENGTH, 0);
+
+/// The characters used in PKCE code verifiers.
+#[rustfmt::skip]
+pub const CHARS: [char; LENGTH] = [
+    // upper
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J',
+    'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T',
+    'U', 'V', 'W', 'X', 'Y', 'Z',
+    // lower
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j',
+    'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't',
+    'u', 'v', 'w', 'x', 'y', 'z',
+    // digit
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+    // special
+    '-', '.', '_', '~',
+];
+
+/// The byte representation of [`CHARS`].
+pub const BYTES: &[u8; LENGTH] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
+
+/// The string representation of [`CHARS`].
+pub const STRING: &str = match from_utf8(BYTES) {
+    Ok(string) => string,
+    Err(_) => panic!("the character set is ASCII"),
+};
+
+const_assert_eq!(STRING.len(), LENGTH);
+
+/// The lookup table of valid bytes, indexed by bytes.
+pub const TABLE: [bool; 256] = table();
+
+const fn table() -> [bool; 256] {
+    let mut table = [false; 256];
+
+    let mut index = 0;
+
+    while index < LENGTH {
+        table[BYTES[index] as usize] = true;
+
+        index += 1;
+    }
+
+    table
+}
+
+macro_rules! special_pattern {
+    () => {
+        b'-' | b'.' | b'_' | b'~'
+    };
+}
+
+/// Checks if the given byte is special, i.e. one of `-`, `.`, `_`, or `~`.
+pub const fn is_special(byte: u8) -> bool {
+    matches!(byte, special_pattern!())
+}
+
+/// Checks if the given byte is valid, i.e. either alphanumeric or special.
+pub const fn is_valid(byte: u8) -> bool {
+    TABLE[byte as usize]
+}
+
+/// Checks if the given character is valid, i.e. either ASCII alphanumeric or special.
+pub const fn is_valid_char(character: char) -> bool {
+    // the cast is lossless for ASCII characters
+    character.is_ascii() && is_valid(character as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::charset::{BYTES, CHARS, STRING, is_special, is_valid, is_valid_char};
+
+    #[test]
+    fn equality() {
+        let string: String = CHARS.into_iter().collect();
+
+        assert_eq!(string, STRING);
+        assert_eq!(string.as_bytes(), BYTES);
+    }
+
+    #[test]
+    fn validity() {
+        assert!(STRING.bytes().all(is_valid));
+    }
+
+    proptest! {
+        #[test]
+        fn table(byte in any::<u8>()) {
+            let expected = byte.is_ascii_alphanumeric() || is_special(byte);
+
+            prop_assert_eq!(is_valid(byte), expected);
+        }
+
+        #[test]
+        fn chars(character in any::<char>()) {
+            prop_assert_eq!(is_valid_char(character), CHARS.contains(&character));
+        }
+    }
+}
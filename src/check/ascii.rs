@@ -7,16 +7,29 @@ use miette::Diagnostic;
 
 use thiserror::Error;
 
+use crate::error::ErrorKind;
+
 /// Represents errors that occur when non-ASCII strings are encountered.
 #[derive(Debug, Error)]
 #[error("non-ascii string encountered")]
 #[cfg_attr(
     feature = "diagnostics",
     derive(Diagnostic),
-    diagnostic(code(pkce_std::check::ascii), help("ensure the string is ASCII"))
+    diagnostic(
+        code(pkce_std::check::ascii),
+        help("ensure the string is ASCII"),
+        url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+    )
 )]
 pub struct Error;
 
+impl Error {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::NonAscii
+    }
+}
+
 /// Checks that the given string is ASCII.
 ///
 /// # Examples
@@ -7,13 +7,19 @@ use miette::Diagnostic;
 
 use thiserror::Error;
 
+use crate::{charset, error::ErrorKind};
+
 /// Represents errors that occur when invalid bytes are encountered.
 #[derive(Debug, Error)]
 #[error("invalid byte `{byte}` encountered")]
 #[cfg_attr(
     feature = "diagnostics",
     derive(Diagnostic),
-    diagnostic(code(pkce_std::check::bytes), help("ensure the byte is valid"))
+    diagnostic(
+        code(pkce_std::check::bytes),
+        help("ensure the byte is valid"),
+        url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+    )
 )]
 pub struct Error {
     /// The invalid byte.
@@ -25,22 +31,23 @@ impl Error {
     pub const fn new(byte: u8) -> Self {
         Self { byte }
     }
-}
 
-macro_rules! special_pattern {
-    () => {
-        b'-' | b'.' | b'_' | b'~'
-    };
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::InvalidCharacter
+    }
 }
 
 /// Checks if the given byte is special, i.e. one of `-`, `.`, `_`, or `~`.
+#[deprecated(since = "0.3.0", note = "use `charset::is_special` instead")]
 pub const fn is_special(byte: u8) -> bool {
-    matches!(byte, special_pattern!())
+    charset::is_special(byte)
 }
 
 /// Checks if the given byte is valid, i.e. either alphanumeric or special.
+#[deprecated(since = "0.3.0", note = "use `charset::is_valid` instead")]
 pub const fn is_valid(byte: u8) -> bool {
-    byte.is_ascii_alphanumeric() || is_special(byte)
+    charset::is_valid(byte)
 }
 
 /// Checks that the given byte is valid.
@@ -61,7 +68,7 @@ pub const fn is_valid(byte: u8) -> bool {
 ///
 /// Returns [`struct@Error`] if the byte is invalid.
 pub const fn check(byte: u8) -> Result<(), Error> {
-    const_early!(!is_valid(byte) => Error::new(byte));
+    const_early!(!charset::is_valid(byte) => Error::new(byte));
 
     Ok(())
 }
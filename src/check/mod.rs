@@ -2,7 +2,8 @@
 
 pub mod ascii;
 pub mod bytes;
+#[deprecated(since = "0.3.0", note = "use the `charset` module instead")]
 pub mod chars;
 pub mod string;
 
-pub use string::{check, check_str, const_check_str};
+pub use string::{check, check_all, check_all_str, check_str, const_check_str};
@@ -1,81 +1,195 @@
 //! Checking PKCE code verifier strings.
 
-use const_macros::{const_map_err, const_try};
+#[cfg(feature = "diagnostics")]
+use std::{fmt, iter};
 
 #[cfg(feature = "diagnostics")]
-use miette::Diagnostic;
+use miette::{Diagnostic, LabeledSpan};
 
 use thiserror::Error;
 
-use crate::check::{ascii, bytes};
+use crate::{
+    check::{ascii, bytes},
+    error::ErrorKind,
+};
 
 /// Represents sources of errors that can occur when checking strings.
+///
+/// Both variants contain the byte `index` and `length` of the offending character,
+/// which are used to label the exact span of it when the `diagnostics` feature is enabled.
+///
+/// Since this error is returned from `const` functions, it can not own the checked string.
+/// Use [`with_source_code`] on the [`Report`] created from this error to highlight
+/// the offending character in the checked string.
+///
+/// [`with_source_code`]: https://docs.rs/miette/latest/miette/struct.Report.html#method.with_source_code
+/// [`Report`]: https://docs.rs/miette/latest/miette/struct.Report.html
 #[derive(Debug, Error)]
 #[error("invalid string encountered")]
-#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
 pub enum Error {
     /// Non-ASCII string encountered.
-    #[cfg_attr(
-        feature = "diagnostics",
-        diagnostic(
-            code(pkce_std::check::string::ascii),
-            help("ensure the string is ASCII")
-        )
-    )]
-    Ascii(#[from] ascii::Error),
+    Ascii {
+        /// The source of this error.
+        source: ascii::Error,
+        /// The byte index of the first non-ASCII character.
+        index: usize,
+        /// The byte length of the first non-ASCII character.
+        length: usize,
+    },
     /// Invalid byte encountered.
-    #[cfg_attr(
-        feature = "diagnostics",
-        diagnostic(code(pkce_std::check::string::byte), help("ensure the byte is valid"))
-    )]
-    Bytes(#[from] bytes::Error),
+    Bytes {
+        /// The source of this error.
+        source: bytes::Error,
+        /// The byte index of the invalid byte.
+        index: usize,
+    },
 }
 
-/// Recursively checks that the given string contains valid characters only.
-///
-/// One may need to increase the recursion limit manually to use this `const` function.
-///
-/// This is done via applying the `recursion_limit` attribute to the crate:
-///
-/// ```
-/// #![recursion_limit = "256"]
-/// ```
+/// The URL of the section describing valid verifier characters.
+#[cfg(feature = "diagnostics")]
+const URL: &str = "https://datatracker.ietf.org/doc/html/rfc7636#section-4.1";
+
+// implemented manually to label spans computed from indices and lengths
+#[cfg(feature = "diagnostics")]
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let code = match self {
+            Self::Ascii { .. } => "pkce_std::check::string::ascii",
+            Self::Bytes { .. } => "pkce_std::check::string::byte",
+        };
+
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let help = match self {
+            Self::Ascii { .. } => "ensure the string is ASCII",
+            Self::Bytes { .. } => "ensure the byte is valid",
+        };
+
+        Some(Box::new(help))
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(URL))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = match self {
+            Self::Ascii { .. } => "non-ascii character",
+            Self::Bytes { .. } => "invalid character",
+        };
+
+        let span = LabeledSpan::at((self.index(), self.length()), label);
+
+        Some(Box::new(iter::once(span)))
+    }
+}
+
+impl Error {
+    /// Constructs [`Self::Ascii`].
+    pub const fn ascii(source: ascii::Error, index: usize, length: usize) -> Self {
+        Self::Ascii {
+            source,
+            index,
+            length,
+        }
+    }
+
+    /// Constructs [`Self::Bytes`].
+    pub const fn bytes(source: bytes::Error, index: usize) -> Self {
+        Self::Bytes { source, index }
+    }
+
+    /// Returns the byte index of the offending character.
+    pub const fn index(&self) -> usize {
+        match self {
+            Self::Ascii { index, .. } | Self::Bytes { index, .. } => *index,
+        }
+    }
+
+    /// Returns the byte length of the offending character.
+    pub const fn length(&self) -> usize {
+        match self {
+            Self::Ascii { length, .. } => *length,
+            Self::Bytes { .. } => 1,
+        }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Ascii { .. } => ErrorKind::NonAscii,
+            Self::Bytes { .. } => ErrorKind::InvalidCharacter,
+        }
+    }
+}
+
+/// Returns the byte length of the UTF-8 character starting with the given byte.
+const fn char_length(byte: u8) -> usize {
+    match byte.leading_ones() {
+        0 => 1,
+        ones => ones as usize,
+    }
+}
+
+/// Checks that the given string contains valid characters only in `const` contexts.
 ///
 /// # Errors
 ///
 /// Returns [`enum@Error`] on non-ASCII or otherwise invalid strings.
 pub const fn const_check_str(string: &str) -> Result<(), Error> {
-    pub const fn check_bytes(bytes: &[u8]) -> Result<(), bytes::Error> {
-        match *bytes {
-            [] => Ok(()),
-            [byte, ref rest @ ..] => {
-                const_try!(bytes::check(byte));
+    let bytes = string.as_bytes();
 
-                check_bytes(rest)
-            }
+    if let Err(source) = ascii::check_str(string) {
+        let mut index = 0;
+
+        while bytes[index].is_ascii() {
+            index += 1;
         }
+
+        return Err(Error::ascii(source, index, char_length(bytes[index])));
     }
 
-    const_try!(const_map_err!(ascii::check_str(string) => Error::Ascii));
+    let mut index = 0;
 
-    const_map_err!(check_bytes(string.as_bytes()) => Error::Bytes)
+    while index < bytes.len() {
+        if let Err(source) = bytes::check(bytes[index]) {
+            return Err(Error::bytes(source, index));
+        }
+
+        index += 1;
+    }
+
+    Ok(())
 }
 
-/// Iterarively checks that the given string contains valid characters only.
+/// Iteratively checks that the given string contains valid characters only.
 ///
 /// # Errors
 ///
 /// Returns [`enum@Error`] on non-ASCII or otherwise invalid codes.
 pub fn check_str(string: &str) -> Result<(), Error> {
-    fn check_bytes(bytes: &[u8]) -> Result<(), bytes::Error> {
-        bytes.iter().copied().try_for_each(bytes::check)
+    fn check_bytes(bytes: &[u8]) -> Result<(), Error> {
+        bytes
+            .iter()
+            .copied()
+            .enumerate()
+            .try_for_each(|(index, byte)| {
+                bytes::check(byte).map_err(|source| Error::bytes(source, index))
+            })
     }
 
-    ascii::check(string)?;
+    ascii::check(string).map_err(|source| {
+        let (index, character) = string
+            .char_indices()
+            .find(|(_, character)| !character.is_ascii())
+            .unwrap_or_default();
 
-    check_bytes(string.as_bytes())?;
+        Error::ascii(source, index, character.len_utf8())
+    })?;
 
-    Ok(())
+    check_bytes(string.as_bytes())
 }
 
 /// Similar to [`check_str`], except it is generic over [`AsRef<str>`].
@@ -86,3 +200,55 @@ pub fn check_str(string: &str) -> Result<(), Error> {
 pub fn check<S: AsRef<str>>(string: S) -> Result<(), Error> {
     check_str(string.as_ref())
 }
+
+/// Checks that the given string contains valid characters only, collecting every invalid one.
+///
+/// Unlike [`check_str`], which stops at the first invalid character, this function
+/// reports all of them in the order they appear in, which is useful for diagnosing
+/// corrupted strings, for instance, ones found in logs.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::check::check_all_str;
+///
+/// let errors = check_all_str("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4g!ekl0é").unwrap_err();
+///
+/// let indices: Vec<_> = errors.iter().map(|error| error.index()).collect();
+///
+/// assert_eq!(indices, [36, 41]);
+/// ```
+///
+/// # Errors
+///
+/// Returns the non-empty [`Vec`] of [`enum@Error`] values, one per invalid character.
+pub fn check_all_str(string: &str) -> Result<(), Vec<Error>> {
+    let errors: Vec<_> = string
+        .char_indices()
+        .filter_map(|(index, character)| {
+            if character.is_ascii() {
+                // ASCII characters are exactly one byte long
+                bytes::check(character as u8)
+                    .err()
+                    .map(|source| Error::bytes(source, index))
+            } else {
+                Some(Error::ascii(ascii::Error, index, character.len_utf8()))
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Similar to [`check_all_str`], except it is generic over [`AsRef<str>`].
+///
+/// # Errors
+///
+/// Any errors in [`check_all_str`] are propagated.
+pub fn check_all<S: AsRef<str>>(string: S) -> Result<(), Vec<Error>> {
+    check_all_str(string.as_ref())
+}
@@ -0,0 +1,160 @@
+//! Command-line integration via [`clap`].
+//!
+//! This module implements [`ValueEnum`] for [`Method`] and [`ValueParserFactory`]
+//! for [`Length`] and [`Count`], so that command-line tools get validated arguments for free.
+//!
+//! Out-of-range lengths and counts are reported along with the valid range.
+//!
+//! # Examples
+//!
+//! ```
+//! use clap::{Arg, Command, value_parser};
+//! use pkce_std::{length::Length, method::Method};
+//!
+//! let command = Command::new("pkce")
+//!     .arg(Arg::new("method").long("method").value_parser(value_parser!(Method)))
+//!     .arg(Arg::new("length").long("length").value_parser(value_parser!(Length)));
+//!
+//! let matches = command
+//!     .clone()
+//!     .try_get_matches_from(["pkce", "--method", "S256", "--length", "64"])
+//!     .unwrap();
+//!
+//! assert_eq!(matches.get_one::<Method>("method"), Some(&Method::Sha256));
+//! assert_eq!(matches.get_one::<Length>("length").unwrap().get(), 64);
+//!
+//! let error = command
+//!     .try_get_matches_from(["pkce", "--length", "40"])
+//!     .unwrap_err();
+//!
+//! assert!(error.to_string().contains("at least `43`"));
+//! ```
+//!
+//! [`clap`]: https://docs.rs/clap
+
+use std::{ffi::OsStr, fmt};
+
+use clap::{
+    Arg, Command, ValueEnum,
+    builder::{PossibleValue, TypedValueParser, ValueParserFactory},
+    error::{Error, ErrorKind},
+};
+
+use crate::{
+    count::Count,
+    length::Length,
+    method::{self, Method},
+};
+
+const METHODS: &[Method] = &[
+    Method::Plain,
+    Method::Sha256,
+    #[cfg(feature = "extended-methods")]
+    Method::Sha384,
+    #[cfg(feature = "extended-methods")]
+    Method::Sha512,
+];
+
+impl ValueEnum for Method {
+    fn value_variants<'a>() -> &'a [Self] {
+        METHODS
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let name = match self {
+            Self::Plain => method::PLAIN,
+            Self::Sha256 => method::SHA256,
+            #[cfg(feature = "extended-methods")]
+            Self::Sha384 => method::SHA384,
+            #[cfg(feature = "extended-methods")]
+            Self::Sha512 => method::SHA512,
+            Self::Custom(_) => return None,
+        };
+
+        Some(PossibleValue::new(name))
+    }
+}
+
+fn invalid<E: fmt::Display>(command: &Command, argument: Option<&Arg>, error: E) -> Error {
+    let message = argument.map_or_else(
+        || format!("invalid value: {error}\n"),
+        |argument| format!("invalid value for `{argument}`: {error}\n"),
+    );
+
+    Error::raw(ErrorKind::ValueValidation, message).with_cmd(command)
+}
+
+fn parse<T, E: fmt::Display>(
+    command: &Command,
+    argument: Option<&Arg>,
+    value: &OsStr,
+    function: fn(usize) -> Result<T, E>,
+) -> Result<T, Error> {
+    let string = value
+        .to_str()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(command))?;
+
+    let value = string
+        .parse()
+        .map_err(|error| invalid(command, argument, error))?;
+
+    function(value).map_err(|error| invalid(command, argument, error))
+}
+
+/// Represents value parsers for [`Length`] values.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LengthParser;
+
+impl TypedValueParser for LengthParser {
+    type Value = Length;
+
+    fn parse_ref(
+        &self,
+        command: &Command,
+        argument: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        parse(command, argument, value, Length::new)
+    }
+}
+
+impl ValueParserFactory for Length {
+    type Parser = LengthParser;
+
+    fn value_parser() -> Self::Parser {
+        LengthParser
+    }
+}
+
+/// Represents value parsers for [`Count`] values.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CountParser;
+
+impl TypedValueParser for CountParser {
+    type Value = Count;
+
+    fn parse_ref(
+        &self,
+        command: &Command,
+        argument: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        parse(command, argument, value, Count::new)
+    }
+}
+
+impl ValueParserFactory for Count {
+    type Parser = CountParser;
+
+    fn value_parser() -> Self::Parser {
+        CountParser
+    }
+}
@@ -0,0 +1,115 @@
+//! Pluggable time sources for expiry logic.
+//!
+//! All expiry functionality in this crate reads the current time through the [`Clock`] trait,
+//! defaulting to [`SystemClock`]. Tests can use [`ManualClock`] to control time explicitly,
+//! while embedded targets can implement [`Clock`] for their own time sources.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//!
+//! use pkce_std::{
+//!     clock::{Clock, ManualClock},
+//!     code::Code,
+//!     outcome::{Check, VerifyOutcome},
+//! };
+//!
+//! let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+//!
+//! let deadline = clock.now() + Duration::from_secs(60);
+//!
+//! let outcome = VerifyOutcome::new().expiry_using(deadline, &clock);
+//!
+//! assert!(outcome.is_accepted());
+//!
+//! clock.advance(Duration::from_secs(60));
+//!
+//! let outcome = VerifyOutcome::new().expiry_using(deadline, &clock);
+//!
+//! assert!(outcome.has_failed(Check::Expiry));
+//! ```
+
+use std::{
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+/// Represents sources of the current time.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> SystemTime {
+        C::now(self)
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Box<C> {
+    fn now(&self) -> SystemTime {
+        C::now(self)
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> SystemTime {
+        C::now(self)
+    }
+}
+
+/// Represents the system clock, which is the default [`Clock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Represents clocks that only move when told to.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<SystemTime>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new(SystemTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.lock()
+    }
+}
+
+impl ManualClock {
+    /// Constructs [`Self`] starting at the given time.
+    pub const fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SystemTime> {
+        self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sets the current time.
+    pub fn set(&self, now: SystemTime) {
+        *self.lock() = now;
+    }
+
+    /// Moves the current time forward by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        *self.lock() += duration;
+    }
+}
@@ -6,8 +6,8 @@
 //! imposes the following invariant: the verifier and challenge are always generated together and
 //! the challenge corresponds to the verifier, meaning `verifier.verify(&challenge)` is always true.
 //!
-//! Therefore it is not possible to access the verifier or challenge separately, and one should use
-//! [`into_pair`] to get both parts, consuming the [`Code`] value.
+//! Therefore both parts can be borrowed via [`verifier`] and [`challenge`], but they can only be
+//! extracted together, using [`into_pair`] that consumes the [`Code`] value.
 //!
 //! # Examples
 //!
@@ -22,56 +22,273 @@
 //! assert!(verifier.verify(&challenge));
 //! ```
 //!
+//! Codes can also be decomposed into named [`Parts`] using [`into_parts`] for storage,
+//! and then reconstructed via [`from_parts`], which validates the parts again.
+//!
+//! [`verifier`]: Code::verifier
+//! [`challenge`]: Code::challenge
 //! [`into_pair`]: Code::into_pair
+//! [`into_parts`]: Code::into_parts
+//! [`from_parts`]: Code::from_parts
 
 use std::borrow::Cow;
 
+#[cfg(not(feature = "expose-secrets"))]
+use std::fmt;
+
 #[cfg(feature = "static")]
 use into_static::IntoStatic;
 
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "rand")]
+use rand::{CryptoRng, Rng, TryRngCore, distr::Distribution, rngs::OsRng};
+
+use thiserror::Error;
+
+#[cfg(feature = "tokio")]
+use tokio::task::{self, JoinError};
+
+#[cfg(feature = "zeroize")]
+use zeroize::ZeroizeOnDrop;
+
+#[cfg(feature = "events")]
+use crate::events;
+
+#[cfg(feature = "testing")]
+use crate::testing::test_rng;
+
+#[cfg(feature = "rand")]
+use crate::distr::CodeDistribution;
+
 use crate::{
-    challenge::Challenge, count::Count, length::Length, method::Method, verifier::Verifier,
+    challenge::{self, Challenge},
+    count::Count,
+    ct,
+    error::ErrorKind,
+    fingerprint::Fingerprint,
+    length::Length,
+    method::Method,
+    params::{AuthorizationParams, TokenParams},
+    verifier::{self, Verifier},
 };
 
+/// Represents errors that can occur when constructing codes from [`Parts`].
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// Invalid verifier.
+    #[error("invalid verifier")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::code::verifier), help("check the verifier"))
+    )]
+    Verifier(#[from] verifier::Error),
+
+    /// The secret does not correspond to the verifier.
+    #[error("secret does not match the verifier")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::code::mismatch),
+            help("make sure the secret was derived from the verifier using the method")
+        )
+    )]
+    Mismatch,
+
+    /// The method failed to derive valid challenge.
+    #[error("invalid challenge")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Challenge(#[from] challenge::Error),
+}
+
+impl Error {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Verifier(error) => error.kind(),
+            Self::Mismatch => ErrorKind::Mismatch,
+            Self::Challenge(error) => error.kind(),
+        }
+    }
+}
+
 /// Represents coupled [`Verifier`] and [`Challenge`] pairs.
 ///
 /// Refer to the [module] documentation for more information.
 ///
 /// [module]: self
-#[derive(Debug, Clone)]
+#[derive(Clone)]
+#[cfg_attr(feature = "expose-secrets", derive(Debug))]
 pub struct Code<'c> {
     verifier: Verifier<'c>,
     challenge: Challenge,
 }
 
+#[cfg(not(feature = "expose-secrets"))]
+impl fmt::Debug for Code<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Code")
+            .field("length", &self.verifier.get().len())
+            .field("method", &self.challenge.method())
+            .finish_non_exhaustive()
+    }
+}
+
+// both the verifier and the challenge are zeroed out on drop
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Code<'_> {}
+
+#[cfg(feature = "rand")]
+impl Distribution<Code<'static>> for CodeDistribution {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Code<'static> {
+        let verifier = Verifier::sample(rng, self.length());
+        Code::generated(verifier, self.method())
+    }
+}
+
 impl<'c> Code<'c> {
-    const fn new(verifier: Verifier<'c>, challenge: Challenge) -> Self {
+    pub(crate) const fn new(verifier: Verifier<'c>, challenge: Challenge) -> Self {
         Self {
             verifier,
             challenge,
         }
     }
 
+    /// Computes the challenge of the generated verifier using the given method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge.
+    pub(crate) fn generated(verifier: Verifier<'c>, method: Method) -> Self {
+        let challenge = verifier.challenge_using(method);
+
+        #[cfg(feature = "events")]
+        events::code(&verifier, challenge.method());
+
+        Self::new(verifier, challenge)
+    }
+
+    /// Returns the verifier of the code.
+    pub const fn verifier(&self) -> &Verifier<'c> {
+        &self.verifier
+    }
+
+    /// Returns the challenge of the code.
+    pub const fn challenge(&self) -> &Challenge {
+        &self.challenge
+    }
+
+    /// Computes the log-safe [`Fingerprint`] of the verifier.
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.verifier.fingerprint()
+    }
+
     /// Consumes [`Self`], returning the `(verifier, challenge)` pair.
     pub fn into_pair(self) -> Pair<'c> {
         (self.verifier, self.challenge)
     }
 
-    /// Consumes [`Self`], returning `(verifier, secret, method)` parts.
+    /// Consumes [`Self`], returning its [`Parts`].
     pub fn into_parts(self) -> Parts<'c> {
         let (secret, method) = self.challenge.into_parts();
 
-        (self.verifier.take(), secret, method)
+        Parts::new(self.verifier.take(), secret, method)
+    }
+
+    /// Consumes [`Self`], returning the [`TokenParams`] and [`AuthorizationParams`] pair.
+    pub fn into_params(self) -> (TokenParams<'c>, AuthorizationParams) {
+        (self.verifier.into(), self.challenge.into())
+    }
+
+    /// Constructs [`Self`] from the given [`Parts`], validating them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Verifier`] if the verifier is invalid, [`Error::Challenge`]
+    /// if the method fails to derive valid challenge, and [`Error::Mismatch`] if the secret
+    /// does not correspond to the verifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::code::Code;
+    ///
+    /// let mut parts = Code::generate_default().into_parts();
+    ///
+    /// assert!(Code::from_parts(parts.clone()).is_ok());
+    ///
+    /// parts.secret = Code::generate_default().into_parts().secret;
+    ///
+    /// assert!(Code::from_parts(parts).is_err());
+    /// ```
+    pub fn from_parts(parts: Parts<'c>) -> Result<Self, Error> {
+        let verifier = Verifier::new(parts.verifier)?;
+
+        let challenge = verifier.try_challenge_using(parts.method)?;
+
+        if !ct::eq(challenge.secret(), &parts.secret) {
+            return Err(Error::Mismatch);
+        }
+
+        Ok(Self::new(verifier, challenge))
     }
 }
 
 impl Code<'_> {
     /// Generates [`Self`] using the given method and length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge, see [`try_generate_using`].
+    ///
+    /// [`try_generate_using`]: Self::try_generate_using
     pub fn generate_using(method: Method, length: Length) -> Self {
         let verifier = Verifier::generate(length);
-        let challenge = verifier.challenge_using(method);
+        Self::generated(verifier, method)
+    }
 
-        Self::new(verifier, challenge)
+    /// Generates [`Self`] using the given method and length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`challenge::Error`] if the method fails to derive valid challenge,
+    /// see [`Challenge::try_create_using`].
+    pub fn try_generate_using(method: Method, length: Length) -> Result<Self, challenge::Error> {
+        let verifier = Verifier::generate(length);
+
+        let challenge = verifier.try_challenge_using(method)?;
+
+        #[cfg(feature = "events")]
+        events::code(&verifier, challenge.method());
+
+        Ok(Self::new(verifier, challenge))
+    }
+
+    /// Generates [`Self`] using the given generator, method and length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge.
+    #[cfg(feature = "rand")]
+    pub fn generate_using_with_rng<R: CryptoRng + Rng + ?Sized>(
+        rng: &mut R,
+        method: Method,
+        length: Length,
+    ) -> Self {
+        let verifier = Verifier::generate_with_rng(rng, length);
+        Self::generated(verifier, method)
+    }
+
+    /// Generates [`Self`] using the given generator, the default method and the given length.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: CryptoRng + Rng + ?Sized>(rng: &mut R, length: Length) -> Self {
+        Self::generate_using_with_rng(rng, Method::default(), length)
     }
 
     /// Generates [`Self`] using the default method and the given length.
@@ -84,12 +301,65 @@ impl Code<'_> {
         Self::generate(Length::default())
     }
 
+    /// Generates [`Self`] using the default method and the maximum length ([`Length::MAX`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{code::Code, length::Length};
+    ///
+    /// let (verifier, challenge) = Code::generate_max().into_pair();
+    ///
+    /// assert_eq!(verifier.get().len(), Length::MAX.get());
+    /// assert!(verifier.verify(&challenge));
+    /// ```
+    pub fn generate_max() -> Self {
+        Self::generate(Length::MAX)
+    }
+
+    /// Generates reproducible [`Self`] using the default method
+    /// and the given length from the given `seed`.
+    ///
+    /// This is intended for tests and fixtures only, see [`testing`] for more information.
+    ///
+    /// [`testing`]: crate::testing
+    #[cfg(feature = "testing")]
+    pub fn generate_seeded(seed: u64, length: Length) -> Self {
+        Self::generate_with_rng(&mut test_rng(seed), length)
+    }
+
     /// Generates [`Self`] using the given method and bytes count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge.
     pub fn generate_encode_using(method: Method, count: Count) -> Self {
         let verifier = Verifier::generate_encode(count);
-        let challenge = verifier.challenge_using(method);
+        Self::generated(verifier, method)
+    }
 
-        Self::new(verifier, challenge)
+    /// Generates [`Self`] using the given generator, method and bytes count.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge.
+    #[cfg(feature = "rand")]
+    pub fn generate_encode_using_with_rng<R: CryptoRng + Rng + ?Sized>(
+        rng: &mut R,
+        method: Method,
+        count: Count,
+    ) -> Self {
+        let verifier = Verifier::generate_encode_with_rng(rng, count);
+        Self::generated(verifier, method)
+    }
+
+    /// Generates [`Self`] using the given generator, the default method and the given bytes count.
+    #[cfg(feature = "rand")]
+    pub fn generate_encode_with_rng<R: CryptoRng + Rng + ?Sized>(
+        rng: &mut R,
+        count: Count,
+    ) -> Self {
+        Self::generate_encode_using_with_rng(rng, Method::default(), count)
     }
 
     /// Generates [`Self`] using the default method and the given bytes count.
@@ -101,6 +371,73 @@ impl Code<'_> {
     pub fn generate_encode_default() -> Self {
         Self::generate_encode(Count::default())
     }
+
+    /// Generates [`Self`] using the default method and the maximum bytes count ([`Count::MAX`]).
+    pub fn generate_encode_max() -> Self {
+        Self::generate_encode(Count::MAX)
+    }
+
+    /// Generates [`Self`] using the default method and the given length,
+    /// drawing entropy directly from the operating system via [`OsRng`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    #[cfg(feature = "rand")]
+    pub fn generate_secure(length: Length) -> Self {
+        Self::generate_with_rng(&mut OsRng.unwrap_err(), length)
+    }
+
+    /// Generates [`Self`] using the default method and the given bytes count,
+    /// drawing entropy directly from the operating system via [`OsRng`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    #[cfg(feature = "rand")]
+    pub fn generate_encode_secure(count: Count) -> Self {
+        Self::generate_encode_with_rng(&mut OsRng.unwrap_err(), count)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Code<'static> {
+    /// Generates [`Self`] on the blocking thread pool using the given method and length.
+    ///
+    /// This is useful for runtimes that forbid any synchronous RNG or hashing work
+    /// on the reactor threads. The synchronous API is otherwise unaffected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JoinError`] if the blocking task panics or gets cancelled,
+    /// for instance, when the custom method derives invalid secret.
+    pub async fn generate_using_async(method: Method, length: Length) -> Result<Self, JoinError> {
+        task::spawn_blocking(move || Self::generate_using(method, length)).await
+    }
+
+    /// Generates [`Self`] on the blocking thread pool using the default method and length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::Code;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let code = Code::generate_async().await.unwrap();
+    ///
+    /// let (verifier, challenge) = code.into_pair();
+    ///
+    /// assert!(verifier.verify(&challenge));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JoinError`] if the blocking task gets cancelled.
+    pub async fn generate_async() -> Result<Self, JoinError> {
+        Self::generate_using_async(Method::default(), Length::default()).await
+    }
 }
 
 /// An alias for [`Code<'static>`].
@@ -128,14 +465,92 @@ impl<'c> From<Code<'c>> for Pair<'c> {
     }
 }
 
-/// Represents `(verifier, secret, method)` parts.
-pub type Parts<'p> = (Cow<'p, str>, String, Method);
+/// Represents code parts, namely the verifier, the challenge secret and the method.
+///
+/// Parts are redacted when formatted with [`Debug`], unless the `expose-secrets` feature
+/// is enabled.
+#[derive(Clone)]
+#[cfg_attr(feature = "expose-secrets", derive(Debug))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Parts<'p> {
+    /// The verifier string.
+    pub verifier: Cow<'p, str>,
+    /// The challenge secret.
+    pub secret: String,
+    /// The challenge method.
+    pub method: Method,
+}
+
+#[cfg(not(feature = "expose-secrets"))]
+impl fmt::Debug for Parts<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Parts")
+            .field("length", &self.verifier.len())
+            .field("method", &self.method)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'p> Parts<'p> {
+    /// Constructs [`Self`].
+    pub const fn new(verifier: Cow<'p, str>, secret: String, method: Method) -> Self {
+        Self {
+            verifier,
+            secret,
+            method,
+        }
+    }
+}
 
 /// Represents owned [`Parts`] values.
 pub type OwnedParts = Parts<'static>;
 
+#[cfg(feature = "static")]
+impl IntoStatic for Parts<'_> {
+    type Static = OwnedParts;
+
+    fn into_static(self) -> Self::Static {
+        Self::Static::new(self.verifier.into_static(), self.secret, self.method)
+    }
+}
+
 impl<'c> From<Code<'c>> for Parts<'c> {
     fn from(code: Code<'c>) -> Self {
         code.into_parts()
     }
 }
+
+impl<'c> TryFrom<Parts<'c>> for Code<'c> {
+    type Error = Error;
+
+    fn try_from(parts: Parts<'c>) -> Result<Self, Self::Error> {
+        Self::from_parts(parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        code::Code,
+        length::{self, Length},
+        method::Method,
+    };
+
+    proptest! {
+        #[test]
+        fn verify(
+            value in length::MIN..=length::MAX,
+            method in prop_oneof![Just(Method::Plain), Just(Method::Sha256)],
+        ) {
+            let code = Code::generate_using(method, Length::new(value).unwrap());
+
+            let (verifier, challenge) = code.into_pair();
+
+            prop_assert!(verifier.verify(&challenge));
+            prop_assert!(verifier.verify_decode(&challenge));
+        }
+    }
+}
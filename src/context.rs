@@ -0,0 +1,500 @@
+//! Configuring PKCE behavior in one place.
+//!
+//! Large applications tend to pass the same method, length, validation policy and store
+//! to every PKCE-related call. The [`Pkce`] context bundles them together, so that it can be
+//! configured once (see [`Pkce::builder`]) and then passed around:
+//!
+//! - [`generate`] generates codes using the configured method, length and source;
+//! - [`generate_encode`] generates codes by encoding the configured count of random bytes;
+//! - [`verify`] validates incoming verifiers against the policy before verifying them;
+//! - [`params`] returns the authorization request parameters for the given challenge.
+//!
+//! The context only generates verifiers accepted by its own policy, which is checked when
+//! building it.
+//!
+//! Applications mandating specific lengths (for instance, compliance profiles requiring
+//! `128` characters) should configure them once here instead of passing them to every
+//! generation call:
+//!
+//! ```
+//! use pkce_std::{context::Pkce, count::Count, length::Length};
+//!
+//! let pkce = Pkce::builder()
+//!     .length(Length::MAX)
+//!     .count(Count::MAX)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(pkce.generate_verifier().get().len(), 128);
+//! assert_eq!(pkce.generate_encode().into_pair().0.get().len(), 128);
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{context::Pkce, length::Length, method::Method};
+//!
+//! let pkce = Pkce::builder()
+//!     .method(Method::Sha256)
+//!     .length(Length::new(64).unwrap())
+//!     .build()
+//!     .unwrap();
+//!
+//! let (verifier, challenge) = pkce.generate().into_pair();
+//!
+//! assert_eq!(verifier.get().len(), 64);
+//!
+//! assert!(pkce.verify(verifier.get(), &challenge).unwrap());
+//!
+//! let params = pkce.params(&challenge);
+//!
+//! assert_eq!(params[1], ("code_challenge_method", "S256"));
+//! ```
+//!
+//! # Stores
+//!
+//! Contexts can also hold any [`ChallengeStore`], for instance, [`MemoryStore`]
+//! or [`KvChallengeStore`], in which case challenges can be stored and verified by key:
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use pkce_std::{context::Pkce, store::MemoryStore};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let store = MemoryStore::new(Duration::from_secs(600));
+//!
+//! let pkce = Pkce::builder().store(store).build().unwrap();
+//!
+//! let (verifier, _) = pkce.generate_stored("state").await.unwrap().into_pair();
+//!
+//! assert!(pkce.verify_stored("state", verifier.get()).await.unwrap());
+//!
+//! // challenges are removed once used
+//! assert!(pkce.verify_stored("state", verifier.get()).await.is_err());
+//! # }
+//! ```
+//!
+//! [`generate`]: Pkce::generate
+//! [`generate_encode`]: Pkce::generate_encode
+//! [`verify`]: Pkce::verify
+//! [`params`]: Pkce::params
+//! [`MemoryStore`]: crate::store::MemoryStore
+//! [`KvChallengeStore`]: crate::kv::KvChallengeStore
+
+use std::error::Error as StdError;
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+#[cfg(feature = "events")]
+use crate::events;
+
+use crate::{
+    challenge::Challenge,
+    charset,
+    code::Code,
+    count::Count,
+    generate,
+    length::Length,
+    method::Method,
+    store::ChallengeStore,
+    validator::{self, VerifierValidator},
+    verifier::{Origin, Verifier},
+};
+
+pub use crate::challenge::{CODE_CHALLENGE, CODE_CHALLENGE_METHOD};
+
+/// Represents sources of randomness used to generate verifiers.
+///
+/// Sources fill the given buffers with random characters from the [`charset::STRING`] set,
+/// just like [`generate::fill`] does, which is the default one.
+pub type Source = fn(&mut [u8]);
+
+/// The message used when panicking on sources producing invalid verifiers.
+pub const SOURCE: &str = "the source produced invalid verifier";
+
+/// Represents errors that can occur when building contexts.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum BuildError {
+    /// The length (or the encoded length of the count) is out of the range accepted by the policy.
+    #[error("length `{length}` is out of `[{min}, {max}]` range accepted by the policy")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::context::build::length),
+            help("make sure the length is accepted by the validator")
+        )
+    )]
+    Length {
+        /// The configured length.
+        length: Length,
+        /// The minimum length accepted.
+        min: Length,
+        /// The maximum length accepted.
+        max: Length,
+    },
+
+    /// The character that can be generated is not allowed by the policy.
+    #[error("character `{character}` can be generated, but is not allowed by the policy")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::context::build::character),
+            help(
+                "make sure the validator allows all characters from `{}`",
+                charset::STRING
+            )
+        )
+    )]
+    Character {
+        /// The disallowed character.
+        character: char,
+    },
+}
+
+impl BuildError {
+    /// Constructs [`Self::Length`].
+    pub const fn length(length: Length, min: Length, max: Length) -> Self {
+        Self::Length { length, min, max }
+    }
+
+    /// Constructs [`Self::Character`].
+    pub const fn character(character: char) -> Self {
+        Self::Character { character }
+    }
+}
+
+/// Represents errors that can occur when verifying stored challenges.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error<E: StdError + 'static> {
+    /// The verifier is rejected by the policy.
+    #[error("verifier rejected by the policy")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Policy(#[from] validator::Error),
+
+    /// The store failed.
+    #[error("store error")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::context::store),
+            help("see the store error for more information")
+        )
+    )]
+    Store(#[source] E),
+
+    /// No challenge is stored under the key.
+    #[error("challenge not found")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::context::missing),
+            help("the challenge has either expired or been used already")
+        )
+    )]
+    Missing,
+}
+
+/// Represents builders of [`Pkce`] contexts.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct Builder<S = ()> {
+    method: Method,
+    length: Length,
+    count: Count,
+    validator: VerifierValidator,
+    source: Source,
+    store: S,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// Constructs [`Self`] with the default configuration and no store.
+    pub fn new() -> Self {
+        Self {
+            method: Method::default(),
+            length: Length::default(),
+            count: Count::default(),
+            validator: VerifierValidator::new(),
+            source: generate::fill,
+            store: (),
+        }
+    }
+}
+
+impl<S> Builder<S> {
+    /// Sets the method used to derive challenges.
+    #[must_use]
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = method;
+
+        self
+    }
+
+    /// Sets the length of generated verifiers.
+    #[must_use]
+    pub const fn length(mut self, length: Length) -> Self {
+        self.length = length;
+
+        self
+    }
+
+    /// Sets the count of random bytes encoded into generated verifiers.
+    ///
+    /// This is used by [`Pkce::generate_encode`] only.
+    #[must_use]
+    pub const fn count(mut self, count: Count) -> Self {
+        self.count = count;
+
+        self
+    }
+
+    /// Sets the policy used to validate incoming verifiers.
+    #[must_use]
+    pub fn validator(mut self, validator: VerifierValidator) -> Self {
+        self.validator = validator;
+
+        self
+    }
+
+    /// Sets the [`Source`] of randomness used to generate verifiers.
+    #[must_use]
+    pub const fn source(mut self, source: Source) -> Self {
+        self.source = source;
+
+        self
+    }
+
+    /// Sets the store, replacing the previous one.
+    pub fn store<T>(self, store: T) -> Builder<T> {
+        Builder {
+            method: self.method,
+            length: self.length,
+            count: self.count,
+            validator: self.validator,
+            source: self.source,
+            store,
+        }
+    }
+
+    /// Builds [`Pkce`] from the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::Length`] if either the length or the encoded length of the count
+    /// is not accepted by the validator, and [`BuildError::Character`] if the validator does not allow some of the characters
+    /// that can be generated.
+    pub fn build(self) -> Result<Pkce<S>, BuildError> {
+        let (min, max) = (self.validator.min_length(), self.validator.max_length());
+
+        for length in [self.length, self.count.into()] {
+            if length < min || length > max {
+                return Err(BuildError::length(length, min, max));
+            }
+        }
+
+        if let Some(character) = charset::CHARS
+            .into_iter()
+            .find(|&character| !self.validator.allows(character))
+        {
+            return Err(BuildError::character(character));
+        }
+
+        let pkce = Pkce {
+            method: self.method,
+            length: self.length,
+            count: self.count,
+            validator: self.validator,
+            source: self.source,
+            store: self.store,
+        };
+
+        Ok(pkce)
+    }
+}
+
+/// Represents configured PKCE contexts.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct Pkce<S = ()> {
+    method: Method,
+    length: Length,
+    count: Count,
+    validator: VerifierValidator,
+    source: Source,
+    store: S,
+}
+
+impl Default for Pkce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pkce {
+    /// Constructs [`Self`] with the default configuration and no store.
+    pub fn new() -> Self {
+        Builder::new()
+            .build()
+            .expect("the default configuration is always valid")
+    }
+
+    /// Returns the [`Builder`] used to construct [`Self`].
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+}
+
+impl<S> Pkce<S> {
+    /// Returns the configured method.
+    pub const fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Returns the configured length.
+    pub const fn length(&self) -> Length {
+        self.length
+    }
+
+    /// Returns the configured count.
+    pub const fn count(&self) -> Count {
+        self.count
+    }
+
+    /// Returns the configured validator.
+    pub const fn validator(&self) -> &VerifierValidator {
+        &self.validator
+    }
+
+    /// Returns the configured store.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Consumes [`Self`], returning the configured store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    /// Generates [`Code`] using the configured method, length and source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source produces characters outside of the [`charset::STRING`] set.
+    pub fn generate(&self) -> Code<'static> {
+        let verifier = self.generate_verifier();
+
+        Code::generated(verifier, self.method)
+    }
+
+    /// Generates [`Code`] by encoding the configured count of random bytes,
+    /// using the configured method.
+    ///
+    /// Note that the configured source is not used here.
+    pub fn generate_encode(&self) -> Code<'static> {
+        let verifier = Verifier::generate_encode(self.count);
+
+        Code::generated(verifier, self.method)
+    }
+
+    /// Generates [`Verifier`] using the configured length and source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source produces characters outside of the [`charset::STRING`] set.
+    pub fn generate_verifier(&self) -> Verifier<'static> {
+        let mut data = vec![0; self.length.get()];
+
+        (self.source)(&mut data);
+
+        let string = String::from_utf8(data).expect(SOURCE);
+
+        let verifier = Verifier::owned(string).expect(SOURCE);
+
+        verifier.with_origin(Origin::String(self.length))
+    }
+
+    /// Validates the given verifier against the policy and then verifies the [`Challenge`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`validator::Error`] if the verifier is rejected by the policy.
+    pub fn verify(&self, verifier: &str, challenge: &Challenge) -> Result<bool, validator::Error> {
+        let verifier = self.validator.validate(verifier)?;
+
+        Ok(verifier.verify(challenge))
+    }
+
+    /// Returns the authorization request parameters for the given [`Challenge`]
+    /// as `(name, value)` pairs.
+    pub fn params<'c>(&self, challenge: &'c Challenge) -> [(&'static str, &'c str); 2] {
+        [
+            (CODE_CHALLENGE, challenge.secret()),
+            (CODE_CHALLENGE_METHOD, challenge.method().static_str()),
+        ]
+    }
+}
+
+impl<S: ChallengeStore> Pkce<S> {
+    /// Generates [`Code`] and stores its challenge under the given key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChallengeStore::Error`] if storing fails.
+    ///
+    /// # Panics
+    ///
+    /// See [`generate`] for more information.
+    ///
+    /// [`generate`]: Self::generate
+    pub async fn generate_stored(&self, key: &str) -> Result<Code<'static>, S::Error> {
+        let verifier = self.generate_verifier();
+
+        let challenge = verifier.challenge_using(self.method);
+
+        self.store.store(key.to_owned(), challenge.clone()).await?;
+
+        #[cfg(feature = "events")]
+        events::code(&verifier, challenge.method());
+
+        Ok(Code::new(verifier, challenge))
+    }
+
+    /// Takes the challenge stored under the given key and verifies the verifier against it.
+    ///
+    /// The challenge is removed regardless of the outcome, so that it can only be used once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Store`] if the store fails, [`Error::Missing`] if no challenge
+    /// is stored under the key, and [`Error::Policy`] if the verifier is rejected by the policy.
+    pub async fn verify_stored(&self, key: &str, verifier: &str) -> Result<bool, Error<S::Error>> {
+        let challenge = self
+            .store
+            .take(key)
+            .await
+            .map_err(Error::Store)?
+            .ok_or(Error::Missing)?;
+
+        let verified = self.verify(verifier, &challenge)?;
+
+        Ok(verified)
+    }
+}
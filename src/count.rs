@@ -28,7 +28,11 @@
 //! assert_eq!(count.encoded(), length.get());
 //! ```
 
-use std::{fmt, num::ParseIntError, str::FromStr};
+use std::{
+    fmt,
+    num::{NonZeroUsize, ParseIntError},
+    str::FromStr,
+};
 
 use const_macros::{const_early, const_ok, const_try};
 
@@ -40,7 +44,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 use thiserror::Error;
 
-use crate::{encoding, length::Length};
+use crate::{encoding, error::ErrorKind, length::Length};
 
 /// The minimum count.
 pub const MIN: usize = 32;
@@ -53,18 +57,91 @@ pub const MAX: usize = 96;
 
 /// Represents errors that can occur when constructing [`Count`] values.
 ///
-/// This error is returned when the given value is less than [`MIN`] or greater than [`MAX`].
+/// This error is returned when the given value is less than [`MIN`] or greater than [`MAX`],
+/// with the variants telling these cases apart along with how far out of range the value is.
 #[derive(Debug, Error)]
-#[error("unexpected count `{value}`; expected in range `[{MIN}, {MAX}]`")]
-pub struct Error {
-    /// The unexpected value.
-    pub value: usize,
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The value is less than [`MIN`].
+    #[error("expected count of at least `{MIN}`, got `{value}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::count::short),
+            help("add at least `{missing}` more bytes"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+        )
+    )]
+    TooShort {
+        /// The invalid value.
+        value: usize,
+        /// The amount missing to reach [`MIN`].
+        missing: usize,
+    },
+    /// The value is greater than [`MAX`].
+    #[error("expected count of at most `{MAX}`, got `{value}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::count::long),
+            help("remove at least `{excess}` bytes"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+        )
+    )]
+    TooLong {
+        /// The invalid value.
+        value: usize,
+        /// The amount exceeding [`MAX`].
+        excess: usize,
+    },
 }
 
 impl Error {
-    /// Constructs [`Self`]
+    /// Constructs [`Self`] from the given out-of-range value.
+    ///
+    /// Values less than [`MIN`] result in [`Self::TooShort`], and the rest in [`Self::TooLong`].
     pub const fn new(value: usize) -> Self {
-        Self { value }
+        if value < MIN {
+            Self::too_short(value)
+        } else {
+            Self::too_long(value)
+        }
+    }
+
+    /// Constructs [`Self::TooShort`].
+    pub const fn too_short(value: usize) -> Self {
+        Self::TooShort {
+            value,
+            missing: MIN.saturating_sub(value),
+        }
+    }
+
+    /// Constructs [`Self::TooLong`].
+    pub const fn too_long(value: usize) -> Self {
+        Self::TooLong {
+            value,
+            excess: value.saturating_sub(MAX),
+        }
+    }
+
+    /// Returns the invalid value.
+    pub const fn value(&self) -> usize {
+        match *self {
+            Self::TooShort { value, .. } | Self::TooLong { value, .. } => value,
+        }
+    }
+
+    /// Returns how far out of range the value is.
+    pub const fn distance(&self) -> usize {
+        match *self {
+            Self::TooShort { missing, .. } => missing,
+            Self::TooLong { excess, .. } => excess,
+        }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::InvalidCount
     }
 }
 
@@ -78,7 +155,8 @@ pub enum ParseError {
         feature = "diagnostics",
         diagnostic(
             code(pkce_std::count::parse),
-            help("make sure the count is in the valid range")
+            help("make sure the count is in the valid range"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
         )
     )]
     Length(#[from] Error),
@@ -91,6 +169,16 @@ pub enum ParseError {
     Int(#[from] ParseIntError),
 }
 
+impl ParseError {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Length(error) => error.kind(),
+            Self::Int(error) => ErrorKind::int(error),
+        }
+    }
+}
+
 /// Represents byte counts.
 ///
 /// Refer to the [module] documentation for more information.
@@ -135,6 +223,32 @@ impl TryFrom<usize> for Count {
     }
 }
 
+impl TryFrom<u32> for Count {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        // values not fitting into `usize` are certainly greater than `MAX`
+        Self::new(usize::try_from(value).unwrap_or(usize::MAX))
+    }
+}
+
+impl TryFrom<u64> for Count {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        // values not fitting into `usize` are certainly greater than `MAX`
+        Self::new(usize::try_from(value).unwrap_or(usize::MAX))
+    }
+}
+
+impl TryFrom<NonZeroUsize> for Count {
+    type Error = Error;
+
+    fn try_from(value: NonZeroUsize) -> Result<Self, Self::Error> {
+        Self::new(value.get())
+    }
+}
+
 impl From<Count> for usize {
     fn from(count: Count) -> Self {
         count.get()
@@ -198,9 +312,11 @@ impl Count {
     ///
     /// # Errors
     ///
-    /// [`struct@Error`] is returned if the value is less than [`MIN`] or greater than [`MAX`].
+    /// [`Error::TooShort`] is returned if the value is less than [`MIN`],
+    /// and [`Error::TooLong`] if it is greater than [`MAX`].
     pub const fn check(value: usize) -> Result<(), Error> {
-        const_early!(value < MIN || value > MAX => Error::new(value));
+        const_early!(value < MIN => Error::too_short(value));
+        const_early!(value > MAX => Error::too_long(value));
 
         Ok(())
     }
@@ -219,6 +335,22 @@ impl Count {
         self.value
     }
 
+    /// Adds the given value to [`Self`], returning [`None`] if the result is out of range.
+    pub const fn checked_add(self, value: usize) -> Option<Self> {
+        match self.get().checked_add(value) {
+            Some(value) => Self::new_ok(value),
+            None => None,
+        }
+    }
+
+    /// Subtracts the given value from [`Self`], returning [`None`] if the result is out of range.
+    pub const fn checked_sub(self, value: usize) -> Option<Self> {
+        match self.get().checked_sub(value) {
+            Some(value) => Self::new_ok(value),
+            None => None,
+        }
+    }
+
     /// Returns the encoded length corresponding to the byte count.
     pub const fn encoded(self) -> usize {
         encoding::length(self.get())
@@ -233,3 +365,62 @@ impl Count {
     /// The maximum value of [`Self`].
     pub const MAX: Self = Self::new_ok(MAX).unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        count::{Count, MAX, MIN},
+        encoding,
+        length::{self, Length},
+    };
+
+    proptest! {
+        #[test]
+        fn length_consistency(value in MIN..=MAX) {
+            let count = Count::new(value).unwrap();
+
+            let length = Length::from(count);
+
+            prop_assert_eq!(length.get(), encoding::length(value));
+            prop_assert_eq!(length.get(), count.encoded());
+
+            prop_assert!(Length::new(length.get()).is_ok());
+        }
+
+        #[test]
+        fn out_of_range(value in prop_oneof![0..MIN, MAX + 1..1024]) {
+            prop_assert!(Count::new(value).is_err());
+        }
+
+        #[test]
+        fn length_range(value in 0..1024usize) {
+            let valid = (length::MIN..=length::MAX).contains(&value);
+
+            prop_assert_eq!(Length::new(value).is_ok(), valid);
+        }
+
+        #[test]
+        fn checked_arithmetic(value in MIN..=MAX, delta in 0..1024usize) {
+            let count = Count::new(value).unwrap();
+
+            prop_assert_eq!(count.checked_add(delta), Count::new(value + delta).ok());
+            prop_assert_eq!(
+                count.checked_sub(delta),
+                value.checked_sub(delta).and_then(|value| Count::new(value).ok())
+            );
+        }
+
+        #[test]
+        fn integer_conversions(value in any::<u64>()) {
+            let expected = usize::try_from(value).ok().and_then(|value| Count::new(value).ok());
+
+            prop_assert_eq!(Count::try_from(value).ok(), expected);
+
+            if let Ok(value) = u32::try_from(value) {
+                prop_assert_eq!(Count::try_from(value).ok(), expected);
+            }
+        }
+    }
+}
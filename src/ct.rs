@@ -0,0 +1,56 @@
+//! Constant-time comparisons.
+//!
+//! Comparing secrets using `==` short-circuits on the first differing byte, which makes
+//! the time taken depend on the length of the common prefix, leaking it to the attacker.
+//!
+//! This module exposes the comparisons this crate uses internally (for verifiers,
+//! challenges and states), so that applications can compare related secrets,
+//! for instance, states and nonces, the same way.
+//!
+//! Note that the lengths of the compared values are not considered secret,
+//! meaning that comparing values of different lengths returns early.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::ct;
+//!
+//! assert!(ct::eq("state", "state"));
+//! assert!(!ct::eq("state", "stale"));
+//! ```
+
+use constant_time_eq::constant_time_eq;
+
+/// Compares the given strings in constant time.
+pub fn eq(left: &str, right: &str) -> bool {
+    eq_bytes(left.as_bytes(), right.as_bytes())
+}
+
+/// Compares the given byte slices in constant time.
+pub fn eq_bytes(left: &[u8], right: &[u8]) -> bool {
+    constant_time_eq(left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{eq, eq_bytes};
+
+    proptest! {
+        #[test]
+        fn reflexive(string in ".*") {
+            prop_assert!(eq(&string, &string));
+        }
+
+        #[test]
+        fn consistent(left in ".*", right in ".*") {
+            prop_assert_eq!(eq(&left, &right), left == right);
+        }
+
+        #[test]
+        fn consistent_bytes(left in any::<Vec<u8>>(), right in any::<Vec<u8>>()) {
+            prop_assert_eq!(eq_bytes(&left, &right), left == right);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Attaching PKCE to the device authorization grant.
+//!
+//! The [device authorization grant] does not use PKCE by itself, yet some authorization servers
+//! accept `code_challenge` and `code_challenge_method` in the device authorization request,
+//! expecting the matching `code_verifier` in the follow-up token request.
+//!
+//! The [`AuthorizationRequest`] and [`TokenRequest`] types build the fields of these requests,
+//! returning them as `(name, value)` pairs to be form-encoded by the HTTP client of choice.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{
+//!     code::Code,
+//!     device::{self, AuthorizationRequest, TokenRequest},
+//! };
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! let request = AuthorizationRequest::new("client", &challenge).scope("profile");
+//!
+//! let pairs = request.pairs();
+//!
+//! assert!(pairs.contains(&(device::CODE_CHALLENGE_METHOD, "S256")));
+//!
+//! // the authorization server responds with `device_code`, which is then polled with
+//!
+//! let request = TokenRequest::new("client", "GmRhmhcxhwAzkoEqiMEg_DnyEysNkuNhszIySk9eS", &verifier);
+//!
+//! let pairs = request.pairs();
+//!
+//! assert!(pairs.contains(&(device::GRANT_TYPE, device::DEVICE_CODE_GRANT)));
+//! assert!(pairs.contains(&(device::CODE_VERIFIER, verifier.get())));
+//! ```
+//!
+//! [device authorization grant]: https://datatracker.ietf.org/doc/html/rfc8628
+
+use crate::{challenge::Challenge, verifier::Verifier};
+
+pub use crate::{
+    challenge::{CODE_CHALLENGE, CODE_CHALLENGE_METHOD},
+    verifier::CODE_VERIFIER,
+};
+
+/// The `client_id` parameter name.
+pub const CLIENT_ID: &str = "client_id";
+
+/// The `scope` parameter name.
+pub const SCOPE: &str = "scope";
+
+/// The `grant_type` parameter name.
+pub const GRANT_TYPE: &str = "grant_type";
+
+/// The `device_code` parameter name.
+pub const DEVICE_CODE: &str = "device_code";
+
+/// The device code grant type.
+pub const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Represents device authorization requests with PKCE attached.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorizationRequest<'r> {
+    client_id: &'r str,
+    scope: Option<&'r str>,
+    challenge: &'r Challenge,
+}
+
+impl<'r> AuthorizationRequest<'r> {
+    /// Constructs [`Self`] with the given client identifier and [`Challenge`].
+    pub const fn new(client_id: &'r str, challenge: &'r Challenge) -> Self {
+        Self {
+            client_id,
+            scope: None,
+            challenge,
+        }
+    }
+
+    /// Sets the requested scope.
+    #[must_use]
+    pub const fn scope(mut self, scope: &'r str) -> Self {
+        self.scope = Some(scope);
+
+        self
+    }
+
+    /// Returns the client identifier.
+    pub const fn client_id(&self) -> &'r str {
+        self.client_id
+    }
+
+    /// Returns the attached [`Challenge`].
+    pub const fn challenge(&self) -> &'r Challenge {
+        self.challenge
+    }
+
+    /// Returns the request fields as `(name, value)` pairs.
+    pub fn pairs(&self) -> Vec<(&'static str, &'r str)> {
+        let mut pairs = vec![(CLIENT_ID, self.client_id)];
+
+        if let Some(scope) = self.scope {
+            pairs.push((SCOPE, scope));
+        }
+
+        pairs.push((CODE_CHALLENGE, self.challenge.secret()));
+        pairs.push((CODE_CHALLENGE_METHOD, self.challenge.method().static_str()));
+
+        pairs
+    }
+}
+
+/// Represents device access token requests with PKCE attached.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy)]
+pub struct TokenRequest<'r> {
+    client_id: &'r str,
+    device_code: &'r str,
+    verifier: &'r Verifier<'r>,
+}
+
+impl<'r> TokenRequest<'r> {
+    /// Constructs [`Self`] with the given client identifier, device code and [`Verifier`].
+    pub const fn new(client_id: &'r str, device_code: &'r str, verifier: &'r Verifier<'r>) -> Self {
+        Self {
+            client_id,
+            device_code,
+            verifier,
+        }
+    }
+
+    /// Returns the client identifier.
+    pub const fn client_id(&self) -> &'r str {
+        self.client_id
+    }
+
+    /// Returns the device code.
+    pub const fn device_code(&self) -> &'r str {
+        self.device_code
+    }
+
+    /// Returns the attached [`Verifier`].
+    pub const fn verifier(&self) -> &'r Verifier<'r> {
+        self.verifier
+    }
+
+    /// Returns the request fields as `(name, value)` pairs.
+    pub fn pairs(&self) -> Vec<(&'static str, &'r str)> {
+        vec![
+            (GRANT_TYPE, DEVICE_CODE_GRANT),
+            (DEVICE_CODE, self.device_code),
+            (CLIENT_ID, self.client_id),
+            (CODE_VERIFIER, self.verifier.get()),
+        ]
+    }
+}
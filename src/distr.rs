@@ -0,0 +1,87 @@
+//! Sampling PKCE code verifiers and codes via [`rand`] distributions.
+//!
+//! The [`VerifierDistribution`] and [`CodeDistribution`] types implement [`Distribution`]
+//! for [`Verifier<'static>`] and [`Code<'static>`] respectively, which allows plugging
+//! generation into existing [`rand`] pipelines.
+//!
+//! Note that [`Distribution`] can not require generators to be cryptographically secure,
+//! therefore it is up to the caller to only sample using [`CryptoRng`] generators.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{
+//!     distr::{CodeDistribution, VerifierDistribution},
+//!     length::Length,
+//!     verifier::Verifier,
+//! };
+//! use rand::Rng;
+//!
+//! let mut rng = rand::rng();
+//!
+//! let verifier: Verifier<'_> = rng.sample(VerifierDistribution::default());
+//!
+//! assert_eq!(verifier.get().len(), Length::default().get());
+//!
+//! let (verifier, challenge) = rng.sample(CodeDistribution::default()).into_pair();
+//!
+//! assert!(verifier.verify(&challenge));
+//! ```
+//!
+//! [`rand`]: https://docs.rs/rand
+//! [`Distribution`]: rand::distr::Distribution
+//! [`CryptoRng`]: rand::CryptoRng
+//! [`Verifier<'static>`]: crate::verifier::Verifier
+//! [`Code<'static>`]: crate::code::Code
+
+use crate::{length::Length, method::Method};
+
+/// Represents distributions of verifiers with the given length.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct VerifierDistribution {
+    length: Length,
+}
+
+impl VerifierDistribution {
+    /// Constructs [`Self`] with the given length.
+    pub const fn new(length: Length) -> Self {
+        Self { length }
+    }
+
+    /// Returns the length of sampled verifiers.
+    pub const fn length(&self) -> Length {
+        self.length
+    }
+}
+
+/// Represents distributions of codes with the given method and length.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CodeDistribution {
+    method: Method,
+    length: Length,
+}
+
+impl CodeDistribution {
+    /// Constructs [`Self`] with the given method and length.
+    pub const fn new(method: Method, length: Length) -> Self {
+        Self { method, length }
+    }
+
+    /// Returns the method of sampled codes.
+    pub const fn method(&self) -> Method {
+        self.method
+    }
+
+    /// Returns the length of sampled verifiers.
+    pub const fn length(&self) -> Length {
+        self.length
+    }
+}
@@ -5,6 +5,17 @@
 //! There are also [`try_length`] and [`length`] functions to calculate the
 //! length of the encoded data.
 //!
+//! Decoding is available via [`decode`], returning [`DecodeError`] on failure.
+//!
+//! Encoding and decoding into caller-provided buffers is available via [`encode_into_slice`]
+//! and [`decode_slice`] respectively, neither of which allocates.
+//!
+//! With the `base64ct` feature enabled, [`encode`] uses the constant-time implementation
+//! from the [`base64ct`] crate instead, which affects challenge derivation as well.
+//! The output is exactly the same regardless of the backend.
+//!
+//! [`base64ct`]: https://docs.rs/base64ct
+//!
 //! # Examples
 //!
 //! ```
@@ -17,7 +28,145 @@
 //! assert_eq!(encoded.len(), length(data.len()));
 //! ```
 
-use base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine};
+use base64::{
+    DecodeError as Base64DecodeError, DecodeSliceError, EncodeSliceError,
+    engine::{Engine, general_purpose::URL_SAFE_NO_PAD},
+};
+
+#[cfg(feature = "base64ct")]
+use base64ct::{Base64UrlUnpadded, Encoding};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::error::ErrorKind;
+
+/// Represents errors that can occur when decoding Base64 data.
+///
+/// This type does not depend on the underlying Base64 implementation.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum DecodeError {
+    /// The data contains the invalid character.
+    #[error("invalid character `{character}` at index `{index}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::encoding::decode::character),
+            help("make sure the data is URL-safe Base64 without padding")
+        )
+    )]
+    Character {
+        /// The invalid character.
+        character: char,
+        /// The index of the invalid character.
+        index: usize,
+    },
+
+    /// The data has invalid length.
+    #[error("invalid length `{length}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::encoding::decode::length),
+            help("make sure the data is not truncated")
+        )
+    )]
+    Length {
+        /// The invalid length.
+        length: usize,
+    },
+
+    /// The last character has non-zero trailing bits.
+    #[error("non-zero trailing bits in `{character}` at index `{index}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::encoding::decode::trailing_bits),
+            help("make sure the data is encoded canonically")
+        )
+    )]
+    TrailingBits {
+        /// The last character.
+        character: char,
+        /// The index of the last character.
+        index: usize,
+    },
+
+    /// The data is padded.
+    #[error("unexpected padding")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::encoding::decode::padding),
+            help("make sure the data is not padded")
+        )
+    )]
+    Padding,
+
+    /// The buffer is too small.
+    #[error("buffer is too small")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::encoding::decode::buffer),
+            help("make sure the buffer is large enough to fit the decoded data")
+        )
+    )]
+    Buffer,
+}
+
+impl DecodeError {
+    /// Constructs [`Self::Character`].
+    pub const fn character(character: char, index: usize) -> Self {
+        Self::Character { character, index }
+    }
+
+    /// Constructs [`Self::Length`].
+    pub const fn length(length: usize) -> Self {
+        Self::Length { length }
+    }
+
+    /// Constructs [`Self::TrailingBits`].
+    pub const fn trailing_bits(character: char, index: usize) -> Self {
+        Self::TrailingBits { character, index }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Character { .. } => ErrorKind::InvalidCharacter,
+            Self::Length { .. } | Self::TrailingBits { .. } | Self::Padding => {
+                ErrorKind::InvalidEncoding
+            }
+            Self::Buffer => ErrorKind::Overflow,
+        }
+    }
+}
+
+impl From<Base64DecodeError> for DecodeError {
+    fn from(error: Base64DecodeError) -> Self {
+        match error {
+            Base64DecodeError::InvalidByte(index, byte) => Self::character(byte.into(), index),
+            Base64DecodeError::InvalidLength(length) => Self::length(length),
+            Base64DecodeError::InvalidLastSymbol(index, byte) => {
+                Self::trailing_bits(byte.into(), index)
+            }
+            Base64DecodeError::InvalidPadding => Self::Padding,
+        }
+    }
+}
+
+impl From<DecodeSliceError> for DecodeError {
+    fn from(error: DecodeSliceError) -> Self {
+        match error {
+            DecodeSliceError::DecodeError(error) => error.into(),
+            DecodeSliceError::OutputSliceTooSmall => Self::Buffer,
+        }
+    }
+}
 
 /// Encodes given data into Base64.
 ///
@@ -32,10 +181,203 @@ use base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine};
 ///
 /// assert_eq!(encode(data), "SGVsbG8sIHdvcmxkIQ");
 /// ```
+#[cfg(not(feature = "base64ct"))]
 pub fn encode<D: AsRef<[u8]>>(data: D) -> String {
     URL_SAFE_NO_PAD.encode(data)
 }
 
+/// Encodes given data into Base64 in constant time.
+///
+/// This function uses the URL-safe and no-padding variant of Base64.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::encoding::encode;
+///
+/// let data = "Hello, world!";
+///
+/// assert_eq!(encode(data), "SGVsbG8sIHdvcmxkIQ");
+/// ```
+#[cfg(feature = "base64ct")]
+pub fn encode<D: AsRef<[u8]>>(data: D) -> String {
+    Base64UrlUnpadded::encode_string(data.as_ref())
+}
+
+/// Encodes given data into Base64 into the provided buffer, returning the number of bytes written.
+///
+/// This function uses the URL-safe and no-padding variant of Base64, and does not allocate.
+///
+/// # Errors
+///
+/// Returns [`EncodeSliceError`] if the buffer is too small, see [`length`].
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::encoding::encode_into_slice;
+///
+/// let mut buffer = [0; 18];
+///
+/// let written = encode_into_slice("Hello, world!", &mut buffer).unwrap();
+///
+/// assert_eq!(&buffer[..written], b"SGVsbG8sIHdvcmxkIQ");
+/// ```
+#[cfg(not(feature = "base64ct"))]
+pub fn encode_into_slice<D: AsRef<[u8]>>(
+    data: D,
+    buffer: &mut [u8],
+) -> Result<usize, EncodeSliceError> {
+    URL_SAFE_NO_PAD.encode_slice(data, buffer)
+}
+
+/// Encodes given data into Base64 into the provided buffer in constant time,
+/// returning the number of bytes written.
+///
+/// This function uses the URL-safe and no-padding variant of Base64, and does not allocate.
+///
+/// # Errors
+///
+/// Returns [`EncodeSliceError`] if the buffer is too small, see [`length`].
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::encoding::encode_into_slice;
+///
+/// let mut buffer = [0; 18];
+///
+/// let written = encode_into_slice("Hello, world!", &mut buffer).unwrap();
+///
+/// assert_eq!(&buffer[..written], b"SGVsbG8sIHdvcmxkIQ");
+/// ```
+#[cfg(feature = "base64ct")]
+pub fn encode_into_slice<D: AsRef<[u8]>>(
+    data: D,
+    buffer: &mut [u8],
+) -> Result<usize, EncodeSliceError> {
+    Base64UrlUnpadded::encode(data.as_ref(), buffer)
+        .map(str::len)
+        .map_err(|_| EncodeSliceError::OutputSliceTooSmall)
+}
+
+/// The URL-safe Base64 alphabet.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The message used when panicking on encoded length mismatches.
+pub const CONST_LENGTH: &str = "`N` must be equal to the encoded length of the data";
+
+/// Encodes given data into Base64 in `const` contexts.
+///
+/// This function uses the URL-safe and no-padding variant of Base64.
+///
+/// # Panics
+///
+/// Panics if `N` is not equal to the encoded length of the data, see [`length`].
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::encoding::const_encode;
+///
+/// const ENCODED: [u8; 18] = const_encode(b"Hello, world!");
+///
+/// assert_eq!(&ENCODED, b"SGVsbG8sIHdvcmxkIQ");
+/// ```
+pub const fn const_encode<const N: usize>(data: &[u8]) -> [u8; N] {
+    assert!(N == length(data.len()), "{}", CONST_LENGTH);
+
+    let mut output = [0; N];
+
+    let mut index = 0;
+    let mut written = 0;
+
+    while index < data.len() {
+        let first = data[index];
+
+        let second = if index + 1 < data.len() {
+            data[index + 1]
+        } else {
+            0
+        };
+
+        let third = if index + 2 < data.len() {
+            data[index + 2]
+        } else {
+            0
+        };
+
+        let sextets = [
+            first >> 2,
+            ((first & 0b11) << 4) | (second >> 4),
+            ((second & 0b1111) << 2) | (third >> 6),
+            third & 0b11_1111,
+        ];
+
+        let mut sextet = 0;
+
+        // the last chunk may produce fewer than four characters
+        while sextet < sextets.len() && written < N {
+            output[written] = ALPHABET[sextets[sextet] as usize];
+
+            written += 1;
+            sextet += 1;
+        }
+
+        index += 3;
+    }
+
+    output
+}
+
+/// Decodes given Base64 data into the provided buffer, returning the number of bytes written.
+///
+/// This function uses the URL-safe and no-padding variant of Base64, and does not allocate.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if the data is not valid Base64 or the buffer is too small.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::encoding::decode_slice;
+///
+/// let mut buffer = [0; 13];
+///
+/// let written = decode_slice("SGVsbG8sIHdvcmxkIQ", &mut buffer).unwrap();
+///
+/// assert_eq!(&buffer[..written], b"Hello, world!");
+/// ```
+pub fn decode_slice<D: AsRef<[u8]>>(data: D, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+    let written = URL_SAFE_NO_PAD.decode_slice(data, buffer)?;
+
+    Ok(written)
+}
+
+/// Decodes given Base64 data.
+///
+/// This function uses the URL-safe and no-padding variant of Base64.
+///
+/// # Errors
+///
+/// Returns [`DecodeError`] if the data is not valid Base64.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::encoding::{DecodeError, decode};
+///
+/// assert_eq!(decode("SGVsbG8sIHdvcmxkIQ").unwrap(), b"Hello, world!");
+///
+/// assert_eq!(decode("SGVsbG8+").unwrap_err(), DecodeError::character('+', 7));
+/// ```
+pub fn decode<D: AsRef<[u8]>>(data: D) -> Result<Vec<u8>, DecodeError> {
+    let decoded = URL_SAFE_NO_PAD.decode(data)?;
+
+    Ok(decoded)
+}
+
 /// Computes the length of the Base64 encoded data from the given length.
 ///
 /// # Examples
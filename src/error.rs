@@ -0,0 +1,330 @@
+//! Unified error type.
+//!
+//! Each module defines its own error type, describing the failures specific to it.
+//! Applications that do not need to tell these apart can use the [`enum@Error`] type instead,
+//! which any of the core errors can be converted into, making it possible to use `?` freely.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{Error, Length, Method, Verifier};
+//!
+//! fn parse(verifier: &str, length: usize, method: &str) -> Result<(), Error> {
+//!     let _verifier = Verifier::borrowed(verifier)?;
+//!     let _length = Length::new(length)?;
+//!     let _method: Method = method.parse()?;
+//!
+//!     Ok(())
+//! }
+//!
+//! let error = parse("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ", 42, "S256").unwrap_err();
+//!
+//! assert!(matches!(error, Error::Length(_)));
+//! ```
+//!
+//! # Kinds
+//!
+//! Every error of this crate provides the `kind` method returning its [`ErrorKind`],
+//! which allows branching on failures programmatically:
+//!
+//! ```
+//! use pkce_std::{Verifier, error::ErrorKind};
+//!
+//! let error = Verifier::borrowed("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ!").unwrap_err();
+//!
+//! assert_eq!(error.kind(), ErrorKind::InvalidCharacter);
+//! ```
+//!
+//! # OAuth errors
+//!
+//! Token endpoints can map errors to the [OAuth error codes] via [`ErrorKind::oauth_error`]
+//! and respond with the suggested [`OAuthError::status`]. As per [RFC 7636], malformed
+//! verifiers result in `invalid_request`, while verifiers not matching challenges result
+//! in `invalid_grant`:
+//!
+//! ```
+//! use pkce_std::{Verifier, error::OAuthError};
+//!
+//! let error = Verifier::borrowed("short").unwrap_err();
+//!
+//! let oauth = error.kind().oauth_error();
+//!
+//! assert_eq!(oauth, OAuthError::InvalidRequest);
+//! assert_eq!(oauth.as_str(), "invalid_request");
+//! assert_eq!(oauth.status(), 400);
+//! ```
+//!
+//! The JSON response body is built via [`OAuthError::body`], which is shared by the
+//! framework integrations of this crate, and should be sent with the [`CONTENT_TYPE`]
+//! and [`CACHE_CONTROL`] headers:
+//!
+//! ```
+//! use pkce_std::error::OAuthError;
+//!
+//! assert_eq!(
+//!     OAuthError::InvalidGrant.body(Some("verifier does not match")),
+//!     r#"{"error":"invalid_grant","error_description":"verifier does not match"}"#,
+//! );
+//! ```
+//!
+//! [OAuth error codes]: https://datatracker.ietf.org/doc/html/rfc6749#section-5.2
+//! [RFC 7636]: https://datatracker.ietf.org/doc/html/rfc7636#section-4.6
+
+use std::{
+    fmt,
+    num::{IntErrorKind, ParseIntError},
+};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{challenge, check::string, count, generate, length, method, verifier};
+
+/// Represents kinds of errors.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The length is out of range or otherwise invalid.
+    InvalidLength,
+    /// The bytes count is out of range.
+    InvalidCount,
+    /// The character is not allowed.
+    InvalidCharacter,
+    /// The string is not ASCII.
+    NonAscii,
+    /// The string is not a valid integer.
+    InvalidNumber,
+    /// The value does not fit into the integer type or the buffer.
+    Overflow,
+    /// The data is not encoded properly.
+    InvalidEncoding,
+    /// The method is unknown.
+    UnknownMethod,
+    /// The method is known, but unsupported.
+    UnsupportedMethod,
+    /// The method name is reserved.
+    ReservedMethod,
+    /// The values do not correspond to each other.
+    Mismatch,
+    /// The parameter is missing.
+    MissingParameter,
+    /// The parameter is included more than once.
+    DuplicateParameter,
+    /// The data failed authentication.
+    Unauthenticated,
+    /// The data has expired.
+    Expired,
+    /// The operating system failed to provide entropy.
+    Entropy,
+}
+
+/// The `invalid_request` literal.
+pub const INVALID_REQUEST: &str = "invalid_request";
+
+/// The `invalid_grant` literal.
+pub const INVALID_GRANT: &str = "invalid_grant";
+
+/// The HTTP status suggested for OAuth errors (`400 Bad Request`).
+pub const BAD_REQUEST: u16 = 400;
+
+/// The content type of OAuth error responses.
+pub const CONTENT_TYPE: &str = "application/json";
+
+/// The `Cache-Control` header value of OAuth error responses.
+pub const CACHE_CONTROL: &str = "no-store";
+
+/// Writes the given string as the JSON string literal.
+fn write_json(string: &str, output: &mut String) {
+    output.push('"');
+
+    for character in string.chars() {
+        match character {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            control if control.is_control() => {
+                output.push_str(&format!("\\u{:04x}", u32::from(control)));
+            }
+            other => output.push(other),
+        }
+    }
+
+    output.push('"');
+}
+
+/// Represents OAuth error codes returned by token endpoints.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OAuthError {
+    /// The request is malformed, for instance, the verifier is invalid.
+    InvalidRequest,
+    /// The grant is invalid, for instance, the verifier does not match the challenge.
+    InvalidGrant,
+}
+
+impl fmt::Display for OAuthError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(formatter)
+    }
+}
+
+impl OAuthError {
+    /// Returns the error code, used as the `error` field of the response.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidRequest => INVALID_REQUEST,
+            Self::InvalidGrant => INVALID_GRANT,
+        }
+    }
+
+    /// Returns the suggested HTTP status code.
+    ///
+    /// Both codes are returned with `400 Bad Request`, as per the standard.
+    pub const fn status(self) -> u16 {
+        match self {
+            Self::InvalidRequest | Self::InvalidGrant => BAD_REQUEST,
+        }
+    }
+
+    /// Returns the JSON response body containing this error code
+    /// and the optional `error_description`.
+    ///
+    /// Refer to the [module] documentation for more information.
+    ///
+    /// [module]: self
+    pub fn body(self, description: Option<&str>) -> String {
+        let mut body = format!(r#"{{"error":"{self}""#);
+
+        if let Some(description) = description {
+            body.push_str(r#","error_description":"#);
+
+            write_json(description, &mut body);
+        }
+
+        body.push('}');
+
+        body
+    }
+}
+
+impl ErrorKind {
+    /// Returns the [`OAuthError`] token endpoints should respond with.
+    ///
+    /// [`Self::Mismatch`] and [`Self::Expired`] result in [`OAuthError::InvalidGrant`],
+    /// and everything else in [`OAuthError::InvalidRequest`].
+    ///
+    /// Note that failed verification (when `verify` returns `false`) should also result
+    /// in [`OAuthError::InvalidGrant`].
+    pub const fn oauth_error(self) -> OAuthError {
+        match self {
+            Self::Mismatch | Self::Expired => OAuthError::InvalidGrant,
+            _ => OAuthError::InvalidRequest,
+        }
+    }
+
+    /// Returns the kind of the given [`ParseIntError`].
+    pub(crate) const fn int(error: &ParseIntError) -> Self {
+        match error.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Self::Overflow,
+            _ => Self::InvalidNumber,
+        }
+    }
+}
+
+/// Represents errors aggregating the core errors of this crate.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// Invalid verifier.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Verifier(#[from] verifier::Error),
+
+    /// Invalid challenge.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Challenge(#[from] challenge::Error),
+
+    /// Invalid length.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Length(#[from] length::Error),
+
+    /// Invalid count.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Count(#[from] count::Error),
+
+    /// Unknown method.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Method(#[from] method::Error),
+
+    /// Invalid string.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Check(#[from] string::Error),
+
+    /// Entropy failure.
+    #[error(transparent)]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Entropy(#[from] generate::EntropyError),
+}
+
+impl Error {
+    /// Returns the [`OAuthError`] token endpoints should respond with.
+    ///
+    /// See [`ErrorKind::oauth_error`] for more information.
+    pub const fn oauth_error(&self) -> OAuthError {
+        self.kind().oauth_error()
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Verifier(error) => error.kind(),
+            Self::Challenge(error) => error.kind(),
+            Self::Length(error) => error.kind(),
+            Self::Count(error) => error.kind(),
+            Self::Method(error) => error.kind(),
+            Self::Check(error) => error.kind(),
+            Self::Entropy(error) => error.kind(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::OAuthError;
+
+    #[test]
+    fn body() {
+        assert_eq!(
+            OAuthError::InvalidRequest.body(None),
+            r#"{"error":"invalid_request"}"#
+        );
+    }
+
+    #[test]
+    fn body_escaped() {
+        assert_eq!(
+            OAuthError::InvalidGrant.body(Some("\"quoted\"\\\n\u{1}")),
+            r#"{"error":"invalid_grant","error_description":"\"quoted\"\\\n\u0001"}"#
+        );
+    }
+}
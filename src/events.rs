@@ -0,0 +1,220 @@
+//! Observing generation events.
+//!
+//! Test suites often need to assert that configured policies are actually applied, for instance,
+//! that all codes generated during some run used `S256` with the length of at least `64`.
+//!
+//! Every time [`Verifier`] or [`Code`] is generated, the [`Event`] describing the [`Origin`]
+//! (that is, the length or the bytes count used) and the [`Method`] is emitted to observers
+//! registered on the current thread via [`observe`]. Events never contain secrets.
+//!
+//! Note that generating codes emits two events: [`Event::Verifier`] for the verifier
+//! and then [`Event::Code`] for the code itself.
+//!
+//! This module requires the `events` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use pkce_std::{
+//!     code::Code,
+//!     events::{self, Recorder},
+//!     length::Length,
+//!     method::Method,
+//! };
+//!
+//! let recorder = Arc::new(Recorder::new());
+//!
+//! events::observe(recorder.clone(), || {
+//!     let _code = Code::generate(Length::new(64).unwrap());
+//!     let _other = Code::generate_default();
+//! });
+//!
+//! let codes: Vec<_> = recorder
+//!     .take()
+//!     .into_iter()
+//!     .filter(|event| event.is_code())
+//!     .collect();
+//!
+//! assert_eq!(codes.len(), 2);
+//!
+//! assert!(codes.iter().all(|event| {
+//!     event.method() == Some(&Method::Sha256) && event.length().get() >= 64
+//! }));
+//! ```
+//!
+//! [`Code`]: crate::code::Code
+
+use std::{
+    cell::RefCell,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    length::Length,
+    method::Method,
+    verifier::{Origin, Verifier},
+};
+
+/// Represents generation events.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// The verifier was generated.
+    Verifier {
+        /// The origin of the verifier.
+        origin: Origin,
+    },
+    /// The code was generated.
+    Code {
+        /// The origin of the verifier.
+        origin: Origin,
+        /// The method used to compute the challenge.
+        method: Method,
+    },
+}
+
+impl Event {
+    /// Returns the [`Origin`] of the generated verifier.
+    pub const fn origin(&self) -> Origin {
+        match self {
+            Self::Verifier { origin } | Self::Code { origin, .. } => *origin,
+        }
+    }
+
+    /// Returns the [`Length`] of the generated verifier.
+    pub fn length(&self) -> Length {
+        self.origin().length()
+    }
+
+    /// Returns the [`Method`] used to compute the challenge, if the code was generated.
+    pub const fn method(&self) -> Option<&Method> {
+        match self {
+            Self::Verifier { .. } => None,
+            Self::Code { method, .. } => Some(method),
+        }
+    }
+
+    /// Checks whether [`Self`] is [`Self::Verifier`].
+    pub const fn is_verifier(&self) -> bool {
+        matches!(self, Self::Verifier { .. })
+    }
+
+    /// Checks whether [`Self`] is [`Self::Code`].
+    pub const fn is_code(&self) -> bool {
+        matches!(self, Self::Code { .. })
+    }
+}
+
+/// Represents observers of generation events.
+pub trait Observer {
+    /// Observes the given event.
+    fn observe(&self, event: &Event);
+}
+
+impl<O: Observer + ?Sized> Observer for &O {
+    fn observe(&self, event: &Event) {
+        O::observe(self, event);
+    }
+}
+
+impl<O: Observer + ?Sized> Observer for Box<O> {
+    fn observe(&self, event: &Event) {
+        O::observe(self, event);
+    }
+}
+
+impl<O: Observer + ?Sized> Observer for Arc<O> {
+    fn observe(&self, event: &Event) {
+        O::observe(self, event);
+    }
+}
+
+/// Represents observers recording every event.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Default)]
+pub struct Recorder {
+    events: Mutex<Vec<Event>>,
+}
+
+impl Observer for Recorder {
+    fn observe(&self, event: &Event) {
+        self.lock().push(event.clone());
+    }
+}
+
+impl Recorder {
+    /// Constructs [`Self`] with no events recorded.
+    pub const fn new() -> Self {
+        Self {
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Event>> {
+        self.events.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the copy of the events recorded so far.
+    pub fn events(&self) -> Vec<Event> {
+        self.lock().clone()
+    }
+
+    /// Takes the events recorded so far, leaving none behind.
+    pub fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut *self.lock())
+    }
+}
+
+thread_local! {
+    static OBSERVERS: RefCell<Vec<Arc<dyn Observer>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes the innermost observer when dropped, even if the observed function panics.
+struct Scope;
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        OBSERVERS.with_borrow_mut(|observers| observers.pop());
+    }
+}
+
+/// Calls the given function, emitting events generated on the current thread to the observer.
+///
+/// Calls can be nested, in which case events are emitted to every observer registered.
+pub fn observe<O: Observer + 'static, F: FnOnce() -> R, R>(observer: Arc<O>, function: F) -> R {
+    OBSERVERS.with_borrow_mut(|observers| observers.push(observer));
+
+    let _scope = Scope;
+
+    function()
+}
+
+/// Emits the given event to every observer registered on the current thread.
+fn emit(event: &Event) {
+    // observers are cloned so that they can generate codes themselves
+    let observers = OBSERVERS.with_borrow(Clone::clone);
+
+    for observer in observers {
+        observer.observe(event);
+    }
+}
+
+/// Emits [`Event::Verifier`] with the given origin.
+pub(crate) fn verifier(origin: Origin) {
+    emit(&Event::Verifier { origin });
+}
+
+/// Emits [`Event::Code`] for the given verifier, provided it was generated.
+pub(crate) fn code(verifier: &Verifier<'_>, method: Method) {
+    if let Some(origin) = verifier.origin() {
+        emit(&Event::Code { origin, method });
+    }
+}
@@ -0,0 +1,396 @@
+//! Expiring codes and challenges.
+//!
+//! Authorization codes are short-lived, and so are the challenges attached to them.
+//! [`ExpiringCode`] and [`TimedChallenge`] wrap [`Code`] and [`Challenge`] respectively,
+//! recording the creation time and the time-to-live in [`Expiry`].
+//!
+//! Both types expose their [`Expiry`], which provides [`is_expired`] and [`remaining`],
+//! and refuse verification after expiry. Verification records both the verifier and the expiry
+//! checks via [`VerifyOutcome`], so that the time taken does not depend on which of the checks
+//! failed.
+//!
+//! Deadlines overflowing [`SystemTime`] are never reached.
+//!
+//! Every time-dependent method has the `_using` counterpart accepting any [`Clock`].
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//!
+//! use pkce_std::{clock::ManualClock, code::Code, expiring::TimedChallenge};
+//!
+//! let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! let challenge = TimedChallenge::new_using(challenge, Duration::from_secs(60), &clock);
+//!
+//! assert!(challenge.verify_using(&verifier, &clock));
+//!
+//! clock.advance(Duration::from_secs(45));
+//!
+//! let expiry = challenge.expiry();
+//!
+//! assert_eq!(expiry.remaining_using(&clock), Duration::from_secs(15));
+//!
+//! clock.advance(Duration::from_secs(15));
+//!
+//! assert!(expiry.is_expired_using(&clock));
+//! assert!(!challenge.verify_using(&verifier, &clock));
+//! ```
+//!
+//! [`is_expired`]: Expiry::is_expired
+//! [`remaining`]: Expiry::remaining
+
+use std::time::{Duration, SystemTime};
+
+use crate::{
+    challenge::Challenge,
+    clock::{Clock, SystemClock},
+    code::Code,
+    outcome::{Check, VerifyOutcome},
+    verifier::Verifier,
+};
+
+/// Represents creation times and time-to-live values.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Expiry {
+    created: SystemTime,
+    ttl: Duration,
+}
+
+impl Expiry {
+    /// Constructs [`Self`] created at the given time and expiring after `ttl`.
+    pub const fn new(created: SystemTime, ttl: Duration) -> Self {
+        Self { created, ttl }
+    }
+
+    /// Constructs [`Self`] created now according to the given clock, expiring after `ttl`.
+    pub fn now_using<C: Clock + ?Sized>(ttl: Duration, clock: &C) -> Self {
+        Self::new(clock.now(), ttl)
+    }
+
+    /// Constructs [`Self`] created now, expiring after `ttl`.
+    pub fn now(ttl: Duration) -> Self {
+        Self::now_using(ttl, &SystemClock)
+    }
+
+    /// Returns the creation time.
+    pub const fn created(self) -> SystemTime {
+        self.created
+    }
+
+    /// Returns the time-to-live.
+    pub const fn ttl(self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the time at which [`Self`] expires.
+    ///
+    /// Returns [`None`] if the deadline overflows [`SystemTime`], meaning it is never reached.
+    pub fn deadline(self) -> Option<SystemTime> {
+        self.created.checked_add(self.ttl)
+    }
+
+    /// Checks whether [`Self`] has expired at the given time.
+    pub fn is_expired_at(self, now: SystemTime) -> bool {
+        self.deadline().is_some_and(|deadline| now >= deadline)
+    }
+
+    /// Checks whether [`Self`] has expired according to the given clock.
+    pub fn is_expired_using<C: Clock + ?Sized>(self, clock: &C) -> bool {
+        self.is_expired_at(clock.now())
+    }
+
+    /// Checks whether [`Self`] has expired.
+    pub fn is_expired(self) -> bool {
+        self.is_expired_using(&SystemClock)
+    }
+
+    /// Returns the time remaining until expiry at the given time.
+    ///
+    /// Returns [`Duration::ZERO`] if [`Self`] has expired, and [`Duration::MAX`]
+    /// if it never expires.
+    pub fn remaining_at(self, now: SystemTime) -> Duration {
+        self.deadline().map_or(Duration::MAX, |deadline| {
+            deadline.duration_since(now).unwrap_or_default()
+        })
+    }
+
+    /// Returns the time remaining until expiry according to the given clock.
+    ///
+    /// See [`remaining_at`] for more information.
+    ///
+    /// [`remaining_at`]: Self::remaining_at
+    pub fn remaining_using<C: Clock + ?Sized>(self, clock: &C) -> Duration {
+        self.remaining_at(clock.now())
+    }
+
+    /// Returns the time remaining until expiry.
+    ///
+    /// See [`remaining_at`] for more information.
+    ///
+    /// [`remaining_at`]: Self::remaining_at
+    pub fn remaining(self) -> Duration {
+        self.remaining_using(&SystemClock)
+    }
+
+    /// Records checking that [`Self`] has not expired according to the given clock.
+    fn record_using<C: Clock + ?Sized>(self, outcome: VerifyOutcome, clock: &C) -> VerifyOutcome {
+        outcome.check(Check::Expiry, !self.is_expired_using(clock))
+    }
+}
+
+/// Represents challenges with expiry.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct TimedChallenge {
+    challenge: Challenge,
+    expiry: Expiry,
+}
+
+impl TimedChallenge {
+    /// Constructs [`Self`] with the given expiry.
+    pub const fn with_expiry(challenge: Challenge, expiry: Expiry) -> Self {
+        Self { challenge, expiry }
+    }
+
+    /// Constructs [`Self`] created at the given time and expiring after `ttl`.
+    pub const fn with_created(challenge: Challenge, created: SystemTime, ttl: Duration) -> Self {
+        Self::with_expiry(challenge, Expiry::new(created, ttl))
+    }
+
+    /// Constructs [`Self`] created now according to the given clock, expiring after `ttl`.
+    pub fn new_using<C: Clock + ?Sized>(challenge: Challenge, ttl: Duration, clock: &C) -> Self {
+        Self::with_expiry(challenge, Expiry::now_using(ttl, clock))
+    }
+
+    /// Constructs [`Self`] created now, expiring after `ttl`.
+    pub fn new(challenge: Challenge, ttl: Duration) -> Self {
+        Self::new_using(challenge, ttl, &SystemClock)
+    }
+
+    /// Returns the wrapped challenge.
+    pub const fn challenge(&self) -> &Challenge {
+        &self.challenge
+    }
+
+    /// Consumes [`Self`], returning the wrapped challenge.
+    pub fn into_challenge(self) -> Challenge {
+        self.challenge
+    }
+
+    /// Returns the expiry.
+    pub const fn expiry(&self) -> Expiry {
+        self.expiry
+    }
+
+    /// Records verifying the challenge against the given verifier and checking expiry
+    /// according to the given clock.
+    pub fn outcome_using<C: Clock + ?Sized>(
+        &self,
+        verifier: &Verifier<'_>,
+        clock: &C,
+    ) -> VerifyOutcome {
+        let outcome = VerifyOutcome::new().verifier(verifier, &self.challenge);
+
+        self.expiry.record_using(outcome, clock)
+    }
+
+    /// Verifies the challenge against the given verifier, refusing verification
+    /// if [`Self`] has expired according to the given clock.
+    pub fn verify_using<C: Clock + ?Sized>(&self, verifier: &Verifier<'_>, clock: &C) -> bool {
+        self.outcome_using(verifier, clock).is_accepted()
+    }
+
+    /// Verifies the challenge against the given verifier, refusing verification
+    /// if [`Self`] has expired.
+    pub fn verify(&self, verifier: &Verifier<'_>) -> bool {
+        self.verify_using(verifier, &SystemClock)
+    }
+}
+
+/// Represents codes with expiry.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct ExpiringCode<'c> {
+    code: Code<'c>,
+    expiry: Expiry,
+}
+
+impl<'c> ExpiringCode<'c> {
+    /// Constructs [`Self`] with the given expiry.
+    pub const fn with_expiry(code: Code<'c>, expiry: Expiry) -> Self {
+        Self { code, expiry }
+    }
+
+    /// Constructs [`Self`] created at the given time and expiring after `ttl`.
+    pub const fn with_created(code: Code<'c>, created: SystemTime, ttl: Duration) -> Self {
+        Self::with_expiry(code, Expiry::new(created, ttl))
+    }
+
+    /// Constructs [`Self`] created now according to the given clock, expiring after `ttl`.
+    pub fn new_using<C: Clock + ?Sized>(code: Code<'c>, ttl: Duration, clock: &C) -> Self {
+        Self::with_expiry(code, Expiry::now_using(ttl, clock))
+    }
+
+    /// Constructs [`Self`] created now, expiring after `ttl`.
+    pub fn new(code: Code<'c>, ttl: Duration) -> Self {
+        Self::new_using(code, ttl, &SystemClock)
+    }
+
+    /// Returns the wrapped code.
+    pub const fn code(&self) -> &Code<'c> {
+        &self.code
+    }
+
+    /// Consumes [`Self`], returning the wrapped code.
+    pub fn into_code(self) -> Code<'c> {
+        self.code
+    }
+
+    /// Consumes [`Self`], returning the verifier and the challenge expiring along with it.
+    pub fn into_pair(self) -> (Verifier<'c>, TimedChallenge) {
+        let (verifier, challenge) = self.code.into_pair();
+
+        let challenge = TimedChallenge::with_expiry(challenge, self.expiry);
+
+        (verifier, challenge)
+    }
+
+    /// Returns the expiry.
+    pub const fn expiry(&self) -> Expiry {
+        self.expiry
+    }
+
+    /// Records verifying the given challenge against the verifier of the code and checking
+    /// expiry according to the given clock.
+    pub fn outcome_using<C: Clock + ?Sized>(
+        &self,
+        challenge: &Challenge,
+        clock: &C,
+    ) -> VerifyOutcome {
+        let outcome = VerifyOutcome::new().verifier(self.code.verifier(), challenge);
+
+        self.expiry.record_using(outcome, clock)
+    }
+
+    /// Verifies the given challenge against the verifier of the code, refusing verification
+    /// if [`Self`] has expired according to the given clock.
+    pub fn verify_using<C: Clock + ?Sized>(&self, challenge: &Challenge, clock: &C) -> bool {
+        self.outcome_using(challenge, clock).is_accepted()
+    }
+
+    /// Verifies the given challenge against the verifier of the code, refusing verification
+    /// if [`Self`] has expired.
+    pub fn verify(&self, challenge: &Challenge) -> bool {
+        self.verify_using(challenge, &SystemClock)
+    }
+}
+
+impl<'c> From<ExpiringCode<'c>> for Code<'c> {
+    fn from(expiring: ExpiringCode<'c>) -> Self {
+        expiring.into_code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        clock::ManualClock, code::Code, expiring::ExpiringCode, outcome::Check, verifier::Verifier,
+    };
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn code() -> Code<'static> {
+        let verifier = Verifier::borrowed(VERIFIER).unwrap().into_owned();
+
+        let challenge = verifier.challenge();
+
+        Code::new(verifier, challenge)
+    }
+
+    #[test]
+    fn expiry() {
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+
+        let code = ExpiringCode::new_using(code(), TTL, &clock);
+
+        let expiry = code.expiry();
+
+        assert!(!expiry.is_expired_using(&clock));
+        assert_eq!(expiry.remaining_using(&clock), TTL);
+
+        clock.advance(TTL);
+
+        assert!(expiry.is_expired_using(&clock));
+        assert_eq!(expiry.remaining_using(&clock), Duration::ZERO);
+    }
+
+    #[test]
+    fn overflow() {
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+
+        let code = ExpiringCode::new_using(code(), Duration::MAX, &clock);
+
+        let expiry = code.expiry();
+
+        assert!(expiry.deadline().is_none());
+
+        clock.advance(TTL);
+
+        assert!(!expiry.is_expired_using(&clock));
+        assert_eq!(expiry.remaining_using(&clock), Duration::MAX);
+
+        let challenge = code.code().challenge().clone();
+
+        assert!(code.verify_using(&challenge, &clock));
+    }
+
+    #[test]
+    fn verify() {
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+
+        let code = ExpiringCode::new_using(code(), TTL, &clock);
+
+        let challenge = code.code().challenge().clone();
+
+        assert!(code.verify_using(&challenge, &clock));
+
+        clock.advance(TTL);
+
+        let outcome = code.outcome_using(&challenge, &clock);
+
+        assert!(outcome.has_failed(Check::Expiry));
+        assert!(!outcome.has_failed(Check::Verifier));
+    }
+
+    #[test]
+    fn into_pair() {
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+
+        let code = ExpiringCode::new_using(code(), TTL, &clock);
+
+        let expiry = code.expiry();
+
+        let (verifier, challenge) = code.into_pair();
+
+        assert_eq!(challenge.expiry(), expiry);
+        assert!(challenge.verify_using(&verifier, &clock));
+    }
+}
@@ -0,0 +1,95 @@
+//! Log-safe fingerprints of PKCE code verifiers.
+//!
+//! Logging verifiers leaks secrets, yet correlating log lines related to the same verifier
+//! is often needed. The [`Fingerprint`] type is a short, non-reversible digest of the verifier,
+//! which can be logged instead.
+//!
+//! Fingerprints are computed as the first [`LENGTH`] bytes of the domain-separated SHA-256 digest
+//! of the verifier, so they can not be linked to the challenges derived from the same verifier.
+//!
+//! With the `log` feature enabled, verifier generation and verification events are emitted
+//! through the [`log`] facade, identifying verifiers by their fingerprints.
+//!
+//! [`log`]: https://docs.rs/log
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::Code;
+//!
+//! let code = Code::generate_default();
+//!
+//! let fingerprint = code.fingerprint();
+//!
+//! let (verifier, _) = code.into_pair();
+//!
+//! assert_eq!(verifier.fingerprint(), fingerprint);
+//!
+//! assert_eq!(fingerprint.to_string().len(), 16);
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+use sha2::{Digest, Sha256};
+
+/// The length of fingerprints, in bytes.
+pub const LENGTH: usize = 8;
+
+/// The domain used to separate fingerprints from other digests.
+pub const DOMAIN: &[u8] = b"pkce-std/fingerprint";
+
+/// Represents fingerprints of verifiers.
+///
+/// Fingerprints are displayed as lowercase hexadecimal strings.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fingerprint {
+    bytes: [u8; LENGTH],
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.bytes
+            .iter()
+            .try_for_each(|byte| write!(formatter, "{byte:02x}"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Fingerprint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Fingerprint {
+    /// Constructs [`Self`] from the given bytes.
+    pub const fn new(bytes: [u8; LENGTH]) -> Self {
+        Self { bytes }
+    }
+
+    /// Computes the fingerprint of the given string.
+    pub fn compute<S: AsRef<str>>(string: S) -> Self {
+        let digest = Sha256::new()
+            .chain_update(DOMAIN)
+            .chain_update(string.as_ref())
+            .finalize();
+
+        let mut bytes = [0; LENGTH];
+
+        bytes.copy_from_slice(&digest[..LENGTH]);
+
+        Self::new(bytes)
+    }
+
+    /// Returns the contained bytes.
+    pub const fn get(self) -> [u8; LENGTH] {
+        self.bytes
+    }
+}
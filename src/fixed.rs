@@ -0,0 +1,266 @@
+//! PKCE code verifiers of fixed lengths.
+//!
+//! Protocol implementations often standardize on one verifier length.
+//! The [`FixedVerifier<'_, LEN>`] type wraps [`Verifier<'_>`] values of exactly `LEN` characters,
+//! with `LEN` checked against the [standard] bounds at compile time.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::fixed::FixedVerifier;
+//!
+//! let verifier = FixedVerifier::<64>::generate();
+//!
+//! assert_eq!(verifier.get().len(), 64);
+//!
+//! let challenge = verifier.challenge();
+//!
+//! assert!(verifier.verify(&challenge));
+//! ```
+//!
+//! Lengths outside of the valid range are rejected at compile time:
+//!
+//! ```compile_fail
+//! use pkce_std::fixed::FixedVerifier;
+//!
+//! let verifier = FixedVerifier::<42>::generate();
+//! ```
+//!
+//! [`FixedVerifier<'_, LEN>`]: FixedVerifier
+//! [`Verifier<'_>`]: Verifier
+//! [standard]: https://datatracker.ietf.org/doc/html/rfc7636#section-4.1
+
+use std::{borrow::Cow, fmt};
+
+#[cfg(feature = "static")]
+use into_static::IntoStatic;
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::Challenge,
+    error::ErrorKind,
+    length::Length,
+    method::Method,
+    verifier::{self, Verifier},
+};
+
+/// Represents errors that can occur when constructing fixed verifiers.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// Invalid verifier.
+    #[error("invalid verifier")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Verifier(#[from] verifier::Error),
+
+    /// Verifier length mismatch.
+    #[error("expected verifier of length `{expected}`, got `{actual}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::fixed::mismatch),
+            help("make sure the verifier has length `{expected}`")
+        )
+    )]
+    Mismatch {
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        actual: usize,
+    },
+}
+
+impl Error {
+    /// Constructs [`Self::Mismatch`].
+    pub const fn mismatch(expected: usize, actual: usize) -> Self {
+        Self::Mismatch { expected, actual }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Verifier(error) => error.kind(),
+            Self::Mismatch { .. } => ErrorKind::Mismatch,
+        }
+    }
+}
+
+/// Represents verifiers of exactly `LEN` characters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedVerifier<'v, const LEN: usize> {
+    verifier: Verifier<'v>,
+}
+
+impl<const LEN: usize> fmt::Display for FixedVerifier<'_, LEN> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.verifier.fmt(formatter)
+    }
+}
+
+impl<const LEN: usize> AsRef<str> for FixedVerifier<'_, LEN> {
+    fn as_ref(&self) -> &str {
+        self.get()
+    }
+}
+
+impl<'v, const LEN: usize> AsRef<Verifier<'v>> for FixedVerifier<'v, LEN> {
+    fn as_ref(&self) -> &Verifier<'v> {
+        self.verifier()
+    }
+}
+
+impl<'v, const LEN: usize> From<FixedVerifier<'v, LEN>> for Verifier<'v> {
+    fn from(fixed: FixedVerifier<'v, LEN>) -> Self {
+        fixed.into_verifier()
+    }
+}
+
+impl<'v, const LEN: usize> TryFrom<Verifier<'v>> for FixedVerifier<'v, LEN> {
+    type Error = Error;
+
+    fn try_from(verifier: Verifier<'v>) -> Result<Self, Self::Error> {
+        Self::from_verifier(verifier)
+    }
+}
+
+impl<const LEN: usize> FixedVerifier<'_, LEN> {
+    /// The length of [`Self`], checked at compile time.
+    ///
+    /// Referring to this constant fails to compile if `LEN` is out of the valid range.
+    pub const LENGTH: Length = match Length::new_ok(LEN) {
+        Some(length) => length,
+        None => panic!("fixed verifier length is out of the valid range"),
+    };
+
+    /// Generates random [`Self`].
+    pub fn generate() -> Self {
+        let verifier = Verifier::generate(Self::LENGTH);
+
+        // SAFETY: the generated verifier has length of exactly `LEN`
+        unsafe { Self::new_unchecked(verifier) }
+    }
+}
+
+impl<'v, const LEN: usize> FixedVerifier<'v, LEN> {
+    /// Constructs [`Self`] from the given [`Verifier<'_>`], provided it has length `LEN`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Mismatch`] if the length of the verifier is not `LEN`.
+    ///
+    /// [`Verifier<'_>`]: Verifier
+    pub fn from_verifier(verifier: Verifier<'v>) -> Result<Self, Error> {
+        let expected = Self::LENGTH.get();
+        let actual = verifier.get().len();
+
+        if actual != expected {
+            return Err(Error::mismatch(expected, actual));
+        }
+
+        // SAFETY: the verifier has length of exactly `LEN`
+        Ok(unsafe { Self::new_unchecked(verifier) })
+    }
+
+    /// Constructs [`Self`] without checking the length of the verifier.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the verifier has length `LEN`.
+    pub const unsafe fn new_unchecked(verifier: Verifier<'v>) -> Self {
+        Self { verifier }
+    }
+
+    /// Constructs [`Self`], provided that the given value is valid and has length `LEN`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Verifier`] if the value is not a valid verifier,
+    /// and [`Error::Mismatch`] if its length is not `LEN`.
+    pub fn new(value: Cow<'v, str>) -> Result<Self, Error> {
+        let verifier = Verifier::new(value)?;
+
+        Self::from_verifier(verifier)
+    }
+
+    /// Constructs [`Self`] from borrowed `value`, provided it is valid and has length `LEN`.
+    ///
+    /// # Errors
+    ///
+    /// See [`new`] for more information.
+    ///
+    /// [`new`]: Self::new
+    pub fn borrowed(value: &'v str) -> Result<Self, Error> {
+        Self::new(Cow::Borrowed(value))
+    }
+
+    /// Constructs [`Self`] from owned `value`, provided it is valid and has length `LEN`.
+    ///
+    /// # Errors
+    ///
+    /// See [`new`] for more information.
+    ///
+    /// [`new`]: Self::new
+    pub fn owned(value: String) -> Result<Self, Error> {
+        Self::new(Cow::Owned(value))
+    }
+
+    /// Returns the contained [`Verifier<'_>`].
+    ///
+    /// [`Verifier<'_>`]: Verifier
+    pub const fn verifier(&self) -> &Verifier<'v> {
+        &self.verifier
+    }
+
+    /// Consumes [`Self`], returning the contained [`Verifier<'_>`].
+    ///
+    /// [`Verifier<'_>`]: Verifier
+    pub fn into_verifier(self) -> Verifier<'v> {
+        self.verifier
+    }
+
+    /// Returns the verifier string.
+    pub fn get(&self) -> &str {
+        self.verifier.get()
+    }
+
+    /// Computes the [`Challenge`] of [`Self`] with the given [`Method`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge, see [`Verifier::challenge_using`].
+    pub fn challenge_using(&self, method: Method) -> Challenge {
+        self.verifier.challenge_using(method)
+    }
+
+    /// Computes the [`Challenge`] of [`Self`] with the default [`Method`].
+    pub fn challenge(&self) -> Challenge {
+        self.verifier.challenge()
+    }
+
+    /// Verifies that the given [`Challenge`] corresponds to [`Self`].
+    pub fn verify(&self, challenge: &Challenge) -> bool {
+        self.verifier.verify(challenge)
+    }
+}
+
+/// An alias for [`FixedVerifier<'static, LEN>`].
+#[cfg(feature = "static")]
+pub type StaticFixedVerifier<const LEN: usize> = FixedVerifier<'static, LEN>;
+
+#[cfg(feature = "static")]
+impl<const LEN: usize> IntoStatic for FixedVerifier<'_, LEN> {
+    type Static = StaticFixedVerifier<LEN>;
+
+    fn into_static(self) -> Self::Static {
+        // SAFETY: calling `into_static` does not change the verifier length
+        unsafe { Self::Static::new_unchecked(self.verifier.into_static()) }
+    }
+}
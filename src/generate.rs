@@ -3,33 +3,247 @@
 //! This module provides two functions for generating random bytes and strings:
 //! [`bytes`] and [`string`] accepting the desired length as [`Count`] and [`Length`] respectively.
 //!
+//! Both functions use the thread-local generator; `bytes_with` and `string_with` accept
+//! the generator to use instead, for instance, hardware or deterministic ones.
+//!
+//! Allocation-free generation is available via [`fill`] and `fill_with`, which write
+//! random characters into caller-provided buffers.
+//!
+//! # Backends
+//!
+//! The `rand` feature (enabled by default) provides the generation via the [`rand`] crate.
+//!
+//! Otherwise, [`bytes`], [`string`] and [`fill`] draw entropy directly from the operating
+//! system via the [`getrandom`] crate, sampling characters without bias using rejection
+//! sampling. This is also the case when the `os-rng-only` feature is enabled, which selects
+//! this backend even if `rand` is enabled too (for instance, by other dependencies),
+//! while keeping the APIs accepting generators available.
+//!
+//! Regardless of the backend, [`try_fill`] and [`try_string`] draw entropy directly from
+//! the operating system, returning [`EntropyError`] instead of panicking on failures.
+//!
+//! [`rand`]: https://docs.rs/rand
+//! [`getrandom`]: https://docs.rs/getrandom
+//!
 //! Because of the imposed length restrictions, the functions are safe to use
 //! in the context of this crate. See [`count`] and [`length`] for more information.
 //!
 //! [`count`]: crate::count
 //! [`length`]: crate::length
 
-#[cfg(feature = "unsafe-assert")]
+#[cfg(all(feature = "rand", feature = "unsafe-assert"))]
 use std::hint::assert_unchecked;
 
-use rand::{Rng, RngCore, distr::Uniform, rng};
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+#[cfg(feature = "rand")]
+use rand::{CryptoRng, Rng, distr::Uniform};
 
-use crate::{
-    check::chars::{CHARS, LENGTH},
-    count::Count,
-    length::Length,
-};
+#[cfg(all(feature = "rand", not(feature = "os-rng-only")))]
+use rand::rng;
+
+use thiserror::Error;
+
+#[cfg(feature = "rand")]
+use crate::charset::{CHARS, LENGTH};
+
+use crate::{charset::BYTES, count::Count, error::ErrorKind, length::Length};
+
+/// Represents errors that occur when the operating system fails to provide entropy.
+#[derive(Debug, Error)]
+#[error("failed to get entropy from the operating system")]
+#[cfg_attr(
+    feature = "diagnostics",
+    derive(Diagnostic),
+    diagnostic(
+        code(pkce_std::generate::entropy),
+        help("make sure the operating system entropy source is available")
+    )
+)]
+pub struct EntropyError(#[source] getrandom::Error);
+
+impl EntropyError {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::Entropy
+    }
+}
 
 /// Generates `count` random bytes.
+#[cfg(all(feature = "rand", not(feature = "os-rng-only")))]
+pub fn bytes(count: Count) -> Vec<u8> {
+    bytes_with(&mut rng(), count)
+}
+
+/// The message used when panicking on entropy failures.
+pub const ENTROPY: &str = "failed to get entropy from the operating system";
+
+pub(crate) fn try_entropy(buffer: &mut [u8]) -> Result<(), EntropyError> {
+    getrandom::fill(buffer).map_err(EntropyError)
+}
+
+#[cfg(any(feature = "os-rng-only", not(feature = "rand")))]
+fn entropy(buffer: &mut [u8]) {
+    try_entropy(buffer).expect(ENTROPY);
+}
+
+/// Generates `count` random bytes using the operating system entropy.
+///
+/// # Panics
+///
+/// Panics if the operating system fails to provide entropy.
+#[cfg(any(feature = "os-rng-only", not(feature = "rand")))]
 pub fn bytes(count: Count) -> Vec<u8> {
     let mut data = vec![0; count.get()];
 
-    rng().fill_bytes(&mut data);
+    entropy(&mut data);
+
+    data
+}
+
+/// Generates `count` random bytes using the given generator.
+#[cfg(feature = "rand")]
+pub fn bytes_with<R: CryptoRng + Rng + ?Sized>(rng: &mut R, count: Count) -> Vec<u8> {
+    let mut data = vec![0; count.get()];
+
+    rng.fill_bytes(&mut data);
 
     data
 }
 
 /// Generates random strings of `length` characters from the [`CHARS`] set.
+#[cfg(all(feature = "rand", not(feature = "os-rng-only")))]
+pub fn string(length: Length) -> String {
+    string_with(&mut rng(), length)
+}
+
+/// Fills the given buffer with random characters from the [`CHARS`] set.
+///
+/// This function does not allocate.
+#[cfg(all(feature = "rand", not(feature = "os-rng-only")))]
+pub fn fill(buffer: &mut [u8]) {
+    fill_with(&mut rng(), buffer);
+}
+
+/// The size of the buffer used when sampling characters.
+const BUFFER: usize = 64;
+
+/// Fills the given buffer with random characters from the given non-empty set,
+/// drawing random bytes from `entropy`.
+///
+/// Random bytes greater than or equal to the greatest multiple of the set length
+/// not exceeding `256` are rejected, and the rest are reduced modulo the set length,
+/// so that every character is equally likely.
+///
+/// # Errors
+///
+/// Returns any error returned by `entropy`.
+pub(crate) fn sample<E, F: FnMut(&mut [u8]) -> Result<(), E>>(
+    chars: &[u8],
+    buffer: &mut [u8],
+    mut entropy: F,
+) -> Result<(), E> {
+    let count = chars.len();
+
+    let limit = (usize::from(u8::MAX) + 1) / count * count;
+
+    let mut random = [0; BUFFER];
+    let mut filled = 0;
+
+    while filled < buffer.len() {
+        entropy(&mut random)?;
+
+        let sampled = random
+            .iter()
+            .map(|&byte| usize::from(byte))
+            .filter(|&value| value < limit)
+            .map(|value| chars[value % count]);
+
+        for (slot, byte) in buffer[filled..].iter_mut().zip(sampled) {
+            *slot = byte;
+
+            filled += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills the given buffer with random characters from the [`CHARS`] set
+/// using the operating system entropy.
+///
+/// Characters are sampled without bias using rejection sampling.
+///
+/// This function does not allocate.
+///
+/// # Errors
+///
+/// Returns [`EntropyError`] if the operating system fails to provide entropy.
+///
+/// [`CHARS`]: crate::charset::CHARS
+pub fn try_fill(buffer: &mut [u8]) -> Result<(), EntropyError> {
+    sample(BYTES, buffer, try_entropy)
+}
+
+/// Fills the given buffer with random characters from the [`CHARS`] set
+/// using the operating system entropy.
+///
+/// See [`try_fill`] for more information.
+///
+/// # Panics
+///
+/// Panics if the operating system fails to provide entropy.
+///
+/// [`CHARS`]: crate::charset::CHARS
+#[cfg(any(feature = "os-rng-only", not(feature = "rand")))]
+pub fn fill(buffer: &mut [u8]) {
+    try_fill(buffer).expect(ENTROPY);
+}
+
+/// Generates random strings of `length` characters from the [`CHARS`] set
+/// using the operating system entropy.
+///
+/// See [`try_fill`] for more information.
+///
+/// # Errors
+///
+/// Returns [`EntropyError`] if the operating system fails to provide entropy.
+///
+/// [`CHARS`]: crate::charset::CHARS
+pub fn try_string(length: Length) -> Result<String, EntropyError> {
+    let mut data = vec![0; length.get()];
+
+    try_fill(&mut data)?;
+
+    // SAFETY: `try_fill` only writes ASCII characters from the `CHARS` set
+    let string = unsafe { String::from_utf8_unchecked(data) };
+
+    Ok(string)
+}
+
+/// Generates random strings of `length` characters from the [`CHARS`] set
+/// using the operating system entropy.
+///
+/// See [`fill`] for more information.
+///
+/// # Panics
+///
+/// Panics if the operating system fails to provide entropy.
+///
+/// [`CHARS`]: crate::charset::CHARS
+#[cfg(any(feature = "os-rng-only", not(feature = "rand")))]
+pub fn string(length: Length) -> String {
+    let mut data = vec![0; length.get()];
+
+    fill(&mut data);
+
+    // SAFETY: `fill` only writes ASCII characters from the `CHARS` set
+    unsafe { String::from_utf8_unchecked(data) }
+}
+
+/// Generates random strings of `length` characters from the [`CHARS`] set
+/// using the given generator.
 ///
 /// # Panics
 ///
@@ -47,11 +261,55 @@ pub fn bytes(count: Count) -> Vec<u8> {
 /// ## Feature
 ///
 /// Moreover, the `unsafe-assert` feature can be enabled to `assume` the bounds are correct.
-pub fn string(length: Length) -> String {
+#[cfg(feature = "rand")]
+pub fn string_with<R: CryptoRng + Rng + ?Sized>(rng: &mut R, length: Length) -> String {
+    sample_string(rng, length)
+}
+
+/// Fills the given buffer with random characters from the [`CHARS`] set
+/// using the given generator.
+///
+/// This function does not allocate.
+///
+/// # Panics
+///
+/// This function will not panic, see [`string_with`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::{check::string::check_str, generate::fill_with};
+///
+/// let mut buffer = [0; 43];
+///
+/// fill_with(&mut rand::rng(), &mut buffer);
+///
+/// assert!(check_str(str::from_utf8(&buffer).unwrap()).is_ok());
+/// ```
+#[cfg(feature = "rand")]
+pub fn fill_with<R: CryptoRng + Rng + ?Sized>(rng: &mut R, buffer: &mut [u8]) {
     let distribution = Uniform::new(0, LENGTH).unwrap();
 
-    rng()
-        .sample_iter(distribution)
+    for (slot, index) in buffer.iter_mut().zip(rng.sample_iter(distribution)) {
+        #[cfg(feature = "unsafe-assert")]
+        unsafe {
+            assert_unchecked(index < LENGTH);
+        }
+
+        *slot = CHARS[index] as u8;
+    }
+}
+
+/// Similar to [`string_with`], except the generator is not required to be [`CryptoRng`].
+///
+/// This is used in [`Distribution`] implementations, which can not impose such a requirement.
+///
+/// [`Distribution`]: rand::distr::Distribution
+#[cfg(feature = "rand")]
+pub(crate) fn sample_string<R: Rng + ?Sized>(rng: &mut R, length: Length) -> String {
+    let distribution = Uniform::new(0, LENGTH).unwrap();
+
+    rng.sample_iter(distribution)
         .take(length.get())
         .map(|index| {
             #[cfg(feature = "unsafe-assert")]
@@ -63,3 +321,72 @@ pub fn string(length: Length) -> String {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "rand")]
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[cfg(feature = "rand")]
+    use crate::generate::{bytes_with, string_with};
+
+    use crate::{
+        check::string::check_str,
+        count::Count,
+        generate::{bytes, fill, string},
+        length::Length,
+    };
+
+    #[test]
+    fn bytes_count() {
+        for count in [Count::MIN, Count::DEFAULT, Count::MAX] {
+            assert_eq!(bytes(count).len(), count.get());
+        }
+    }
+
+    #[test]
+    fn string_validity() {
+        for length in [Length::MIN, Length::DEFAULT, Length::MAX] {
+            let generated = string(length);
+
+            assert_eq!(generated.len(), length.get());
+
+            assert!(check_str(&generated).is_ok());
+        }
+    }
+
+    #[test]
+    fn fill_validity() {
+        let mut buffer = [0; Length::DEFAULT.get()];
+
+        fill(&mut buffer);
+
+        assert!(check_str(str::from_utf8(&buffer).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn entropy() {
+        let count = Count::default();
+
+        assert_ne!(bytes(count), bytes(count));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn seeded() {
+        let seed = 42;
+
+        let count = Count::default();
+        let length = Length::default();
+
+        assert_eq!(
+            bytes_with(&mut StdRng::seed_from_u64(seed), count),
+            bytes_with(&mut StdRng::seed_from_u64(seed), count),
+        );
+
+        assert_eq!(
+            string_with(&mut StdRng::seed_from_u64(seed), length),
+            string_with(&mut StdRng::seed_from_u64(seed), length),
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Guarding PKCE codes during the token exchange.
+//!
+//! Clients usually persist the verifier (keyed by the `state`) between the authorization
+//! and the token requests. If the exchange fails midway, for instance, with an error
+//! returned early or a panic, the verifier can be left behind both in memory and in the store.
+//!
+//! The [`CodeGuard`] type holds the verifier-challenge pair for the duration of the exchange,
+//! and on drop, regardless of the outcome, runs the provided cleanup (usually removing the
+//! store entry) and zeroes out the secrets.
+//!
+//! This module requires the `zeroize` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{cell::RefCell, collections::HashMap};
+//!
+//! use pkce_std::{code::Code, guard::CodeGuard};
+//!
+//! let store = RefCell::new(HashMap::new());
+//!
+//! let code = Code::generate_default();
+//!
+//! store.borrow_mut().insert("state", code.clone());
+//!
+//! {
+//!     let guard = CodeGuard::new(code, || {
+//!         store.borrow_mut().remove("state");
+//!     });
+//!
+//!     // send `guard.verifier()` in the token request
+//!
+//!     assert!(guard.verifier().verify(guard.challenge()));
+//! }
+//!
+//! assert!(store.borrow().is_empty());
+//! ```
+
+use std::fmt;
+
+use crate::{challenge::Challenge, code::Code, verifier::Verifier};
+
+/// Represents guards that clean up and zero out codes on drop.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+pub struct CodeGuard<'c, F: FnOnce()> {
+    verifier: Verifier<'c>,
+    challenge: Challenge,
+    cleanup: Option<F>,
+}
+
+impl<F: FnOnce()> fmt::Debug for CodeGuard<'_, F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CodeGuard")
+            .field("verifier", &self.verifier)
+            .field("challenge", &self.challenge)
+            .field("armed", &self.cleanup.is_some())
+            .finish()
+    }
+}
+
+impl<F: FnOnce()> Drop for CodeGuard<'_, F> {
+    fn drop(&mut self) {
+        if let Some(cleanup) = self.cleanup.take() {
+            cleanup();
+        }
+
+        // the verifier and the challenge are zeroed out when dropped right after
+    }
+}
+
+impl<'c, F: FnOnce()> CodeGuard<'c, F> {
+    /// Constructs [`Self`] from the given [`Code`] and cleanup.
+    ///
+    /// The cleanup is run exactly once, when [`Self`] is dropped. Note that panicking
+    /// in the cleanup while already panicking aborts the process.
+    pub fn new(code: Code<'c>, cleanup: F) -> Self {
+        let (verifier, challenge) = code.into_pair();
+
+        Self {
+            verifier,
+            challenge,
+            cleanup: Some(cleanup),
+        }
+    }
+
+    /// Returns the guarded [`Verifier<'_>`].
+    ///
+    /// [`Verifier<'_>`]: Verifier
+    pub const fn verifier(&self) -> &Verifier<'c> {
+        &self.verifier
+    }
+
+    /// Returns the guarded [`Challenge`].
+    pub const fn challenge(&self) -> &Challenge {
+        &self.challenge
+    }
+
+    /// Consumes [`Self`], running the cleanup and zeroing out the secrets.
+    ///
+    /// This is equivalent to dropping [`Self`], but makes the intent explicit.
+    pub fn finish(self) {
+        drop(self);
+    }
+}
@@ -2,7 +2,261 @@
 
 use sha2::{Digest, Sha256};
 
+#[cfg(feature = "extended-methods")]
+use sha2::{Sha384, Sha512};
+
+/// The length of SHA-256 digests, in bytes.
+pub const SHA256_LENGTH: usize = 32;
+
+/// The length of SHA-384 digests, in bytes.
+#[cfg(feature = "extended-methods")]
+pub const SHA384_LENGTH: usize = 48;
+
+/// The length of SHA-512 digests, in bytes.
+#[cfg(feature = "extended-methods")]
+pub const SHA512_LENGTH: usize = 64;
+
+/// The maximum length of digests produced by this module, in bytes.
+#[cfg(not(feature = "extended-methods"))]
+pub const MAX_LENGTH: usize = SHA256_LENGTH;
+
+/// The maximum length of digests produced by this module, in bytes.
+#[cfg(feature = "extended-methods")]
+pub const MAX_LENGTH: usize = SHA512_LENGTH;
+
 /// Hashes the given data using SHA-256.
 pub fn sha256<D: AsRef<[u8]>>(data: D) -> impl AsRef<[u8]> {
     Sha256::digest(data)
 }
+
+/// Hashes the given data using SHA-256 into the provided buffer without allocating.
+///
+/// # Examples
+///
+/// Deriving challenge secrets on the stack:
+///
+/// ```
+/// use pkce_std::{
+///     encoding::encode_into_slice,
+///     hash::{SHA256_LENGTH, sha256_into},
+///     verifier::Verifier,
+/// };
+///
+/// let verifier = Verifier::generate_default();
+///
+/// let mut digest = [0; SHA256_LENGTH];
+///
+/// sha256_into(verifier.get(), &mut digest);
+///
+/// let mut secret = [0; 43];
+///
+/// let written = encode_into_slice(digest, &mut secret).unwrap();
+///
+/// assert_eq!(&secret[..written], verifier.challenge().secret().as_bytes());
+/// ```
+pub fn sha256_into<D: AsRef<[u8]>>(data: D, output: &mut [u8; SHA256_LENGTH]) {
+    Sha256::new()
+        .chain_update(data)
+        .finalize_into(output.into());
+}
+
+/// The SHA-256 round constants.
+#[rustfmt::skip]
+const ROUNDS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// The initial SHA-256 state.
+#[rustfmt::skip]
+const INITIAL: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The size of SHA-256 blocks, in bytes.
+const BLOCK: usize = 64;
+
+/// Processes the given SHA-256 block, returning the updated state.
+const fn compress(state: [u32; 8], block: &[u8; BLOCK]) -> [u32; 8] {
+    let mut schedule = [0; 64];
+
+    let mut index = 0;
+
+    while index < 16 {
+        let offset = index * 4;
+
+        schedule[index] = u32::from_be_bytes([
+            block[offset],
+            block[offset + 1],
+            block[offset + 2],
+            block[offset + 3],
+        ]);
+
+        index += 1;
+    }
+
+    while index < 64 {
+        let previous = schedule[index - 2];
+        let early = schedule[index - 15];
+
+        let low = early.rotate_right(7) ^ early.rotate_right(18) ^ (early >> 3);
+        let high = previous.rotate_right(17) ^ previous.rotate_right(19) ^ (previous >> 10);
+
+        schedule[index] = schedule[index - 16]
+            .wrapping_add(low)
+            .wrapping_add(schedule[index - 7])
+            .wrapping_add(high);
+
+        index += 1;
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+
+    index = 0;
+
+    while index < 64 {
+        let high = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+
+        let first = h
+            .wrapping_add(high)
+            .wrapping_add(choice)
+            .wrapping_add(ROUNDS[index])
+            .wrapping_add(schedule[index]);
+
+        let low = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+
+        let second = low.wrapping_add(majority);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(first);
+        d = c;
+        c = b;
+        b = a;
+        a = first.wrapping_add(second);
+
+        index += 1;
+    }
+
+    [
+        state[0].wrapping_add(a),
+        state[1].wrapping_add(b),
+        state[2].wrapping_add(c),
+        state[3].wrapping_add(d),
+        state[4].wrapping_add(e),
+        state[5].wrapping_add(f),
+        state[6].wrapping_add(g),
+        state[7].wrapping_add(h),
+    ]
+}
+
+/// Hashes the given data using SHA-256 in `const` contexts.
+///
+/// This function is considerably slower than [`sha256`] at runtime,
+/// and is intended for computing digests at compile time.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::hash::{SHA256_LENGTH, const_sha256, sha256};
+///
+/// const DIGEST: [u8; SHA256_LENGTH] = const_sha256(b"Hello, world!");
+///
+/// assert_eq!(DIGEST, sha256("Hello, world!").as_ref());
+/// ```
+pub const fn const_sha256(data: &[u8]) -> [u8; SHA256_LENGTH] {
+    let length = data.len();
+
+    // the data, the `0x80` byte and the 8-byte length, padded to the block size
+    let blocks = (length + 9).div_ceil(BLOCK);
+    let total = blocks * BLOCK;
+
+    // the length is required to be represented in bits modulo `2^64`
+    let bits = (length as u64).wrapping_mul(8).to_be_bytes();
+
+    let mut state = INITIAL;
+
+    let mut position = 0;
+
+    while position < total {
+        let mut block = [0; BLOCK];
+
+        let mut index = 0;
+
+        while index < BLOCK {
+            let current = position + index;
+
+            block[index] = if current < length {
+                data[current]
+            } else if current == length {
+                0x80
+            } else if current >= total - 8 {
+                bits[current + 8 - total]
+            } else {
+                0
+            };
+
+            index += 1;
+        }
+
+        state = compress(state, &block);
+
+        position += BLOCK;
+    }
+
+    let mut output = [0; SHA256_LENGTH];
+
+    let mut index = 0;
+
+    while index < state.len() {
+        let [first, second, third, fourth] = state[index].to_be_bytes();
+
+        let offset = index * 4;
+
+        output[offset] = first;
+        output[offset + 1] = second;
+        output[offset + 2] = third;
+        output[offset + 3] = fourth;
+
+        index += 1;
+    }
+
+    output
+}
+
+/// Hashes the given data using SHA-384.
+#[cfg(feature = "extended-methods")]
+pub fn sha384<D: AsRef<[u8]>>(data: D) -> impl AsRef<[u8]> {
+    Sha384::digest(data)
+}
+
+/// Hashes the given data using SHA-512.
+#[cfg(feature = "extended-methods")]
+pub fn sha512<D: AsRef<[u8]>>(data: D) -> impl AsRef<[u8]> {
+    Sha512::digest(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::hash::{const_sha256, sha256};
+
+    proptest! {
+        #[test]
+        fn const_consistency(data in prop::collection::vec(any::<u8>(), 0..256)) {
+            let expected = sha256(&data);
+
+            prop_assert_eq!(&const_sha256(&data)[..], expected.as_ref());
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Building token requests with the [`http`] crate.
+//!
+//! Token requests are sent as `application/x-www-form-urlencoded` bodies, which include
+//! the `code_verifier` along with the regular parameters (such as `grant_type` and `code`).
+//!
+//! - [`token_body`] encodes the body, merging the given fields with the `code_verifier`;
+//! - [`token_request`] builds the `POST` [`Request`] with the encoded body.
+//!
+//! If the fields already contain `code_verifier`, it is replaced by the given verifier,
+//! so that the body never contains the parameter twice.
+//!
+//! This module requires the `http` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{http, verifier::Verifier};
+//!
+//! let verifier = Verifier::borrowed("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ").unwrap();
+//!
+//! let fields = [("grant_type", "authorization_code"), ("code", "code")];
+//!
+//! let request = http::token_request("https://example.com/token", &verifier, fields).unwrap();
+//!
+//! let body = String::from_utf8(request.into_body()).unwrap();
+//!
+//! assert_eq!(
+//!     body,
+//!     "grant_type=authorization_code&code=code&code_verifier=dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ",
+//! );
+//! ```
+//!
+//! [`http`]: https://docs.rs/http
+
+use ::http::{Method, Request, Uri, header};
+use form_urlencoded::Serializer;
+
+use crate::verifier::{CODE_VERIFIER, Verifier};
+
+/// The content type of token request bodies.
+pub const CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Encodes the token request body, merging the given fields with the `code_verifier`.
+///
+/// Any `code_verifier` contained in the fields is replaced by the given verifier.
+pub fn token_body<I, K, V>(verifier: &Verifier<'_>, fields: I) -> Vec<u8>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut serializer = Serializer::new(String::new());
+
+    for (key, value) in fields {
+        let key = key.as_ref();
+
+        if key != CODE_VERIFIER {
+            serializer.append_pair(key, value.as_ref());
+        }
+    }
+
+    let (key, value) = verifier.form_pair();
+
+    serializer.append_pair(key, value);
+
+    serializer.finish().into_bytes()
+}
+
+/// Builds the `POST` token request to the given URI, with the body encoded via [`token_body`].
+///
+/// # Errors
+///
+/// Returns [`http::Error`] if the URI is invalid.
+///
+/// [`http::Error`]: ::http::Error
+pub fn token_request<U, I, K, V>(
+    uri: U,
+    verifier: &Verifier<'_>,
+    fields: I,
+) -> Result<Request<Vec<u8>>, ::http::Error>
+where
+    Uri: TryFrom<U>,
+    <Uri as TryFrom<U>>::Error: Into<::http::Error>,
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, CONTENT_TYPE)
+        .body(token_body(verifier, fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{http::token_body, verifier::Verifier};
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    #[test]
+    fn replaces_verifier() {
+        let verifier = Verifier::borrowed(VERIFIER).unwrap();
+
+        let body = token_body(&verifier, [("code", "a b"), ("code_verifier", "other")]);
+
+        assert_eq!(
+            body,
+            format!("code=a+b&code_verifier={VERIFIER}").into_bytes()
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! PKCE code verifiers stored inline.
+//!
+//! The [`InlineVerifier<N>`] type stores verifiers of up to `N` characters in fixed buffers,
+//! so that clients without the heap can generate, validate, and verify them.
+//!
+//! The capacity `N` defaults to the [`MAX`] length, and is checked against the [standard] bounds
+//! at compile time.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{inline::InlineVerifier, length::Length};
+//!
+//! let verifier = InlineVerifier::<64>::generate(Length::new(64).unwrap());
+//!
+//! assert_eq!(verifier.get().len(), 64);
+//!
+//! let challenge = verifier.challenge();
+//!
+//! assert!(verifier.verify(&challenge));
+//! ```
+//!
+//! Verifiers longer than the capacity are rejected:
+//!
+//! ```
+//! use pkce_std::inline::InlineVerifier;
+//!
+//! let string = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+//!
+//! assert!(InlineVerifier::<43>::new(string).is_ok());
+//!
+//! assert!(InlineVerifier::<43>::new(&format!("{string}~")).is_err());
+//! ```
+//!
+//! [`InlineVerifier<N>`]: InlineVerifier
+//! [`MAX`]: crate::length::MAX
+//! [standard]: https://datatracker.ietf.org/doc/html/rfc7636#section-4.1
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+#[cfg(feature = "rand")]
+use rand::{CryptoRng, Rng};
+
+use thiserror::Error;
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[cfg(feature = "rand")]
+use crate::generate::fill_with;
+
+use crate::{
+    challenge::{self, Challenge},
+    ct,
+    error::ErrorKind,
+    generate::fill,
+    length::{self, Length},
+    method::Method,
+    verifier::{self, Verifier},
+};
+
+/// Represents errors that can occur when constructing inline verifiers.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// Invalid verifier.
+    #[error("invalid verifier")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Verifier(#[from] verifier::Error),
+
+    /// The verifier does not fit into the buffer.
+    #[error("expected verifier of length at most `{capacity}`, got `{length}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::inline::capacity),
+            help("make sure the verifier is at most `{capacity}` long")
+        )
+    )]
+    Capacity {
+        /// The capacity of the buffer.
+        capacity: usize,
+        /// The verifier length.
+        length: usize,
+    },
+}
+
+impl Error {
+    /// Constructs [`Self::Capacity`].
+    pub const fn capacity(capacity: usize, length: usize) -> Self {
+        Self::Capacity { capacity, length }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Verifier(error) => error.kind(),
+            Self::Capacity { .. } => ErrorKind::Overflow,
+        }
+    }
+}
+
+/// Represents verifiers of at most `N` characters stored inline.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Clone)]
+#[cfg_attr(feature = "expose-secrets", derive(Debug))]
+pub struct InlineVerifier<const N: usize = { length::MAX }> {
+    buffer: [u8; N],
+    length: usize,
+}
+
+#[cfg(feature = "zeroize")]
+impl<const N: usize> Drop for InlineVerifier<N> {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const N: usize> ZeroizeOnDrop for InlineVerifier<N> {}
+
+#[cfg(not(feature = "expose-secrets"))]
+impl<const N: usize> fmt::Debug for InlineVerifier<N> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("InlineVerifier")
+            .field("length", &self.length)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<const N: usize> fmt::Display for InlineVerifier<N> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(formatter)
+    }
+}
+
+impl<const N: usize> AsRef<str> for InlineVerifier<N> {
+    fn as_ref(&self) -> &str {
+        self.get()
+    }
+}
+
+impl<const N: usize> PartialEq for InlineVerifier<N> {
+    fn eq(&self, other: &Self) -> bool {
+        ct::eq(self.get(), other.get())
+    }
+}
+
+impl<const N: usize> Eq for InlineVerifier<N> {}
+
+impl<const N: usize> Hash for InlineVerifier<N> {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.get().hash(hasher);
+    }
+}
+
+impl<const N: usize> TryFrom<&Verifier<'_>> for InlineVerifier<N> {
+    type Error = Error;
+
+    fn try_from(verifier: &Verifier<'_>) -> Result<Self, Self::Error> {
+        Self::new(verifier.get())
+    }
+}
+
+impl<const N: usize> InlineVerifier<N> {
+    /// The capacity of [`Self`], checked at compile time.
+    ///
+    /// Referring to this constant fails to compile if `N` is out of the valid range.
+    pub const CAPACITY: Length = match Length::new_ok(N) {
+        Some(capacity) => capacity,
+        None => panic!("inline verifier capacity is out of the valid range"),
+    };
+
+    /// Constructs [`Self`], provided that the given value is valid and fits into the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Verifier`] if the value is not a valid verifier,
+    /// and [`Error::Capacity`] if it is longer than `N`.
+    pub fn new(value: &str) -> Result<Self, Error> {
+        Verifier::check(value)?;
+
+        let capacity = Self::CAPACITY.get();
+        let length = value.len();
+
+        if length > capacity {
+            return Err(Error::capacity(capacity, length));
+        }
+
+        // SAFETY: `value` is valid and fits into the buffer
+        Ok(unsafe { Self::new_unchecked(value) })
+    }
+
+    /// Constructs [`Self`] without checking the value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `value` is valid for [`Verifier`]
+    /// and is at most `N` characters long.
+    pub unsafe fn new_unchecked(value: &str) -> Self {
+        let mut buffer = [0; N];
+        let length = value.len();
+
+        buffer[..length].copy_from_slice(value.as_bytes());
+
+        Self { buffer, length }
+    }
+
+    /// Generates random [`Self`] with specified length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length is greater than `N`.
+    pub fn generate(length: Length) -> Self {
+        let mut verifier = Self::empty(length);
+
+        fill(verifier.bytes_mut());
+
+        verifier
+    }
+
+    /// Generates random [`Self`] with specified length using the given generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length is greater than `N`.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: CryptoRng + Rng + ?Sized>(rng: &mut R, length: Length) -> Self {
+        let mut verifier = Self::empty(length);
+
+        fill_with(rng, verifier.bytes_mut());
+
+        verifier
+    }
+
+    /// Generates random [`Self`] filling the entire buffer.
+    pub fn generate_full() -> Self {
+        Self::generate(Self::CAPACITY)
+    }
+
+    fn empty(length: Length) -> Self {
+        let capacity = Self::CAPACITY.get();
+        let length = length.get();
+
+        assert!(
+            length <= capacity,
+            "length `{length}` exceeds capacity `{capacity}`"
+        );
+
+        Self {
+            buffer: [0; N],
+            length,
+        }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer[..self.length]
+    }
+
+    /// Returns the verifier string.
+    pub fn get(&self) -> &str {
+        // SAFETY: the buffer is filled with valid characters up to the length,
+        // which are always ASCII and therefore valid UTF-8
+        unsafe { str::from_utf8_unchecked(&self.buffer[..self.length]) }
+    }
+
+    /// Returns the borrowed [`Verifier<'_>`] of [`Self`].
+    ///
+    /// [`Verifier<'_>`]: Verifier
+    pub fn as_verifier(&self) -> Verifier<'_> {
+        // SAFETY: `self` always contains valid verifiers
+        unsafe { Verifier::borrowed_unchecked(self.get()) }
+    }
+
+    /// Computes the [`Challenge`] of [`Self`] with the given [`Method`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method fails to derive valid challenge, see [`Verifier::challenge_using`].
+    pub fn challenge_using(&self, method: Method) -> Challenge {
+        self.as_verifier().challenge_using(method)
+    }
+
+    /// Computes the [`Challenge`] of [`Self`] with the given [`Method`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`challenge::Error`] if the method fails to derive valid challenge,
+    /// see [`Challenge::try_create_using`].
+    pub fn try_challenge_using(&self, method: Method) -> Result<Challenge, challenge::Error> {
+        self.as_verifier().try_challenge_using(method)
+    }
+
+    /// Computes the [`Challenge`] of [`Self`] with the default [`Method`].
+    pub fn challenge(&self) -> Challenge {
+        self.as_verifier().challenge()
+    }
+
+    /// Verifies that the given [`Challenge`] corresponds to [`Self`].
+    pub fn verify(&self, challenge: &Challenge) -> bool {
+        self.as_verifier().verify(challenge)
+    }
+
+    /// Verifies that the given [`Challenge`] corresponds to [`Self`] without allocating,
+    /// see [`Verifier::verify_decode`].
+    pub fn verify_decode(&self, challenge: &Challenge) -> bool {
+        self.as_verifier().verify_decode(challenge)
+    }
+}
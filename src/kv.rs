@@ -0,0 +1,579 @@
+//! Storing PKCE code challenges in key-value stores.
+//!
+//! Servers need to keep challenges between the authorization and the token requests.
+//! The [`KvChallengeStore<B>`] type stores challenges in any key-value store,
+//! provided it implements the tiny [`KvBackend`] trait, so that existing cache clients
+//! can be plugged in without this crate depending on each of them.
+//!
+//! Challenges are stored as `{method} {secret}` strings under `{prefix}challenge:{key}`,
+//! expiring after the configured time-to-live.
+//!
+//! Note that challenges using custom methods can not be loaded, failing with
+//! [`Error::Challenge`], since the store has no access to the [`MethodRegistry`].
+//!
+//! # Duplicates
+//!
+//! Stores can be configured to reject challenges with secrets already stored via
+//! [`KvChallengeStore::unique`], which usually indicates client bugs (reusing verifiers)
+//! or replay attempts. Stored secrets are indexed by their SHA-256 digests under
+//! `{prefix}digest:{digest}`, separately from challenges, and the index entries expire
+//! along with challenges, meaning that secrets can not be reused even after challenges
+//! are taken, until the time-to-live elapses.
+//!
+//! Index entries are created via [`KvBackend::set_if_absent`] before storing challenges,
+//! and removed if storing challenges fails.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{collections::HashMap, convert::Infallible, sync::Mutex, time::Duration};
+//!
+//! use pkce_std::{
+//!     code::Code,
+//!     kv::{Error, KvBackend, KvChallengeStore},
+//! };
+//!
+//! #[derive(Default)]
+//! struct Memory {
+//!     entries: Mutex<HashMap<String, String>>,
+//! }
+//!
+//! impl KvBackend for Memory {
+//!     type Error = Infallible;
+//!
+//!     fn get(&self, key: &str) -> Result<Option<String>, Self::Error> {
+//!         Ok(self.entries.lock().unwrap().get(key).cloned())
+//!     }
+//!
+//!     fn set(&self, key: &str, value: String, _ttl: Duration) -> Result<(), Self::Error> {
+//!         self.entries.lock().unwrap().insert(key.to_owned(), value);
+//!
+//!         Ok(())
+//!     }
+//!
+//!     fn delete(&self, key: &str) -> Result<(), Self::Error> {
+//!         self.entries.lock().unwrap().remove(key);
+//!
+//!         Ok(())
+//!     }
+//!
+//!     fn take(&self, key: &str) -> Result<Option<String>, Self::Error> {
+//!         Ok(self.entries.lock().unwrap().remove(key))
+//!     }
+//! }
+//!
+//! let store = KvChallengeStore::new(Memory::default(), Duration::from_secs(600)).unique(true);
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! store.insert("state", &challenge).unwrap();
+//!
+//! let error = store.insert("other", &challenge).unwrap_err();
+//!
+//! assert!(matches!(error, Error::Duplicate));
+//!
+//! let stored = store.take("state").unwrap().unwrap();
+//!
+//! assert!(verifier.verify(&stored));
+//!
+//! assert!(store.take("state").unwrap().is_none());
+//! ```
+//!
+//! [`KvChallengeStore<B>`]: KvChallengeStore
+//! [`MethodRegistry`]: crate::method::MethodRegistry
+
+use std::{
+    error::Error as StdError,
+    future::{self, Future},
+    time::Duration,
+};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::{self, Challenge},
+    encoding,
+    hash::sha256,
+    method::MethodName,
+    store::ChallengeStore,
+};
+
+/// The default key prefix.
+pub const PREFIX: &str = "pkce:";
+
+/// The separator between methods and secrets in stored values.
+pub const SEPARATOR: char = ' ';
+
+/// The infix of keys storing challenges.
+pub const CHALLENGE: &str = "challenge:";
+
+/// The infix of keys indexing stored secrets by their digests.
+pub const DIGEST: &str = "digest:";
+
+/// Represents minimal key-value store clients.
+///
+/// Implementations are expected to use interior mutability, as all methods take `&self`.
+///
+/// Note that this trait is synchronous, as [`KvChallengeStore`] resolves its [`ChallengeStore`]
+/// futures immediately, so it is meant for in-memory stores only. Clients of remote stores
+/// should implement [`ChallengeStore`] directly instead, so that the runtime is never blocked.
+pub trait KvBackend {
+    /// The error type returned by the backend.
+    type Error: StdError + 'static;
+
+    /// Returns the value associated with the given key, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the backend fails.
+    fn get(&self, key: &str) -> Result<Option<String>, Self::Error>;
+
+    /// Associates the value with the given key, expiring after `ttl`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the backend fails.
+    fn set(&self, key: &str, value: String, ttl: Duration) -> Result<(), Self::Error>;
+
+    /// Associates the value with the given key, expiring after `ttl`,
+    /// unless the key already has a value associated with it.
+    ///
+    /// Returns whether the value was set. This is used by [`unique`] stores.
+    ///
+    /// The default implementation uses [`get`] and [`set`], meaning that concurrent calls
+    /// may both succeed. Backends should override it to make the operation atomic.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the backend fails.
+    ///
+    /// [`unique`]: KvChallengeStore::unique
+    /// [`get`]: Self::get
+    /// [`set`]: Self::set
+    fn set_if_absent(&self, key: &str, value: String, ttl: Duration) -> Result<bool, Self::Error> {
+        if self.get(key)?.is_some() {
+            return Ok(false);
+        }
+
+        self.set(key, value, ttl)?;
+
+        Ok(true)
+    }
+
+    /// Removes the value associated with the given key, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the backend fails.
+    fn delete(&self, key: &str) -> Result<(), Self::Error>;
+
+    /// Removes and returns the value associated with the given key, if any.
+    ///
+    /// This is what ensures challenges are used at most once.
+    ///
+    /// The default implementation uses [`get`] and [`delete`], meaning that concurrent calls
+    /// may both return the same value. Backends should override it to make the operation atomic.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the backend fails.
+    ///
+    /// [`get`]: Self::get
+    /// [`delete`]: Self::delete
+    fn take(&self, key: &str) -> Result<Option<String>, Self::Error> {
+        let value = self.get(key)?;
+
+        if value.is_some() {
+            self.delete(key)?;
+        }
+
+        Ok(value)
+    }
+}
+
+/// Represents errors that can occur when loading or storing challenges.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error<E: StdError + 'static> {
+    /// The backend failed.
+    #[error("backend error")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::kv::backend),
+            help("see the backend error for more information")
+        )
+    )]
+    Backend(#[source] E),
+
+    /// The stored value is malformed.
+    #[error("malformed stored value")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::kv::format),
+            help("make sure the key is only written by the store")
+        )
+    )]
+    Format,
+
+    /// The challenge secret is already stored.
+    #[error("duplicate challenge")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::kv::duplicate),
+            help("make sure verifiers are never reused; this may also indicate replay attempts")
+        )
+    )]
+    Duplicate,
+
+    /// The stored challenge is invalid.
+    #[error("invalid stored challenge")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Challenge(#[from] challenge::Error),
+}
+
+impl<E: StdError + 'static> Error<E> {
+    /// Constructs [`Self::Backend`].
+    pub const fn backend(error: E) -> Self {
+        Self::Backend(error)
+    }
+}
+
+/// Represents challenge stores on top of [`KvBackend`] implementations.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct KvChallengeStore<B: KvBackend> {
+    backend: B,
+    ttl: Duration,
+    prefix: String,
+    unique: bool,
+}
+
+impl<B: KvBackend> KvChallengeStore<B> {
+    /// Constructs [`Self`] with the given backend and time-to-live, using the default [`PREFIX`].
+    pub fn new(backend: B, ttl: Duration) -> Self {
+        Self::with_prefix(backend, ttl, PREFIX)
+    }
+
+    /// Constructs [`Self`] with the given backend, time-to-live and key prefix.
+    pub fn with_prefix<P: Into<String>>(backend: B, ttl: Duration, prefix: P) -> Self {
+        Self {
+            backend,
+            ttl,
+            prefix: prefix.into(),
+            unique: false,
+        }
+    }
+
+    /// Sets whether to reject challenges with secrets already stored.
+    ///
+    /// Refer to the [module] documentation for more information.
+    ///
+    /// [module]: self
+    #[must_use]
+    pub const fn unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+
+        self
+    }
+
+    /// Checks whether challenges with secrets already stored are rejected.
+    pub const fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    /// Returns the contained backend.
+    pub const fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Consumes [`Self`], returning the contained backend.
+    pub fn into_backend(self) -> B {
+        self.backend
+    }
+
+    /// Returns the time-to-live of stored challenges.
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the key prefix.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{prefix}{CHALLENGE}{key}", prefix = self.prefix)
+    }
+
+    fn digest_key(&self, secret: &str) -> String {
+        format!(
+            "{prefix}{DIGEST}{digest}",
+            prefix = self.prefix,
+            digest = encoding::encode(sha256(secret))
+        )
+    }
+
+    /// Stores the [`Challenge`] under the given key, replacing the previous one, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Backend`] if the backend fails, and [`Error::Duplicate`]
+    /// if [`Self`] is [`unique`] and the secret is already stored.
+    ///
+    /// [`unique`]: Self::unique
+    pub fn insert(&self, key: &str, challenge: &Challenge) -> Result<(), Error<B::Error>> {
+        let digest_key = self.unique.then(|| self.digest_key(challenge.secret()));
+
+        if let Some(digest_key) = digest_key.as_deref() {
+            let set = self
+                .backend
+                .set_if_absent(digest_key, key.to_owned(), self.ttl)
+                .map_err(Error::backend)?;
+
+            if !set {
+                return Err(Error::Duplicate);
+            }
+        }
+
+        let value = format!(
+            "{method}{SEPARATOR}{secret}",
+            method = challenge.method().static_str(),
+            secret = challenge.secret()
+        );
+
+        let result = self.backend.set(&self.key(key), value, self.ttl);
+
+        if let (Err(_), Some(digest_key)) = (&result, digest_key.as_deref()) {
+            // release the secret, since the challenge was not stored; the original error
+            // is more relevant than the one (if any) that occurs when rolling back
+            let _ = self.backend.delete(digest_key);
+        }
+
+        result.map_err(Error::backend)
+    }
+
+    fn parse(value: Option<String>) -> Result<Option<Challenge>, Error<B::Error>> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+
+        let (method, secret) = value.rsplit_once(SEPARATOR).ok_or(Error::Format)?;
+
+        let method = MethodName::new(method.to_owned())
+            .method()
+            .map_err(challenge::Error::from)?;
+
+        let challenge = Challenge::new(secret.to_owned(), method)?;
+
+        Ok(Some(challenge))
+    }
+
+    /// Loads the [`Challenge`] stored under the given key, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Backend`] if the backend fails, [`Error::Format`] if the stored value
+    /// is malformed, and [`Error::Challenge`] if the stored challenge is invalid.
+    pub fn get(&self, key: &str) -> Result<Option<Challenge>, Error<B::Error>> {
+        let value = self.backend.get(&self.key(key)).map_err(Error::backend)?;
+
+        Self::parse(value)
+    }
+
+    /// Removes the [`Challenge`] stored under the given key, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Backend`] if the backend fails.
+    pub fn remove(&self, key: &str) -> Result<(), Error<B::Error>> {
+        self.backend.delete(&self.key(key)).map_err(Error::backend)
+    }
+
+    /// Loads and removes the [`Challenge`] stored under the given key, if any.
+    ///
+    /// Atomicity is provided by the backend, see [`KvBackend::take`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// See [`get`] for more information.
+    ///
+    /// [`get`]: Self::get
+    pub fn take(&self, key: &str) -> Result<Option<Challenge>, Error<B::Error>> {
+        let value = self.backend.take(&self.key(key)).map_err(Error::backend)?;
+
+        Self::parse(value)
+    }
+}
+
+// the backend is synchronous, so the futures are ready immediately
+impl<B: KvBackend> ChallengeStore for KvChallengeStore<B>
+where
+    B::Error: Send,
+{
+    type Error = Error<B::Error>;
+
+    fn store(
+        &self,
+        key: String,
+        challenge: Challenge,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        future::ready(self.insert(&key, &challenge))
+    }
+
+    fn take(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = Result<Option<Challenge>, Self::Error>> + Send {
+        future::ready(Self::take(self, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        fmt,
+        sync::{
+            Mutex,
+            atomic::{AtomicBool, Ordering},
+        },
+        time::Duration,
+    };
+
+    use crate::{
+        challenge::Challenge,
+        context::Pkce,
+        kv::{Error, KvBackend, KvChallengeStore},
+        method::Method,
+    };
+
+    const SECRET: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[derive(Debug)]
+    struct Failure;
+
+    impl fmt::Display for Failure {
+        fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("failure")
+        }
+    }
+
+    impl std::error::Error for Failure {}
+
+    #[derive(Default)]
+    struct Memory {
+        entries: Mutex<HashMap<String, String>>,
+        failing: AtomicBool,
+    }
+
+    impl KvBackend for Memory {
+        type Error = Failure;
+
+        fn get(&self, key: &str) -> Result<Option<String>, Self::Error> {
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+
+        fn set(&self, key: &str, value: String, _ttl: Duration) -> Result<(), Self::Error> {
+            if self.failing.load(Ordering::Relaxed) {
+                return Err(Failure);
+            }
+
+            self.entries.lock().unwrap().insert(key.to_owned(), value);
+
+            Ok(())
+        }
+
+        fn set_if_absent(
+            &self,
+            key: &str,
+            value: String,
+            _ttl: Duration,
+        ) -> Result<bool, Self::Error> {
+            let mut entries = self.entries.lock().unwrap();
+
+            if entries.contains_key(key) {
+                return Ok(false);
+            }
+
+            entries.insert(key.to_owned(), value);
+
+            Ok(true)
+        }
+
+        fn delete(&self, key: &str) -> Result<(), Self::Error> {
+            self.entries.lock().unwrap().remove(key);
+
+            Ok(())
+        }
+
+        // `take` uses the default implementation
+    }
+
+    fn challenge() -> Challenge {
+        Challenge::new(SECRET.to_owned(), Method::Plain).unwrap()
+    }
+
+    #[test]
+    fn duplicate() {
+        let store = KvChallengeStore::new(Memory::default(), TTL).unique(true);
+
+        store.insert("state", &challenge()).unwrap();
+
+        let error = store.insert("other", &challenge()).unwrap_err();
+
+        assert!(matches!(error, Error::Duplicate));
+    }
+
+    #[test]
+    fn namespaces() {
+        let store = KvChallengeStore::new(Memory::default(), TTL).unique(true);
+
+        store.insert("state", &challenge()).unwrap();
+
+        let digest_key = store.digest_key(SECRET);
+
+        let key = digest_key.strip_prefix(store.prefix()).unwrap();
+
+        // keys that look like index entries do not clash with them
+        assert!(store.get(key).unwrap().is_none());
+        assert!(store.take("state").unwrap().is_some());
+    }
+
+    #[test]
+    fn rollback() {
+        let store = KvChallengeStore::new(Memory::default(), TTL).unique(true);
+
+        store.backend().failing.store(true, Ordering::Relaxed);
+
+        let error = store.insert("state", &challenge()).unwrap_err();
+
+        assert!(matches!(error, Error::Backend(Failure)));
+
+        store.backend().failing.store(false, Ordering::Relaxed);
+
+        store.insert("state", &challenge()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn context() {
+        let store = KvChallengeStore::new(Memory::default(), TTL);
+
+        let pkce = Pkce::builder().store(store).build().unwrap();
+
+        let (verifier, _) = pkce.generate_stored("state").await.unwrap().into_pair();
+
+        assert!(pkce.verify_stored("state", verifier.get()).await.unwrap());
+        assert!(pkce.verify_stored("state", verifier.get()).await.is_err());
+    }
+}
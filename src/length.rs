@@ -13,8 +13,36 @@
 //!
 //! let length = Length::new(128);
 //! ```
+//!
+//! Values of other integer types can be converted fallibly as well, which is handy when
+//! reading lengths from configuration:
+//!
+//! ```
+//! use pkce_std::length::Length;
+//!
+//! let length = Length::try_from(64u32).unwrap();
+//!
+//! assert_eq!(length.get(), 64);
+//!
+//! assert!(Length::try_from(u64::MAX).is_err());
+//! ```
+//!
+//! Arithmetic on lengths is checked to stay within the valid range:
+//!
+//! ```
+//! use pkce_std::length::Length;
+//!
+//! assert_eq!(Length::MIN.checked_add(21).unwrap().get(), 64);
+//!
+//! assert!(Length::MIN.checked_sub(1).is_none());
+//! assert!(Length::MAX.checked_add(1).is_none());
+//! ```
 
-use std::{fmt, num::ParseIntError, str::FromStr};
+use std::{
+    fmt,
+    num::{NonZeroUsize, ParseIntError},
+    str::FromStr,
+};
 
 use const_macros::{const_early, const_ok, const_try};
 
@@ -26,7 +54,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 use thiserror::Error;
 
-use crate::{count, encoding};
+use crate::{count, encoding, error::ErrorKind};
 
 /// The minimum length.
 pub const MIN: usize = encoding::length(count::MIN);
@@ -39,26 +67,105 @@ pub const MAX: usize = encoding::length(count::MAX);
 
 /// Represents errors that can occur when constructing [`Length`] values.
 ///
-/// This error is returned when the given value is less than [`MIN`] or greater than [`MAX`].
+/// This error is returned when the given value is less than [`MIN`] or greater than [`MAX`],
+/// with the variants telling these cases apart along with how far out of range the value is.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::length::{Error, Length};
+///
+/// let error = Length::new(40).unwrap_err();
+///
+/// assert!(matches!(error, Error::TooShort { missing: 3, .. }));
+///
+/// let error = Length::new(130).unwrap_err();
+///
+/// assert_eq!(error.distance(), 2);
+/// ```
 #[derive(Debug, Error)]
-#[error("expected length in `[{MIN}, {MAX}]` range, got `{value}`")]
-#[cfg_attr(
-    feature = "diagnostics",
-    derive(Diagnostic),
-    diagnostic(
-        code(pkce_std::length),
-        help("make sure the length is at least `{MIN}` and at most `{MAX}`")
-    )
-)]
-pub struct Error {
-    /// The invalid value.
-    pub value: usize,
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The value is less than [`MIN`].
+    #[error("expected length of at least `{MIN}`, got `{value}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::length::short),
+            help("add at least `{missing}` more characters"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+        )
+    )]
+    TooShort {
+        /// The invalid value.
+        value: usize,
+        /// The amount missing to reach [`MIN`].
+        missing: usize,
+    },
+    /// The value is greater than [`MAX`].
+    #[error("expected length of at most `{MAX}`, got `{value}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::length::long),
+            help("remove at least `{excess}` characters"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+        )
+    )]
+    TooLong {
+        /// The invalid value.
+        value: usize,
+        /// The amount exceeding [`MAX`].
+        excess: usize,
+    },
 }
 
 impl Error {
-    /// Constructs [`Self`].
+    /// Constructs [`Self`] from the given out-of-range value.
+    ///
+    /// Values less than [`MIN`] result in [`Self::TooShort`], and the rest in [`Self::TooLong`].
     pub const fn new(value: usize) -> Self {
-        Self { value }
+        if value < MIN {
+            Self::too_short(value)
+        } else {
+            Self::too_long(value)
+        }
+    }
+
+    /// Constructs [`Self::TooShort`].
+    pub const fn too_short(value: usize) -> Self {
+        Self::TooShort {
+            value,
+            missing: MIN.saturating_sub(value),
+        }
+    }
+
+    /// Constructs [`Self::TooLong`].
+    pub const fn too_long(value: usize) -> Self {
+        Self::TooLong {
+            value,
+            excess: value.saturating_sub(MAX),
+        }
+    }
+
+    /// Returns the invalid value.
+    pub const fn value(&self) -> usize {
+        match *self {
+            Self::TooShort { value, .. } | Self::TooLong { value, .. } => value,
+        }
+    }
+
+    /// Returns how far out of range the value is.
+    pub const fn distance(&self) -> usize {
+        match *self {
+            Self::TooShort { missing, .. } => missing,
+            Self::TooLong { excess, .. } => excess,
+        }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::InvalidLength
     }
 }
 
@@ -72,7 +179,8 @@ pub enum ParseError {
         feature = "diagnostics",
         diagnostic(
             code(pkce_std::length::parse),
-            help("make sure the length is in the valid range")
+            help("make sure the length is in the valid range"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
         )
     )]
     Length(#[from] Error),
@@ -85,6 +193,16 @@ pub enum ParseError {
     Int(#[from] ParseIntError),
 }
 
+impl ParseError {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Length(error) => error.kind(),
+            Self::Int(error) => ErrorKind::int(error),
+        }
+    }
+}
+
 /// Represents lengths.
 ///
 /// Refer to the [module] documentation for more information.
@@ -113,6 +231,32 @@ impl TryFrom<usize> for Length {
     }
 }
 
+impl TryFrom<u32> for Length {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        // values not fitting into `usize` are certainly greater than `MAX`
+        Self::new(usize::try_from(value).unwrap_or(usize::MAX))
+    }
+}
+
+impl TryFrom<u64> for Length {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        // values not fitting into `usize` are certainly greater than `MAX`
+        Self::new(usize::try_from(value).unwrap_or(usize::MAX))
+    }
+}
+
+impl TryFrom<NonZeroUsize> for Length {
+    type Error = Error;
+
+    fn try_from(value: NonZeroUsize) -> Result<Self, Self::Error> {
+        Self::new(value.get())
+    }
+}
+
 impl From<Length> for usize {
     fn from(length: Length) -> Self {
         length.get()
@@ -185,9 +329,11 @@ impl Length {
     ///
     /// # Errors
     ///
-    /// [`struct@Error`] is returned if the value is less than [`MIN`] or greater than [`MAX`].
+    /// [`Error::TooShort`] is returned if the value is less than [`MIN`],
+    /// and [`Error::TooLong`] if it is greater than [`MAX`].
     pub const fn check(value: usize) -> Result<(), Error> {
-        const_early!(value < MIN || value > MAX => Error::new(value));
+        const_early!(value < MIN => Error::too_short(value));
+        const_early!(value > MAX => Error::too_long(value));
 
         Ok(())
     }
@@ -206,6 +352,22 @@ impl Length {
         self.value
     }
 
+    /// Adds the given value to [`Self`], returning [`None`] if the result is out of range.
+    pub const fn checked_add(self, value: usize) -> Option<Self> {
+        match self.get().checked_add(value) {
+            Some(value) => Self::new_ok(value),
+            None => None,
+        }
+    }
+
+    /// Subtracts the given value from [`Self`], returning [`None`] if the result is out of range.
+    pub const fn checked_sub(self, value: usize) -> Option<Self> {
+        match self.get().checked_sub(value) {
+            Some(value) => Self::new_ok(value),
+            None => None,
+        }
+    }
+
     /// The minimum value of [`Self`].
     pub const MIN: Self = Self::new_ok(MIN).unwrap();
 
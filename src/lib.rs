@@ -68,6 +68,12 @@
 //! let code = Code::generate_default();
 //! ```
 //!
+//! Or, if only the `(verifier, challenge)` pair is needed:
+//!
+//! ```
+//! let (verifier, challenge) = pkce_std::generate();
+//! ```
+//!
 //! Alternatively, generating from random bytes:
 //!
 //! ```
@@ -99,22 +105,154 @@
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+#[cfg(feature = "actix-web")]
+pub mod actix_web;
+
+pub mod api;
+pub mod audit;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+pub mod cache;
+pub mod callback;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
 pub mod challenge;
+pub mod charset;
 pub mod check;
+
+#[cfg(feature = "clap")]
+pub mod cli;
+
+pub mod clock;
 pub mod code;
+pub mod context;
 pub mod count;
+pub mod ct;
+pub mod device;
+
+#[cfg(feature = "rand")]
+pub mod distr;
+
 pub mod encoding;
+pub mod error;
+
+#[cfg(feature = "events")]
+pub mod events;
+
+pub mod expiring;
+pub mod fingerprint;
+pub mod fixed;
 pub mod generate;
+
+#[cfg(feature = "zeroize")]
+pub mod guard;
+
 pub mod hash;
+
+#[cfg(feature = "http")]
+pub mod http;
+
+pub mod inline;
+pub mod kv;
 pub mod length;
+
+#[cfg(feature = "mlock")]
+pub mod lock;
+
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod macros;
+
 pub mod method;
 
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+#[cfg(feature = "moka")]
+pub mod moka;
+
+#[cfg(feature = "napi")]
+pub mod node;
+
+pub mod outcome;
+pub mod params;
+pub mod policy;
+pub mod protected;
+
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "urlencoded")]
+pub mod query;
+
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
+
+#[cfg(feature = "rocket")]
+pub mod rocket;
+
+#[cfg(feature = "seal")]
+pub mod seal;
+
+#[cfg(feature = "serde")]
+pub mod serde_support;
+
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+
+pub mod stats;
+pub mod store;
+pub mod tagged;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(any(feature = "actix-web", feature = "tower"))]
+pub mod token;
+
+#[cfg(feature = "tower")]
+pub mod tower;
+
+pub mod validator;
+
 #[macro_use]
 pub mod verifier;
 
+#[cfg(feature = "warp")]
+pub mod warp;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 pub use challenge::Challenge;
-pub use code::{Code, Pair};
+pub use code::{Code, OwnedPair, Pair};
 pub use count::Count;
+pub use error::Error;
 pub use length::Length;
 pub use method::Method;
 pub use verifier::Verifier;
+
+#[cfg(feature = "macros")]
+pub use pkce_std_macros::{challenge, verifier};
+
+/// Generates the `(verifier, challenge)` pair using the default method and length.
+///
+/// This is the shorthand for calling [`Code::generate_default`] and then [`Code::into_pair`].
+///
+/// # Examples
+///
+/// ```
+/// let (verifier, challenge) = pkce_std::generate();
+///
+/// assert!(verifier.verify(&challenge));
+/// ```
+pub fn generate() -> OwnedPair {
+    Code::generate_default().into_pair()
+}
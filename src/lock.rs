@@ -0,0 +1,163 @@
+//! Keeping PKCE code verifiers in locked memory.
+//!
+//! The [`LockedVerifier`] type stores verifiers in page-locked memory, meaning
+//! that the operating system will never swap them to disk.
+//!
+//! When [`LockedVerifier`] is dropped, the memory is zeroed and unlocked.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::lock::LockedVerifier;
+//!
+//! let locked = LockedVerifier::generate_default().unwrap();
+//!
+//! let challenge = locked.challenge();
+//!
+//! assert!(locked.verify(&challenge));
+//! ```
+
+#[cfg(not(feature = "expose-secrets"))]
+use std::fmt;
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::Challenge, count::Count, length::Length, method::Method, verifier::Verifier,
+};
+
+/// Represents errors that can occur when locking memory.
+#[derive(Debug, Error)]
+#[error("failed to lock memory")]
+#[cfg_attr(
+    feature = "diagnostics",
+    derive(Diagnostic),
+    diagnostic(
+        code(pkce_std::lock),
+        help("check the locked memory limits of the process")
+    )
+)]
+pub struct Error;
+
+/// Represents PKCE code verifiers stored in locked memory.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[cfg_attr(feature = "expose-secrets", derive(Debug))]
+pub struct LockedVerifier {
+    value: String,
+}
+
+#[cfg(not(feature = "expose-secrets"))]
+impl fmt::Debug for LockedVerifier {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("LockedVerifier")
+            .field("length", &self.value.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for LockedVerifier {
+    fn drop(&mut self) {
+        let value = self.value.as_mut_str();
+
+        // SAFETY: the pointer and the length are taken from the same live string;
+        // zeroing leaves it valid UTF-8, and it is never accessed afterwards
+        unsafe {
+            memsec::munlock(value.as_mut_ptr(), value.len());
+        }
+    }
+}
+
+impl LockedVerifier {
+    /// Moves the given [`Verifier`] into locked memory.
+    ///
+    /// Borrowed verifiers are copied, so only the copy ends up being locked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the memory could not be locked,
+    /// in which case the moved value is zeroed before being dropped.
+    pub fn new(verifier: Verifier<'_>) -> Result<Self, Error> {
+        let mut value = verifier.take().into_owned();
+
+        let string = value.as_mut_str();
+
+        let (pointer, length) = (string.as_mut_ptr(), string.len());
+
+        // SAFETY: the pointer and the length are taken from the same live string
+        if unsafe { memsec::mlock(pointer, length) } {
+            Ok(Self { value })
+        } else {
+            // SAFETY: the pointer and the length are taken from the same live string,
+            // and zeroing leaves it valid UTF-8
+            unsafe {
+                memsec::memzero(pointer, length);
+            }
+
+            Err(Error)
+        }
+    }
+
+    /// Generates random [`Self`] with specified length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the memory could not be locked.
+    pub fn generate(length: Length) -> Result<Self, Error> {
+        Self::new(Verifier::generate(length))
+    }
+
+    /// Generates random [`Self`] with default length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the memory could not be locked.
+    pub fn generate_default() -> Result<Self, Error> {
+        Self::generate(Length::default())
+    }
+
+    /// Generates `count` random bytes length and encodes them into [`Self`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the memory could not be locked.
+    pub fn generate_encode(count: Count) -> Result<Self, Error> {
+        Self::new(Verifier::generate_encode(count))
+    }
+
+    /// Generates random bytes of default length and encodes them into [`Self`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the memory could not be locked.
+    pub fn generate_encode_default() -> Result<Self, Error> {
+        Self::generate_encode(Count::default())
+    }
+
+    /// Returns the [`Verifier`] borrowing from the locked memory.
+    pub fn verifier(&self) -> Verifier<'_> {
+        // SAFETY: `value` was taken from some valid verifier in `new`
+        unsafe { Verifier::borrowed_unchecked(self.value.as_str()) }
+    }
+
+    /// Computes the [`Challenge`] of [`Self`] with the given [`Method`].
+    pub fn challenge_using(&self, method: Method) -> Challenge {
+        self.verifier().challenge_using(method)
+    }
+
+    /// Computes the [`Challenge`] of [`Self`] with the default [`Method`].
+    pub fn challenge(&self) -> Challenge {
+        self.verifier().challenge()
+    }
+
+    /// Verifies the given [`Challenge`] against [`Self`].
+    pub fn verify(&self, challenge: &Challenge) -> bool {
+        self.verifier().verify(challenge)
+    }
+}
@@ -0,0 +1,50 @@
+//! Compile-time checks emitted by the [`verifier!`] and [`challenge!`] macros.
+//!
+//! The macros expand to `const` items calling these functions, so that literals are validated
+//! using the very same rules as the rest of the crate, failing compilation if they are invalid.
+//!
+//! This module is not part of the public API.
+//!
+//! [`verifier!`]: crate::verifier!
+//! [`challenge!`]: crate::challenge!
+
+use crate::{
+    check::string::const_check_str,
+    length::{self, Length},
+    method::{Method, challenge_length},
+    verifier::{self, Verifier},
+};
+
+/// Checks the given verifier, panicking if it is invalid.
+pub const fn check_verifier(value: &str) {
+    match Verifier::const_check_str(value) {
+        Ok(()) => {}
+        Err(verifier::Error::Length(length::Error::TooShort { .. })) => {
+            panic!("verifier is too short")
+        }
+        Err(verifier::Error::Length(length::Error::TooLong { .. })) => {
+            panic!("verifier is too long")
+        }
+        Err(verifier::Error::String(_)) => panic!("verifier contains invalid character(s)"),
+    }
+}
+
+/// Checks the given secret against the method, panicking if it is invalid.
+pub const fn check_challenge(secret: &str, method: &Method) {
+    let length = secret.len();
+
+    if Length::check(length).is_err() {
+        panic!("invalid challenge secret length")
+    }
+
+    match challenge_length(method) {
+        Some(expected) if length != expected => {
+            panic!("challenge secret length does not match the method")
+        }
+        _ => {}
+    }
+
+    if const_check_str(secret).is_err() {
+        panic!("challenge secret contains invalid character(s)")
+    }
+}
@@ -6,6 +6,24 @@
 //! The former is discouraged and is only used as the last resort,
 //! while the latter is recommended and is marked as the default.
 //!
+//! Some authorization servers also support `S384` and `S512` methods, which are available
+//! via the `extended-methods` feature.
+//!
+//! ```
+//! # #[cfg(feature = "extended-methods")]
+//! # {
+//! use pkce_std::{method::Method, verifier::Verifier};
+//!
+//! let method: Method = "S512".parse().unwrap();
+//!
+//! let verifier = Verifier::generate_default();
+//!
+//! let challenge = verifier.challenge_using(method);
+//!
+//! assert!(verifier.verify_decode(&challenge));
+//! # }
+//! ```
+//!
 //! # Examples
 //!
 //! ```
@@ -17,17 +35,94 @@
 //!
 //! assert_eq!(method, Method::Sha256);
 //! ```
+//!
+//! # Custom methods
+//!
+//! Custom methods can be implemented via the [`ChallengeMethod`] trait and then
+//! registered in [`MethodRegistry`], which parses them into [`Method::Custom`]:
+//!
+//! ```
+//! use pkce_std::{
+//!     method::{ChallengeMethod, Method, MethodRegistry},
+//!     verifier::Verifier,
+//! };
+//!
+//! #[derive(Debug)]
+//! struct Reverse;
+//!
+//! impl ChallengeMethod for Reverse {
+//!     fn name(&self) -> &'static str {
+//!         "reverse"
+//!     }
+//!
+//!     fn transform(&self, verifier: &Verifier<'_>) -> String {
+//!         verifier.get().chars().rev().collect()
+//!     }
+//! }
+//!
+//! static REVERSE: Reverse = Reverse;
+//!
+//! let mut registry = MethodRegistry::new();
+//!
+//! registry.register(&REVERSE).unwrap();
+//!
+//! let method = registry.parse("reverse").unwrap();
+//!
+//! let verifier = Verifier::generate_default();
+//!
+//! let challenge = verifier.challenge_using(method);
+//!
+//! assert!(verifier.verify(&challenge));
+//! ```
+//!
+//! # Method names
+//!
+//! [`Method`] only represents methods that can be used to derive challenges, therefore parsing
+//! unknown methods fails. Unknown methods can be preserved using [`MethodName`] instead,
+//! which round-trips without loss and is converted into [`Method`] when it is needed:
+//!
+//! ```
+//! use pkce_std::method::{Method, MethodName};
+//!
+//! let name = MethodName::new("S3-512");
+//!
+//! assert_eq!(name.as_str(), "S3-512");
+//!
+//! assert!(name.method().is_err());
+//!
+//! assert_eq!(MethodName::new("S256").method().unwrap(), Method::Sha256);
+//! ```
 
-use std::str::FromStr;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    str::FromStr,
+};
 
 #[cfg(feature = "diagnostics")]
 use miette::Diagnostic;
 
 #[cfg(feature = "serde")]
-use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 use thiserror::Error;
 
+#[cfg(feature = "extended-methods")]
+use crate::hash::{SHA384_LENGTH, SHA512_LENGTH, sha384, sha512};
+
+use crate::{
+    encoding::{encode, length},
+    error::ErrorKind,
+    hash::{SHA256_LENGTH, sha256},
+    verifier::Verifier,
+};
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
 /// Represents errors that can occur when parsing PKCE methods.
 #[derive(Debug, Error)]
 #[error("unknown method `{unknown}`")]
@@ -36,7 +131,8 @@ use thiserror::Error;
     derive(Diagnostic),
     diagnostic(
         code(pkce_std::method),
-        help("expected either `{PLAIN}` (discouraged) or `{SHA256}` (recommended)")
+        help("expected either `{PLAIN}` (discouraged) or `{SHA256}` (recommended)"),
+        url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.2")
     )
 )]
 pub struct Error {
@@ -49,6 +145,67 @@ impl Error {
     pub const fn new(unknown: String) -> Self {
         Self { unknown }
     }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::UnknownMethod
+    }
+}
+
+/// Represents errors that occur when unknown [`MethodName`] values are used as methods.
+#[derive(Debug, Error)]
+#[error("unsupported method `{name}`")]
+#[cfg_attr(
+    feature = "diagnostics",
+    derive(Diagnostic),
+    diagnostic(
+        code(pkce_std::method::unsupported),
+        help("unknown methods can not be used to derive challenges")
+    )
+)]
+pub struct UnsupportedError {
+    /// The unsupported method name.
+    pub name: String,
+}
+
+impl UnsupportedError {
+    /// Constructs [`Self`].
+    pub const fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::UnsupportedMethod
+    }
+}
+
+/// Represents errors that occur when registering custom methods with reserved names.
+#[derive(Debug, Error)]
+#[error("method name `{name}` is reserved")]
+#[cfg_attr(
+    feature = "diagnostics",
+    derive(Diagnostic),
+    diagnostic(
+        code(pkce_std::method::reserved),
+        help("custom methods can not use the names of built-in methods")
+    )
+)]
+pub struct ReservedError {
+    /// The reserved method name.
+    pub name: String,
+}
+
+impl ReservedError {
+    /// Constructs [`Self`].
+    pub const fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        ErrorKind::ReservedMethod
+    }
 }
 
 /// The `plain` literal.
@@ -57,14 +214,127 @@ pub const PLAIN: &str = "plain";
 /// The `S256` literal.
 pub const SHA256: &str = "S256";
 
+/// The `S384` literal.
+#[cfg(feature = "extended-methods")]
+pub const SHA384: &str = "S384";
+
+/// The `S512` literal.
+#[cfg(feature = "extended-methods")]
+pub const SHA512: &str = "S512";
+
+/// The names reserved for built-in methods, regardless of the features enabled.
+///
+/// Custom methods can not be registered under these names, compared ignoring the case.
+pub const RESERVED: [&str; 4] = [PLAIN, SHA256, "S384", "S512"];
+
+/// Checks whether the given name is reserved for built-in methods.
+///
+/// See [`RESERVED`] for more information.
+pub fn is_reserved(name: &str) -> bool {
+    RESERVED
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(name))
+}
+
+/// Represents PKCE code challenge method implementations.
+///
+/// This trait is object-safe, which allows registering custom methods in [`MethodRegistry`].
+pub trait ChallengeMethod: fmt::Debug + Send + Sync {
+    /// Returns the name of the method, which is used as the `code_challenge_method`.
+    fn name(&self) -> &'static str;
+
+    /// Transforms the given verifier into the challenge secret.
+    ///
+    /// The returned secret is checked when creating challenges, which fails
+    /// if it is invalid, see [`Challenge::try_create_using`].
+    ///
+    /// [`Challenge::try_create_using`]: crate::challenge::Challenge::try_create_using
+    fn transform(&self, verifier: &Verifier<'_>) -> String;
+}
+
 /// Represents PKCE code challenge methods.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+///
+/// Methods are compared by their variants; [`Self::Custom`] methods are additionally compared
+/// by their names, which means that custom methods are never equal to built-in methods,
+/// even if their names match.
+#[derive(Debug, Clone, Copy, Default)]
 pub enum Method {
     /// The plain method, which is discouraged and only used as the last resort.
     Plain,
     /// The SHA-256 method, which is recommended and marked as the default.
     #[default]
     Sha256,
+    /// The SHA-384 method, which is not part of the standard.
+    #[cfg(feature = "extended-methods")]
+    Sha384,
+    /// The SHA-512 method, which is not part of the standard.
+    #[cfg(feature = "extended-methods")]
+    Sha512,
+    /// The custom method, usually obtained from [`MethodRegistry`].
+    Custom(&'static dyn ChallengeMethod),
+}
+
+impl PartialEq for Method {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(left), Self::Custom(right)) => left.name() == right.name(),
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Method {}
+
+impl Hash for Method {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        mem::discriminant(self).hash(hasher);
+
+        self.static_str().hash(hasher);
+    }
+}
+
+/// Returns the length of challenge secrets produced by the given method, if it is fixed.
+///
+/// Hashing methods produce secrets of fixed length, while [`Method::Plain`] produces secrets
+/// of the verifier length, in which case [`None`] is returned. The same goes for
+/// [`Method::Custom`], since its output is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::method::{Method, challenge_length};
+///
+/// assert_eq!(challenge_length(&Method::Sha256), Some(43));
+/// assert_eq!(challenge_length(&Method::Plain), None);
+/// ```
+pub const fn challenge_length(method: &Method) -> Option<usize> {
+    match method {
+        Method::Sha256 => Some(length(SHA256_LENGTH)),
+        #[cfg(feature = "extended-methods")]
+        Method::Sha384 => Some(length(SHA384_LENGTH)),
+        #[cfg(feature = "extended-methods")]
+        Method::Sha512 => Some(length(SHA512_LENGTH)),
+        Method::Plain | Method::Custom(_) => None,
+    }
+}
+
+impl Method {
+    /// Transforms the given verifier into the challenge secret.
+    ///
+    /// Secrets derived by [`Self::Custom`] methods are returned as-is, without being checked.
+    pub fn transform(&self, verifier: &Verifier<'_>) -> String {
+        let string = verifier.get();
+
+        match self {
+            Self::Plain => string.to_owned(),
+            Self::Sha256 => encode(sha256(string)),
+            #[cfg(feature = "extended-methods")]
+            Self::Sha384 => encode(sha384(string)),
+            #[cfg(feature = "extended-methods")]
+            Self::Sha512 => encode(sha512(string)),
+            Self::Custom(custom) => custom.transform(verifier),
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -77,7 +347,7 @@ impl Serialize for Method {
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Method {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let string = <&str>::deserialize(deserializer)?;
+        let string = String::deserialize(deserializer)?;
 
         string.parse().map_err(de::Error::custom)
     }
@@ -87,22 +357,392 @@ type StaticStr = &'static str;
 
 impl Method {
     /// Returns the static string representation of the method.
-    pub const fn static_str(&self) -> StaticStr {
+    pub fn static_str(&self) -> StaticStr {
         match self {
             Self::Plain => PLAIN,
             Self::Sha256 => SHA256,
+            #[cfg(feature = "extended-methods")]
+            Self::Sha384 => SHA384,
+            #[cfg(feature = "extended-methods")]
+            Self::Sha512 => SHA512,
+            Self::Custom(custom) => custom.name(),
         }
     }
+
+    /// Parses [`Self`] leniently, accepting common aliases regardless of the case.
+    ///
+    /// The following aliases are accepted, in addition to the standard names:
+    ///
+    /// - `sha256` and `sha-256` for [`Self::Sha256`];
+    /// - `sha384` and `sha-384` for `Self::Sha384` (with `extended-methods`);
+    /// - `sha512` and `sha-512` for `Self::Sha512` (with `extended-methods`).
+    ///
+    /// Deserialization is strict by default; use the [`lenient`] module to deserialize
+    /// methods using this function instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the method is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::method::Method;
+    ///
+    /// assert_eq!(Method::parse_lenient("s256").unwrap(), Method::Sha256);
+    /// assert_eq!(Method::parse_lenient("SHA-256").unwrap(), Method::Sha256);
+    /// assert_eq!(Method::parse_lenient("PLAIN").unwrap(), Method::Plain);
+    ///
+    /// assert!(Method::parse_lenient("S3-512").is_err());
+    /// ```
+    ///
+    /// [`lenient`]: self::serde::lenient
+    pub fn parse_lenient(string: &str) -> Result<Self, Error> {
+        let matches = |names: &[&str]| names.iter().any(|name| name.eq_ignore_ascii_case(string));
+
+        if matches(&[PLAIN]) {
+            return Ok(Self::Plain);
+        }
+
+        if matches(&[SHA256, "sha256", "sha-256"]) {
+            return Ok(Self::Sha256);
+        }
+
+        #[cfg(feature = "extended-methods")]
+        if matches(&[SHA384, "sha384", "sha-384"]) {
+            return Ok(Self::Sha384);
+        }
+
+        #[cfg(feature = "extended-methods")]
+        if matches(&[SHA512, "sha512", "sha-512"]) {
+            return Ok(Self::Sha512);
+        }
+
+        Err(Error::new(string.to_owned()))
+    }
 }
 
 impl FromStr for Method {
     type Err = Error;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        match string {
-            PLAIN => Ok(Self::Plain),
-            SHA256 => Ok(Self::Sha256),
-            _ => Err(Self::Err::new(string.to_owned())),
+        Self::const_from_str(string).ok_or_else(|| Self::Err::new(string.to_owned()))
+    }
+}
+
+const fn str_eq(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut index = 0;
+
+    while index < left.len() {
+        if left[index] != right[index] {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+impl Method {
+    /// Parses [`Self`] from the given string in `const` contexts.
+    ///
+    /// This function accepts the same names as [`from_str`], returning [`None`]
+    /// if the method is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::method::Method;
+    ///
+    /// const METHOD: Method = Method::const_from_str("S256").unwrap();
+    ///
+    /// assert_eq!(METHOD, Method::Sha256);
+    ///
+    /// assert!(Method::const_from_str("S3-512").is_none());
+    /// ```
+    ///
+    /// [`from_str`]: Self::from_str
+    pub const fn const_from_str(string: &str) -> Option<Self> {
+        if str_eq(string, PLAIN) {
+            return Some(Self::Plain);
+        }
+
+        if str_eq(string, SHA256) {
+            return Some(Self::Sha256);
+        }
+
+        #[cfg(feature = "extended-methods")]
+        if str_eq(string, SHA384) {
+            return Some(Self::Sha384);
+        }
+
+        #[cfg(feature = "extended-methods")]
+        if str_eq(string, SHA512) {
+            return Some(Self::Sha512);
+        }
+
+        None
+    }
+}
+
+/// Represents names of PKCE code challenge methods, which may be unknown.
+///
+/// Unlike [`Method`], this type preserves unknown names, which makes it suitable for
+/// parse boundaries, for instance, when storing and forwarding authorization parameters.
+/// Use [`method`] to convert names into methods before deriving challenges.
+///
+/// [`method`]: Self::method
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MethodName {
+    /// The known method.
+    Known(Method),
+    /// The unknown method name.
+    Unknown(Cow<'static, str>),
+}
+
+impl Default for MethodName {
+    fn default() -> Self {
+        Self::Known(Method::default())
+    }
+}
+
+impl MethodName {
+    /// Constructs [`Self`] from the given name without loss.
+    ///
+    /// Known names are mapped to their respective methods,
+    /// while unknown ones are preserved in [`Self::Unknown`].
+    pub fn new<N: Into<Cow<'static, str>>>(name: N) -> Self {
+        let name = name.into();
+
+        name.parse().map_or(Self::Unknown(name), Self::Known)
+    }
+
+    /// Returns the string representation of the method name.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Known(method) => method.static_str(),
+            Self::Unknown(name) => name,
+        }
+    }
+
+    /// Checks whether the method name is known.
+    pub const fn is_known(&self) -> bool {
+        matches!(self, Self::Known(_))
+    }
+
+    /// Returns the method, if it is known.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnsupportedError`] if the method name is unknown.
+    pub fn method(&self) -> Result<Method, UnsupportedError> {
+        match self {
+            Self::Known(method) => Ok(*method),
+            Self::Unknown(name) => Err(UnsupportedError::new(name.as_ref().to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for MethodName {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(formatter)
+    }
+}
+
+impl From<Method> for MethodName {
+    fn from(method: Method) -> Self {
+        Self::Known(method)
+    }
+}
+
+impl TryFrom<MethodName> for Method {
+    type Error = UnsupportedError;
+
+    fn try_from(name: MethodName) -> Result<Self, Self::Error> {
+        name.method()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MethodName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_str().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for MethodName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = String::deserialize(deserializer)?;
+
+        Ok(Self::new(string))
+    }
+}
+
+/// Represents registries of custom PKCE code challenge methods.
+///
+/// Built-in methods are always available and take precedence over custom ones.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Default)]
+pub struct MethodRegistry {
+    custom: HashMap<StaticStr, &'static dyn ChallengeMethod>,
+}
+
+impl MethodRegistry {
+    /// Constructs empty [`Self`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the given custom method, returning the previously registered one, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReservedError`] if the name of the method is reserved for built-in methods,
+    /// see [`RESERVED`] for more information.
+    pub fn register(
+        &mut self,
+        method: &'static dyn ChallengeMethod,
+    ) -> Result<Option<Method>, ReservedError> {
+        let name = method.name();
+
+        if is_reserved(name) {
+            return Err(ReservedError::new(name.to_owned()));
+        }
+
+        let previous = self.custom.insert(name, method).map(Method::Custom);
+
+        Ok(previous)
+    }
+
+    /// Unregisters the custom method with the given name, returning it, if any.
+    pub fn unregister(&mut self, name: &str) -> Option<Method> {
+        self.custom.remove(name).map(Method::Custom)
+    }
+
+    /// Returns the method with the given name, if any.
+    ///
+    pub fn get(&self, name: &str) -> Option<Method> {
+        name.parse()
+            .ok()
+            .or_else(|| self.custom.get(name).copied().map(Method::Custom))
+    }
+
+    /// Parses the method with the given name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the method is neither built-in nor registered.
+    pub fn parse(&self, string: &str) -> Result<Method, Error> {
+        self.get(string)
+            .ok_or_else(|| Error::new(string.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{BuildHasher, RandomState};
+
+    use crate::{
+        method::{ChallengeMethod, Method, MethodName, MethodRegistry},
+        verifier::Verifier,
+    };
+
+    #[derive(Debug)]
+    struct Named(&'static str);
+
+    impl ChallengeMethod for Named {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn transform(&self, verifier: &Verifier<'_>) -> String {
+            verifier.get().chars().rev().collect()
         }
     }
+
+    static PLAIN: Named = Named("plain");
+    static SHA256: Named = Named("s256");
+    static REVERSE: Named = Named("reverse");
+
+    #[derive(Debug)]
+    struct Invalid;
+
+    impl ChallengeMethod for Invalid {
+        fn name(&self) -> &'static str {
+            "invalid"
+        }
+
+        fn transform(&self, _verifier: &Verifier<'_>) -> String {
+            "invalid secret".to_owned()
+        }
+    }
+
+    static INVALID: Invalid = Invalid;
+
+    #[test]
+    fn equality() {
+        assert_ne!(Method::Custom(&PLAIN), Method::Plain);
+        assert_ne!(
+            MethodName::from(Method::Custom(&REVERSE)),
+            MethodName::new("reverse")
+        );
+
+        assert_eq!(Method::Custom(&REVERSE), Method::Custom(&Named("reverse")));
+
+        let state = RandomState::new();
+
+        assert_ne!(
+            state.hash_one(Method::Custom(&PLAIN)),
+            state.hash_one(Method::Plain)
+        );
+    }
+
+    #[test]
+    fn reserved() {
+        let mut registry = MethodRegistry::new();
+
+        assert_eq!(registry.register(&PLAIN).unwrap_err().name, "plain");
+        assert_eq!(registry.register(&SHA256).unwrap_err().name, "s256");
+
+        assert!(registry.register(&REVERSE).unwrap().is_none());
+
+        assert_eq!(registry.parse("plain").unwrap(), Method::Plain);
+    }
+
+    #[test]
+    fn plain_fast_path() {
+        let verifier = Verifier::generate_default();
+
+        let challenge = verifier.challenge_using(Method::Custom(&PLAIN));
+
+        assert!(verifier.verify(&challenge));
+    }
+
+    #[test]
+    fn invalid_custom() {
+        let verifier = Verifier::generate_default();
+
+        assert!(
+            verifier
+                .try_challenge_using(Method::Custom(&INVALID))
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the method failed to derive valid challenge")]
+    fn invalid_custom_panics() {
+        Verifier::generate_default().challenge_using(Method::Custom(&INVALID));
+    }
 }
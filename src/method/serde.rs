@@ -0,0 +1,62 @@
+//! Serde adapters for embedding methods into user-defined types.
+//!
+//! Refer to the [`lenient`] module for accepting common aliases.
+
+pub mod lenient {
+    //! Handling methods leniently.
+    //!
+    //! Unlike regular deserialization, which only accepts the standard names, this module
+    //! parses methods using [`Method::parse_lenient`], accepting common aliases regardless
+    //! of the case. Serialization always uses the standard names.
+    //!
+    //! # Examples
+    //!
+    //! ```
+    //! use pkce_std::method::{self, Method};
+    //! use serde::{Deserialize, Serialize};
+    //!
+    //! #[derive(Serialize, Deserialize)]
+    //! struct Metadata {
+    //!     #[serde(with = "method::serde::lenient")]
+    //!     method: Method,
+    //! }
+    //!
+    //! let metadata: Metadata = serde_json::from_str(r#"{"method": "sha-256"}"#).unwrap();
+    //!
+    //! assert_eq!(metadata.method, Method::Sha256);
+    //!
+    //! let string = serde_json::to_string(&metadata).unwrap();
+    //!
+    //! assert_eq!(string, r#"{"method":"S256"}"#);
+    //!
+    //! assert!(serde_json::from_str::<Method>(r#""sha-256""#).is_err());
+    //! ```
+    //!
+    //! [`Method::parse_lenient`]: crate::method::Method::parse_lenient
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use crate::method::Method;
+
+    /// Serializes the method using its standard name.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(method: &Method, serializer: S) -> Result<S::Ok, S::Error> {
+        method.serialize(serializer)
+    }
+
+    /// Deserializes the method leniently, see [`Method::parse_lenient`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer error if the value is not a string or the method is unknown.
+    ///
+    /// [`Method::parse_lenient`]: crate::method::Method::parse_lenient
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Method, D::Error> {
+        let string = String::deserialize(deserializer)?;
+
+        Method::parse_lenient(&string).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,180 @@
+//! Mobile bindings via [`uniffi`].
+//!
+//! This module provides [`uniffi`] scaffolding for verifiers, challenges, codes and methods,
+//! so that Kotlin and Swift applications doing native OAuth flows can reuse this crate.
+//!
+//! The bindings are generated from the compiled library using `uniffi-bindgen`.
+//!
+//! [`uniffi`]: https://docs.rs/uniffi
+
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::{
+    api,
+    challenge::{self, Challenge},
+    error,
+    generate::EntropyError,
+    length::{self, Length},
+    method::Method,
+    verifier::{self, Verifier},
+};
+
+/// Represents errors exposed to foreign languages.
+#[derive(Debug, Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum PkceError {
+    /// Invalid length.
+    #[error(transparent)]
+    Length(#[from] length::Error),
+
+    /// Invalid verifier.
+    #[error(transparent)]
+    Verifier(#[from] verifier::Error),
+
+    /// Invalid challenge.
+    #[error(transparent)]
+    Challenge(#[from] challenge::Error),
+
+    /// Entropy failure.
+    #[error(transparent)]
+    Entropy(#[from] EntropyError),
+
+    /// Generation failure.
+    #[error(transparent)]
+    Generate(#[from] error::Error),
+}
+
+/// Represents challenge methods exposed to foreign languages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, uniffi::Enum)]
+pub enum PkceMethod {
+    /// The plain method, see [`Method::Plain`].
+    Plain,
+    /// The SHA-256 method, see [`Method::Sha256`].
+    #[default]
+    Sha256,
+}
+
+impl From<PkceMethod> for Method {
+    fn from(method: PkceMethod) -> Self {
+        match method {
+            PkceMethod::Plain => Self::Plain,
+            PkceMethod::Sha256 => Self::Sha256,
+        }
+    }
+}
+
+/// Represents challenges exposed to foreign languages.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, uniffi::Record)]
+pub struct PkceChallenge {
+    /// The `code_challenge`.
+    pub secret: String,
+    /// The `code_challenge_method`.
+    pub method: PkceMethod,
+}
+
+impl PkceChallenge {
+    fn from_challenge(challenge: &Challenge, method: PkceMethod) -> Self {
+        Self {
+            secret: challenge.secret().to_owned(),
+            method,
+        }
+    }
+
+    fn to_challenge(&self) -> Result<Challenge, PkceError> {
+        let challenge = Challenge::new(self.secret.clone(), self.method.into())?;
+
+        Ok(challenge)
+    }
+}
+
+/// Represents verifiers exposed to foreign languages.
+#[derive(Debug, uniffi::Object)]
+pub struct PkceVerifier {
+    verifier: Verifier<'static>,
+}
+
+#[uniffi::export]
+impl PkceVerifier {
+    /// Generates random verifiers of the given length.
+    #[uniffi::constructor]
+    pub fn generate(length: u32) -> Result<Arc<Self>, PkceError> {
+        let length = Length::new(length as usize)?;
+
+        let verifier = Verifier::try_generate(length)?;
+
+        Ok(Arc::new(Self { verifier }))
+    }
+
+    /// Parses the given string, validating it.
+    #[uniffi::constructor]
+    pub fn parse(string: String) -> Result<Arc<Self>, PkceError> {
+        let verifier = Verifier::owned(string)?;
+
+        Ok(Arc::new(Self { verifier }))
+    }
+
+    /// Returns the `code_verifier`.
+    pub fn get(&self) -> String {
+        self.verifier.get().to_owned()
+    }
+
+    /// Computes the challenge with the given method.
+    pub fn challenge(&self, method: PkceMethod) -> PkceChallenge {
+        let challenge = self.verifier.challenge_using(method.into());
+
+        PkceChallenge::from_challenge(&challenge, method)
+    }
+
+    /// Verifies the given challenge against the verifier.
+    pub fn verify(&self, challenge: PkceChallenge) -> Result<bool, PkceError> {
+        let challenge = challenge.to_challenge()?;
+
+        Ok(self.verifier.verify(&challenge))
+    }
+}
+
+/// Represents codes exposed to foreign languages.
+#[derive(Debug, uniffi::Object)]
+pub struct PkceCode {
+    verifier: Arc<PkceVerifier>,
+    challenge: PkceChallenge,
+}
+
+#[uniffi::export]
+impl PkceCode {
+    /// Generates random codes using the given method and the default length.
+    #[uniffi::constructor]
+    pub fn generate(method: PkceMethod) -> Result<Arc<Self>, PkceError> {
+        let api::Pair {
+            verifier,
+            challenge,
+            ..
+        } = api::generate_pair_using(method.into(), Length::default())?;
+
+        let verifier = Arc::new(PkceVerifier {
+            verifier: Verifier::owned(verifier)?,
+        });
+
+        let challenge = PkceChallenge {
+            secret: challenge,
+            method,
+        };
+
+        Ok(Arc::new(Self {
+            verifier,
+            challenge,
+        }))
+    }
+
+    /// Returns the verifier.
+    pub fn verifier(&self) -> Arc<PkceVerifier> {
+        Arc::clone(&self.verifier)
+    }
+
+    /// Returns the challenge.
+    pub fn challenge(&self) -> PkceChallenge {
+        self.challenge.clone()
+    }
+}
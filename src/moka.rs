@@ -0,0 +1,194 @@
+//! Storing PKCE code challenges in [`moka`] caches.
+//!
+//! The [`MokaStore`] type implements [`ChallengeStore`] on top of the asynchronous
+//! [`Cache`], which bounds the number of stored challenges and expires them automatically
+//! after the configured time-to-live, without running separate cache servers.
+//!
+//! Deadlines are checked against the [`Clock`] of the store when taking challenges,
+//! so that expiry is exact regardless of when the cache evicts entries.
+//!
+//! This module requires the `moka` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use pkce_std::{code::Code, moka::MokaStore, store::ChallengeStore};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let store = MokaStore::new(Duration::from_secs(600), 10_000);
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! store.store("code".to_owned(), challenge).await.unwrap();
+//!
+//! let stored = store.take("code").await.unwrap().unwrap();
+//!
+//! assert!(verifier.verify(&stored));
+//!
+//! assert!(store.take("code").await.unwrap().is_none());
+//! # }
+//! ```
+//!
+//! [`moka`]: https://docs.rs/moka
+
+use std::{convert::Infallible, future, time::Duration};
+
+use ::moka::{
+    future::Cache,
+    ops::compute::{CompResult, Op},
+};
+
+use crate::{
+    challenge::Challenge,
+    clock::{Clock, SystemClock},
+    expiring::TimedChallenge,
+    store::ChallengeStore,
+};
+
+/// Represents challenge stores backed by [`moka`] caches.
+///
+/// Challenges are stored along with their deadlines, which are checked against the [`Clock`]
+/// when taking them, while the cache itself merely evicts expired challenges eventually.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [`moka`]: https://docs.rs/moka
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct MokaStore<C: Clock = SystemClock> {
+    cache: Cache<String, TimedChallenge>,
+    ttl: Duration,
+    clock: C,
+}
+
+impl MokaStore {
+    /// Constructs [`Self`] expiring challenges after `ttl` and holding at most `capacity` of them.
+    pub fn new(ttl: Duration, capacity: u64) -> Self {
+        Self::with_clock(ttl, capacity, SystemClock)
+    }
+}
+
+impl<C: Clock> MokaStore<C> {
+    /// Constructs [`Self`] expiring challenges after `ttl`, as measured by the given clock,
+    /// and holding at most `capacity` of them.
+    pub fn with_clock(ttl: Duration, capacity: u64, clock: C) -> Self {
+        let cache = Cache::builder()
+            .time_to_live(ttl)
+            .max_capacity(capacity)
+            .build();
+
+        Self::from_cache(cache, ttl, clock)
+    }
+
+    /// Constructs [`Self`] from the given cache, expiring challenges after `ttl`,
+    /// as measured by the given clock.
+    ///
+    /// This allows configuring the cache arbitrarily, for instance, with eviction listeners.
+    pub const fn from_cache(cache: Cache<String, TimedChallenge>, ttl: Duration, clock: C) -> Self {
+        Self { cache, ttl, clock }
+    }
+
+    /// Returns the underlying cache.
+    pub const fn cache(&self) -> &Cache<String, TimedChallenge> {
+        &self.cache
+    }
+
+    /// Consumes [`Self`] and returns the underlying cache.
+    pub fn into_cache(self) -> Cache<String, TimedChallenge> {
+        self.cache
+    }
+
+    /// Returns the time-to-live of stored challenges.
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the clock used to expire challenges.
+    pub const fn clock(&self) -> &C {
+        &self.clock
+    }
+}
+
+impl<C: Clock + Send + Sync> ChallengeStore for MokaStore<C> {
+    type Error = Infallible;
+
+    async fn store(&self, key: String, challenge: Challenge) -> Result<(), Self::Error> {
+        let timed = TimedChallenge::new_using(challenge, self.ttl, &self.clock);
+
+        self.cache.insert(key, timed).await;
+
+        Ok(())
+    }
+
+    async fn take(&self, key: &str) -> Result<Option<Challenge>, Self::Error> {
+        // `remove` returns entries evicted lazily too, while compute closures only see live ones
+        let result = self
+            .cache
+            .entry_by_ref(key)
+            .and_compute_with(|entry| {
+                future::ready(if entry.is_some() { Op::Remove } else { Op::Nop })
+            })
+            .await;
+
+        let CompResult::Removed(entry) = result else {
+            return Ok(None);
+        };
+
+        let timed = entry.into_value();
+
+        let challenge =
+            (!timed.expiry().is_expired_using(&self.clock)).then(|| timed.into_challenge());
+
+        Ok(challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        challenge::Challenge, clock::ManualClock, method::Method, moka::MokaStore,
+        store::ChallengeStore,
+    };
+
+    const SECRET: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    fn challenge() -> Challenge {
+        Challenge::new(SECRET.to_owned(), Method::Plain).unwrap()
+    }
+
+    #[tokio::test]
+    async fn take_once() {
+        let store = MokaStore::new(Duration::from_secs(60), 16);
+
+        store.store("code".to_owned(), challenge()).await.unwrap();
+
+        let taken = store.take("code").await.unwrap().unwrap();
+
+        assert_eq!(taken.secret(), SECRET);
+
+        assert!(store.take("code").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn expiry() {
+        let ttl = Duration::from_secs(60);
+
+        let clock = Arc::new(ManualClock::new(SystemTime::UNIX_EPOCH));
+
+        let store = MokaStore::with_clock(ttl, 16, Arc::clone(&clock));
+
+        store.store("code".to_owned(), challenge()).await.unwrap();
+
+        clock.advance(ttl);
+
+        assert!(store.take("code").await.unwrap().is_none());
+    }
+}
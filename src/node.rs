@@ -0,0 +1,83 @@
+//! Node.js bindings via [`napi`].
+//!
+//! This module exposes a small N-API surface, so that Node.js backends can use this crate
+//! instead of re-implementing the rules of RFC 7636 in JavaScript:
+//!
+//! - [`generatePair`] generates the `code_verifier` and the `code_challenge`;
+//! - [`challenge`] derives the `code_challenge` from the `code_verifier`;
+//! - [`verify`] verifies the `code_verifier` against the `code_challenge`.
+//!
+//! The addon is built with `cargo rustc --features napi --crate-type cdylib`,
+//! or via the `@napi-rs/cli` tooling.
+//!
+//! [`napi`]: https://docs.rs/napi
+//! [`generatePair`]: generate_pair
+
+use napi::{Error, Result};
+use napi_derive::napi;
+
+use crate::{api, method::Method};
+
+fn reason<E: ToString>(error: E) -> Error {
+    Error::from_reason(error.to_string())
+}
+
+/// Represents generated `(verifier, challenge)` pairs exposed to JavaScript.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct Pair {
+    /// The `code_verifier`.
+    pub verifier: String,
+    /// The `code_challenge`.
+    pub challenge: String,
+    /// The `code_challenge_method`.
+    pub method: String,
+}
+
+/// Generates the `(verifier, challenge)` pair using the default method and length.
+///
+/// # Errors
+///
+/// Returns [`Error`] if the operating system fails to provide entropy.
+#[napi(js_name = "generatePair")]
+pub fn generate_pair() -> Result<Pair> {
+    let api::Pair {
+        verifier,
+        challenge,
+        method,
+    } = api::generate_pair().map_err(reason)?;
+
+    let pair = Pair {
+        verifier,
+        challenge,
+        method,
+    };
+
+    Ok(pair)
+}
+
+/// Derives the `code_challenge` from the `code_verifier` using the `method`.
+///
+/// If the method is not provided, the default one is used.
+///
+/// # Errors
+///
+/// Returns [`Error`] if the verifier is invalid or the method is unknown.
+#[napi]
+pub fn challenge(verifier: String, method: Option<String>) -> Result<String> {
+    let default = Method::default();
+
+    let method = method.as_deref().unwrap_or(default.static_str());
+
+    api::derive_challenge(&verifier, method).map_err(reason)
+}
+
+/// Verifies the given `code_verifier` against the `code_challenge` using the `method`.
+///
+/// # Errors
+///
+/// Returns [`Error`] if any of the arguments is invalid or the method is unknown.
+#[napi]
+pub fn verify(verifier: String, challenge: String, method: String) -> Result<bool> {
+    api::verify(&verifier, &challenge, &method).map_err(reason)
+}
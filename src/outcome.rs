@@ -0,0 +1,169 @@
+//! Aggregating verification checks into single decisions.
+//!
+//! Token endpoints usually perform several checks before issuing tokens: verifying the
+//! `code_verifier` against the stored `code_challenge`, matching the `state`, and ensuring
+//! that the authorization code has not expired.
+//!
+//! The [`VerifyOutcome`] type records the results of such checks without short-circuiting
+//! and only collapses them into the accept or reject decision at the very end, so that
+//! the time taken does not depend on which of the checks failed.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//!
+//! use pkce_std::{code::Code, outcome::VerifyOutcome};
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! let now = SystemTime::now();
+//! let deadline = now + Duration::from_secs(60);
+//!
+//! let outcome = VerifyOutcome::new()
+//!     .verifier(&verifier, &challenge)
+//!     .state("xyz", "xyz")
+//!     .expiry(deadline, now);
+//!
+//! assert!(outcome.is_accepted());
+//!
+//! let outcome = outcome.state("xyz", "abc");
+//!
+//! assert!(outcome.into_result().is_err());
+//! ```
+
+use std::{hint::black_box, time::SystemTime};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::Challenge,
+    clock::{Clock, SystemClock},
+    ct,
+    verifier::Verifier,
+};
+
+/// Represents errors returned when outcomes are rejected.
+///
+/// This error intentionally does not say which of the checks failed.
+#[derive(Debug, Error)]
+#[error("verification rejected")]
+#[cfg_attr(
+    feature = "diagnostics",
+    derive(Diagnostic),
+    diagnostic(code(pkce_std::outcome), help("one or more checks failed"))
+)]
+pub struct Error;
+
+/// Represents kinds of checks recorded in [`VerifyOutcome`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Check {
+    /// The verifier against the challenge.
+    Verifier,
+    /// The state against the expected one.
+    State,
+    /// The expiry against the current time.
+    Expiry,
+    /// Any other check.
+    Custom,
+}
+
+impl Check {
+    const fn flag(self) -> u8 {
+        match self {
+            Self::Verifier => 0b0001,
+            Self::State => 0b0010,
+            Self::Expiry => 0b0100,
+            Self::Custom => 0b1000,
+        }
+    }
+}
+
+/// Represents aggregated outcomes of verification checks.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct VerifyOutcome {
+    failures: u8,
+}
+
+impl VerifyOutcome {
+    /// Constructs [`Self`] with no checks recorded.
+    pub const fn new() -> Self {
+        Self { failures: 0 }
+    }
+
+    /// Records the result of the given check.
+    ///
+    /// Failures are accumulated without branching on `passed`.
+    pub fn check(self, check: Check, passed: bool) -> Self {
+        let failed = u8::from(!black_box(passed));
+
+        Self {
+            failures: self.failures | (failed * check.flag()),
+        }
+    }
+
+    /// Records verifying the given [`Challenge`] against the [`Verifier`].
+    pub fn verifier(self, verifier: &Verifier<'_>, challenge: &Challenge) -> Self {
+        self.check(Check::Verifier, verifier.verify(challenge))
+    }
+
+    /// Records comparing the given states in constant time.
+    pub fn state<S: AsRef<str>, T: AsRef<str>>(self, expected: S, actual: T) -> Self {
+        let passed = ct::eq(expected.as_ref(), actual.as_ref());
+
+        self.check(Check::State, passed)
+    }
+
+    /// Records checking that `now` is strictly before the `deadline`.
+    pub fn expiry(self, deadline: SystemTime, now: SystemTime) -> Self {
+        self.check(Check::Expiry, now < deadline)
+    }
+
+    /// Records checking that the current time of the given [`Clock`] is strictly before
+    /// the `deadline`.
+    pub fn expiry_using<C: Clock + ?Sized>(self, deadline: SystemTime, clock: &C) -> Self {
+        self.expiry(deadline, clock.now())
+    }
+
+    /// Records checking that the current [`SystemClock`] time is strictly before the `deadline`.
+    pub fn expiry_now(self, deadline: SystemTime) -> Self {
+        self.expiry_using(deadline, &SystemClock)
+    }
+
+    /// Checks whether all recorded checks have passed.
+    pub fn is_accepted(self) -> bool {
+        black_box(self.failures) == 0
+    }
+
+    /// Checks whether any of the recorded checks has failed.
+    pub fn is_rejected(self) -> bool {
+        !self.is_accepted()
+    }
+
+    /// Checks whether the given check has failed.
+    ///
+    /// This is intended for logging purposes and should not be used to make decisions.
+    pub const fn has_failed(self, check: Check) -> bool {
+        self.failures & check.flag() != 0
+    }
+
+    /// Collapses [`Self`] into [`Result`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if any of the recorded checks has failed.
+    pub fn into_result(self) -> Result<(), Error> {
+        if self.is_accepted() {
+            Ok(())
+        } else {
+            Err(Error)
+        }
+    }
+}
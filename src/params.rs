@@ -0,0 +1,233 @@
+//! Request parameters in the wire format.
+//!
+//! The [`AuthorizationParams`] type contains `code_challenge` and `code_challenge_method`
+//! parameters sent along with authorization requests, and the [`TokenParams`] type contains
+//! the `code_verifier` parameter sent along with token requests, as defined in the [standard].
+//!
+//! With the `serde` feature enabled, both types (de)serialize using the exact parameter names,
+//! so that they can be flattened into user-defined requests or encoded directly.
+//!
+//! Note that parameters are plain strings, therefore they are not affected by the
+//! `serde-redacted` feature and are not validated when deserializing.
+//! Use [`AuthorizationParams::to_challenge`] and [`TokenParams::to_verifier`] for validation.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::Code;
+//!
+//! let (verifier, challenge) = Code::generate_default().into_params();
+//!
+//! assert!(verifier.to_verifier().unwrap().verify(&challenge.to_challenge().unwrap()));
+//! ```
+//!
+//! Encoding parameters with [`serde_json`] (the same applies to `serde_urlencoded`):
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use pkce_std::{method::Method, params::AuthorizationParams};
+//!
+//! let params = AuthorizationParams::new("secret".to_owned(), Method::Plain);
+//!
+//! let string = serde_json::to_string(&params).unwrap();
+//!
+//! assert_eq!(string, r#"{"code_challenge":"secret","code_challenge_method":"plain"}"#);
+//! # }
+//! ```
+//!
+//! [standard]: https://datatracker.ietf.org/doc/html/rfc7636#section-4
+//! [`serde_json`]: https://docs.rs/serde_json
+
+use std::borrow::Cow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    challenge::{self, Challenge},
+    method::{Method, MethodName},
+    verifier::{self, Verifier},
+};
+
+/// Represents authorization request parameters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AuthorizationParams {
+    // `serde` requires literals, which are checked against the constants in tests
+    /// The `code_challenge` parameter.
+    #[cfg_attr(feature = "serde", serde(rename = "code_challenge"))]
+    pub challenge: String,
+    /// The `code_challenge_method` parameter.
+    ///
+    /// Unknown methods are preserved in [`MethodName::Unknown`] when deserializing,
+    /// and rejected when constructing [`Challenge`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "code_challenge_method", default = "plain")
+    )]
+    pub method: MethodName,
+}
+
+#[cfg(feature = "serde")]
+const fn plain() -> MethodName {
+    MethodName::Known(Method::Plain)
+}
+
+impl AuthorizationParams {
+    /// Constructs [`Self`].
+    pub const fn new(challenge: String, method: Method) -> Self {
+        Self {
+            challenge,
+            method: MethodName::Known(method),
+        }
+    }
+
+    /// Constructs [`Challenge`] from [`Self`], validating the secret and the method.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`challenge::Error`] if the secret is invalid or the method is unsupported.
+    pub fn to_challenge(&self) -> Result<Challenge, challenge::Error> {
+        Challenge::new(self.challenge.clone(), self.method.method()?)
+    }
+}
+
+impl From<Challenge> for AuthorizationParams {
+    fn from(challenge: Challenge) -> Self {
+        let (secret, method) = challenge.into_parts();
+
+        Self::new(secret, method)
+    }
+}
+
+impl From<&Challenge> for AuthorizationParams {
+    fn from(challenge: &Challenge) -> Self {
+        Self::new(challenge.secret().to_owned(), challenge.method())
+    }
+}
+
+impl TryFrom<AuthorizationParams> for Challenge {
+    type Error = challenge::Error;
+
+    fn try_from(params: AuthorizationParams) -> Result<Self, Self::Error> {
+        let method = params.method.method()?;
+
+        Self::new(params.challenge, method)
+    }
+}
+
+/// Represents token request parameters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TokenParams<'t> {
+    // `serde` requires literals, which are checked against the constants in tests
+    /// The `code_verifier` parameter.
+    #[cfg_attr(feature = "serde", serde(rename = "code_verifier"))]
+    pub verifier: Cow<'t, str>,
+}
+
+impl<'t> TokenParams<'t> {
+    /// Constructs [`Self`].
+    pub const fn new(verifier: Cow<'t, str>) -> Self {
+        Self { verifier }
+    }
+
+    /// Constructs [`Verifier`] from [`Self`], validating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`verifier::Error`] if the verifier is invalid.
+    pub fn to_verifier(&self) -> Result<Verifier<'_>, verifier::Error> {
+        Verifier::borrowed(&self.verifier)
+    }
+}
+
+impl<'t> From<Verifier<'t>> for TokenParams<'t> {
+    fn from(verifier: Verifier<'t>) -> Self {
+        Self::new(verifier.take())
+    }
+}
+
+impl<'t> From<&'t Verifier<'_>> for TokenParams<'t> {
+    fn from(verifier: &'t Verifier<'_>) -> Self {
+        Self::new(Cow::Borrowed(verifier.get()))
+    }
+}
+
+impl<'t> TryFrom<TokenParams<'t>> for Verifier<'t> {
+    type Error = verifier::Error;
+
+    fn try_from(params: TokenParams<'t>) -> Result<Self, Self::Error> {
+        Self::new(params.verifier)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use crate::{
+        challenge::{self, CODE_CHALLENGE, CODE_CHALLENGE_METHOD},
+        error::ErrorKind,
+        method::{Method, MethodName},
+        params::{AuthorizationParams, TokenParams},
+        verifier::CODE_VERIFIER,
+    };
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    #[test]
+    fn authorization_names() {
+        let params = AuthorizationParams::new(VERIFIER.to_owned(), Method::Plain);
+
+        let mut expected = serde_json::Map::new();
+
+        expected.insert(CODE_CHALLENGE.to_owned(), json!(VERIFIER));
+        expected.insert(CODE_CHALLENGE_METHOD.to_owned(), json!("plain"));
+
+        assert_eq!(
+            serde_json::to_value(params).unwrap(),
+            Value::Object(expected)
+        );
+    }
+
+    #[test]
+    fn unknown_method() {
+        let string =
+            format!(r#"{{"code_challenge":"{VERIFIER}","code_challenge_method":"S3-512"}}"#);
+
+        let params: AuthorizationParams = serde_json::from_str(&string).unwrap();
+
+        assert_eq!(params.method, MethodName::new("S3-512"));
+
+        assert_eq!(serde_json::to_string(&params).unwrap(), string);
+
+        let error = params.to_challenge().unwrap_err();
+
+        assert!(matches!(error, challenge::Error::Unsupported(_)));
+        assert_eq!(error.kind(), ErrorKind::UnsupportedMethod);
+    }
+
+    #[test]
+    fn token_names() {
+        let params = TokenParams::new(VERIFIER.into());
+
+        let mut expected = serde_json::Map::new();
+
+        expected.insert(CODE_VERIFIER.to_owned(), json!(VERIFIER));
+
+        assert_eq!(
+            serde_json::to_value(params).unwrap(),
+            Value::Object(expected)
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Generating PKCE code verifiers according to custom policies.
+//!
+//! Some environments can not handle the full character set of verifiers, for instance,
+//! downstream log parsers choking on `.` and `~`. The [`GenerationPolicy`] type is constructed
+//! once using the [`Builder`] and then used to generate verifiers without the excluded characters.
+//!
+//! Characters are sampled uniformly from the remaining set, meaning that every verifier
+//! has `length * log2(characters)` bits of entropy. Building fails unless this is
+//! at least the configured minimum, which defaults to [`MIN_ENTROPY`] bits.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::policy::GenerationPolicy;
+//!
+//! let policy = GenerationPolicy::builder().exclude(".~").build().unwrap();
+//!
+//! let verifier = policy.generate();
+//!
+//! assert!(!verifier.get().contains(['.', '~']));
+//! ```
+//!
+//! Excluding too many characters results in an error:
+//!
+//! ```
+//! use pkce_std::{length::Length, policy::GenerationPolicy};
+//!
+//! let result = GenerationPolicy::builder()
+//!     .length(Length::MIN)
+//!     .exclude("ABCDEFGHIJKLMNOPQRSTUVWXYZ")
+//!     .build();
+//!
+//! assert!(result.is_err());
+//! ```
+
+#[cfg(feature = "rand")]
+use std::convert::Infallible;
+
+#[cfg(feature = "rand")]
+use rand::{CryptoRng, Rng};
+
+#[cfg(all(feature = "rand", not(feature = "os-rng-only")))]
+use rand::rng;
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+#[cfg(any(feature = "os-rng-only", not(feature = "rand")))]
+use crate::generate::{ENTROPY, try_entropy};
+
+use crate::{
+    charset, generate,
+    length::Length,
+    verifier::{Origin, Verifier},
+};
+
+/// The default minimum entropy of generated verifiers, in bits.
+///
+/// This matches the `32` random octets recommended by the standard.
+pub const MIN_ENTROPY: u32 = 256;
+
+/// Represents errors that can occur when building generation policies.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The excluded character is not allowed by the standard in the first place.
+    #[error("character `{character}` is not allowed in verifiers")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::policy::character),
+            help("make sure to only exclude characters from `{}`", charset::STRING),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+        )
+    )]
+    Character {
+        /// The invalid character.
+        character: char,
+    },
+
+    /// The remaining characters are not enough to provide the required entropy.
+    #[error("expected at least `{required}` bits of entropy, got `{actual}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::policy::entropy),
+            help("exclude fewer characters or increase the length")
+        )
+    )]
+    Entropy {
+        /// The entropy provided, in bits (rounded down).
+        actual: u32,
+        /// The entropy required, in bits.
+        required: u32,
+    },
+}
+
+impl Error {
+    /// Constructs [`Self::Character`].
+    pub const fn character(character: char) -> Self {
+        Self::Character { character }
+    }
+
+    /// Constructs [`Self::Entropy`].
+    pub const fn entropy(actual: u32, required: u32) -> Self {
+        Self::Entropy { actual, required }
+    }
+}
+
+/// Represents builders of [`GenerationPolicy`] values.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct Builder {
+    length: Length,
+    excluded: String,
+    min_entropy: u32,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// Constructs [`Self`] with the default length and no characters excluded.
+    pub const fn new() -> Self {
+        Self {
+            length: Length::DEFAULT,
+            excluded: String::new(),
+            min_entropy: MIN_ENTROPY,
+        }
+    }
+
+    /// Sets the length of generated verifiers.
+    #[must_use]
+    pub const fn length(mut self, length: Length) -> Self {
+        self.length = length;
+
+        self
+    }
+
+    /// Excludes the given characters, in addition to the ones excluded previously.
+    ///
+    /// The characters must be a subset of the [`charset::STRING`] set.
+    #[must_use]
+    pub fn exclude<S: AsRef<str>>(mut self, chars: S) -> Self {
+        self.excluded.push_str(chars.as_ref());
+
+        self
+    }
+
+    /// Sets the minimum entropy of generated verifiers, in bits.
+    #[must_use]
+    pub const fn min_entropy(mut self, bits: u32) -> Self {
+        self.min_entropy = bits;
+
+        self
+    }
+
+    /// Builds [`GenerationPolicy`] from the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Character`] if any of the excluded characters is not allowed
+    /// by the standard, and [`Error::Entropy`] if the remaining characters do not provide
+    /// the minimum entropy.
+    pub fn build(self) -> Result<GenerationPolicy, Error> {
+        if let Some(character) = self
+            .excluded
+            .chars()
+            .find(|&character| !charset::is_valid_char(character))
+        {
+            return Err(Error::character(character));
+        }
+
+        let allowed: String = charset::CHARS
+            .into_iter()
+            .filter(|&character| !self.excluded.contains(character))
+            .collect();
+
+        // there is nothing to sample from, regardless of the minimum entropy
+        if allowed.is_empty() {
+            return Err(Error::entropy(0, self.min_entropy));
+        }
+
+        let policy = GenerationPolicy {
+            length: self.length,
+            chars: allowed,
+        };
+
+        // the entropy is non-negative and bounded, so the cast is fine
+        let bits = policy.entropy().floor() as u32;
+
+        if bits < self.min_entropy {
+            return Err(Error::entropy(bits, self.min_entropy));
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Represents policies used to generate verifiers.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GenerationPolicy {
+    length: Length,
+    chars: String,
+}
+
+impl Default for GenerationPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GenerationPolicy {
+    /// Constructs [`Self`] with the default length and no characters excluded.
+    pub fn new() -> Self {
+        Builder::new()
+            .build()
+            .expect("the default policy is always valid")
+    }
+
+    /// Returns the [`Builder`] used to construct [`Self`].
+    pub const fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Returns the length of generated verifiers.
+    pub const fn length(&self) -> Length {
+        self.length
+    }
+
+    /// Returns the characters generated verifiers consist of.
+    pub fn chars(&self) -> &str {
+        &self.chars
+    }
+
+    /// Checks whether the given character can be generated.
+    pub fn allows(&self, character: char) -> bool {
+        self.chars.contains(character)
+    }
+
+    /// Returns the entropy of generated verifiers, in bits.
+    pub fn entropy(&self) -> f64 {
+        // both values are small enough to be represented exactly
+        let (length, count) = (self.length.get() as f64, self.chars.len() as f64);
+
+        length * count.log2()
+    }
+
+    /// Samples the verifier using random bytes provided by `entropy`.
+    ///
+    /// See [`generate::sample`] for more information.
+    fn sample<E, F: FnMut(&mut [u8]) -> Result<(), E>>(
+        &self,
+        entropy: F,
+    ) -> Result<Verifier<'static>, E> {
+        let mut data = vec![0; self.length.get()];
+
+        generate::sample(self.chars.as_bytes(), &mut data, entropy)?;
+
+        // SAFETY: the data is exactly `length` characters long,
+        // and consists of characters from the valid set only
+        let verifier = unsafe { Verifier::owned_unchecked(String::from_utf8_unchecked(data)) };
+
+        Ok(verifier.with_origin(Origin::String(self.length)))
+    }
+
+    /// Generates the verifier according to the policy.
+    #[cfg(all(feature = "rand", not(feature = "os-rng-only")))]
+    pub fn generate(&self) -> Verifier<'static> {
+        self.generate_with_rng(&mut rng())
+    }
+
+    /// Generates the verifier according to the policy using the operating system entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    #[cfg(any(feature = "os-rng-only", not(feature = "rand")))]
+    pub fn generate(&self) -> Verifier<'static> {
+        self.sample(try_entropy).expect(ENTROPY)
+    }
+
+    /// Generates the verifier according to the policy using the given generator.
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: CryptoRng + Rng + ?Sized>(&self, rng: &mut R) -> Verifier<'static> {
+        let Ok(verifier) = self.sample(|buffer| {
+            rng.fill_bytes(buffer);
+
+            Ok::<_, Infallible>(())
+        });
+
+        verifier
+    }
+}
@@ -0,0 +1,134 @@
+//! Canonical byte encoding of PKCE code verifiers.
+//!
+//! External sealing APIs (HSMs, TPMs, secret managers) usually operate on bytes.
+//! The [`to_protected_bytes`] method encodes verifiers as the single length byte
+//! followed by the verifier bytes, and [`from_protected_bytes`] restores them,
+//! validating the result on the way back in.
+//!
+//! Note that the returned bytes contain the secret as-is, so they should be handed
+//! to the sealing API and dropped as soon as possible.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::verifier::Verifier;
+//!
+//! let verifier = Verifier::generate_default();
+//!
+//! let bytes = verifier.to_protected_bytes();
+//!
+//! assert_eq!(Verifier::from_protected_bytes(&bytes).unwrap(), verifier);
+//!
+//! assert!(Verifier::from_protected_bytes(&bytes[..bytes.len() - 1]).is_err());
+//! ```
+//!
+//! [`to_protected_bytes`]: crate::verifier::Verifier::to_protected_bytes
+//! [`from_protected_bytes`]: crate::verifier::Verifier::from_protected_bytes
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{error::ErrorKind, verifier};
+
+/// The length of the length prefix, in bytes.
+pub const PREFIX: usize = 1;
+
+/// Represents errors that can occur when decoding protected bytes.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The length prefix is missing.
+    #[error("missing length prefix")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::protected::empty),
+            help("make sure the bytes were produced by `to_protected_bytes`")
+        )
+    )]
+    Empty,
+
+    /// The length prefix does not match the payload length.
+    #[error("expected payload of length `{expected}`, got `{actual}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::protected::length),
+            help("make sure the bytes were not truncated or extended")
+        )
+    )]
+    Length {
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        actual: usize,
+    },
+
+    /// The payload is not valid UTF-8.
+    #[error("payload is not valid UTF-8")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::protected::encoding),
+            help("make sure the bytes were produced by `to_protected_bytes`")
+        )
+    )]
+    Encoding,
+
+    /// The payload is not a valid verifier.
+    #[error("invalid verifier")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Verifier(#[from] verifier::Error),
+}
+
+impl Error {
+    /// Constructs [`Self::Length`].
+    pub const fn length(expected: usize, actual: usize) -> Self {
+        Self::Length { expected, actual }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Verifier(error) => error.kind(),
+            Self::Empty | Self::Length { .. } | Self::Encoding => ErrorKind::InvalidEncoding,
+        }
+    }
+}
+
+/// Encodes the given string as the length byte followed by the string bytes.
+///
+/// # Panics
+///
+/// Panics if the string is longer than [`u8::MAX`], which is never the case for verifiers.
+pub fn encode(string: &str) -> Vec<u8> {
+    let length = u8::try_from(string.len()).expect("verifiers are at most 128 bytes long");
+
+    let mut bytes = Vec::with_capacity(PREFIX + string.len());
+
+    bytes.push(length);
+    bytes.extend_from_slice(string.as_bytes());
+
+    bytes
+}
+
+/// Decodes the string from the given bytes produced by [`encode`].
+///
+/// # Errors
+///
+/// Returns [`Error::Empty`] if the length prefix is missing, [`Error::Length`] if the prefix
+/// does not match the payload length, and [`Error::Encoding`] if the payload is not UTF-8.
+pub fn decode(bytes: &[u8]) -> Result<&str, Error> {
+    let (&length, payload) = bytes.split_first().ok_or(Error::Empty)?;
+
+    let expected = usize::from(length);
+    let actual = payload.len();
+
+    if actual != expected {
+        return Err(Error::length(expected, actual));
+    }
+
+    str::from_utf8(payload).map_err(|_| Error::Encoding)
+}
@@ -0,0 +1,189 @@
+//! Python bindings via [`pyo3`].
+//!
+//! This module exposes [`Verifier`], [`Challenge`] and [`Code`] as Python classes,
+//! so that Python OAuth tooling can share the same validated implementation:
+//!
+//! ```python
+//! from pkce_std import Code, Verifier
+//!
+//! code = Code.generate()
+//!
+//! verifier = Verifier(code.verifier().get())
+//!
+//! assert verifier.verify(code.challenge())
+//! ```
+//!
+//! Extension modules are built using `maturin` with the `python` feature enabled,
+//! along with `pyo3/extension-module`.
+//!
+//! [`pyo3`]: https://docs.rs/pyo3
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    api,
+    challenge::Challenge,
+    length::{self, Length},
+    method::{Method, SHA256},
+    verifier::Verifier,
+};
+
+fn value_error<E: ToString>(error: E) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+fn parse_method(method: &str) -> PyResult<Method> {
+    method.parse().map_err(value_error)
+}
+
+fn parse_length(length: usize) -> PyResult<Length> {
+    Length::new(length).map_err(value_error)
+}
+
+/// Represents verifiers exposed to Python.
+#[pyclass(name = "Verifier", module = "pkce_std", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyVerifier {
+    verifier: Verifier<'static>,
+}
+
+#[pymethods]
+impl PyVerifier {
+    /// Parses the given string, validating it.
+    #[new]
+    fn new(string: String) -> PyResult<Self> {
+        let verifier = Verifier::owned(string).map_err(value_error)?;
+
+        Ok(Self { verifier })
+    }
+
+    /// Generates random verifiers of the given length.
+    #[staticmethod]
+    #[pyo3(signature = (length = length::DEFAULT))]
+    fn generate(length: usize) -> PyResult<Self> {
+        let verifier = Verifier::try_generate(parse_length(length)?).map_err(value_error)?;
+
+        Ok(Self { verifier })
+    }
+
+    /// Returns the verifier string.
+    fn get(&self) -> &str {
+        self.verifier.get()
+    }
+
+    /// Computes the challenge using the given method.
+    #[pyo3(signature = (method = SHA256))]
+    fn challenge(&self, method: &str) -> PyResult<PyChallenge> {
+        let challenge = self
+            .verifier
+            .try_challenge_using(parse_method(method)?)
+            .map_err(value_error)?;
+
+        Ok(PyChallenge { challenge })
+    }
+
+    /// Verifies the given challenge against the verifier.
+    fn verify(&self, challenge: &PyChallenge) -> bool {
+        self.verifier.verify(&challenge.challenge)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Verifier(<{} characters>)", self.verifier.get().len())
+    }
+}
+
+/// Represents challenges exposed to Python.
+#[pyclass(name = "Challenge", module = "pkce_std", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyChallenge {
+    challenge: Challenge,
+}
+
+#[pymethods]
+impl PyChallenge {
+    /// Constructs challenges from the given secret and method, validating them.
+    #[new]
+    #[pyo3(signature = (secret, method = SHA256))]
+    fn new(secret: String, method: &str) -> PyResult<Self> {
+        let challenge = Challenge::new(secret, parse_method(method)?).map_err(value_error)?;
+
+        Ok(Self { challenge })
+    }
+
+    /// Returns the `code_challenge`.
+    #[getter]
+    fn secret(&self) -> &str {
+        self.challenge.secret()
+    }
+
+    /// Returns the `code_challenge_method`.
+    #[getter]
+    fn method(&self) -> &str {
+        self.challenge.method().static_str()
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.challenge == other.challenge
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Challenge({:?}, {:?})",
+            self.challenge.secret(),
+            self.challenge.method().static_str()
+        )
+    }
+}
+
+/// Represents codes exposed to Python.
+#[pyclass(name = "Code", module = "pkce_std", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyCode {
+    verifier: PyVerifier,
+    challenge: PyChallenge,
+}
+
+#[pymethods]
+impl PyCode {
+    /// Generates random codes using the given method and length.
+    #[staticmethod]
+    #[pyo3(signature = (method = SHA256, length = length::DEFAULT))]
+    fn generate(method: &str, length: usize) -> PyResult<Self> {
+        let method = parse_method(method)?;
+        let length = parse_length(length)?;
+
+        let api::Pair {
+            verifier,
+            challenge,
+            ..
+        } = api::generate_pair_using(method, length).map_err(value_error)?;
+
+        let verifier = Verifier::owned(verifier).map_err(value_error)?;
+        let challenge = Challenge::new(challenge, method).map_err(value_error)?;
+
+        Ok(Self {
+            verifier: PyVerifier { verifier },
+            challenge: PyChallenge { challenge },
+        })
+    }
+
+    /// Returns the verifier.
+    fn verifier(&self) -> PyVerifier {
+        self.verifier.clone()
+    }
+
+    /// Returns the challenge.
+    fn challenge(&self) -> PyChallenge {
+        self.challenge.clone()
+    }
+}
+
+/// The `pkce_std` Python module.
+#[pymodule]
+pub fn pkce_std(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyVerifier>()?;
+    module.add_class::<PyChallenge>()?;
+    module.add_class::<PyCode>()?;
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+//! Parsing PKCE parameters from raw query strings and form bodies.
+//!
+//! Servers that do not use web frameworks can parse `application/x-www-form-urlencoded`
+//! strings directly via [`Challenge::from_query`] and [`Verifier::from_form`].
+//!
+//! Parsing fails with [`enum@Error`], which reports the parameter that was missing,
+//! duplicated or malformed. As defined in the [standard], parameters must not be included
+//! more than once, while missing `code_challenge_method` defaults to [`Method::Plain`].
+//!
+//! This module requires the `urlencoded` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{challenge::Challenge, method::Method, query::Error};
+//!
+//! let secret = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+//!
+//! let query = format!("client_id=client&code_challenge={secret}");
+//!
+//! let challenge = Challenge::from_query(&query).unwrap();
+//!
+//! assert_eq!(challenge.secret(), secret);
+//! assert_eq!(challenge.method(), Method::Plain);
+//!
+//! let error = Challenge::from_query("client_id=client").unwrap_err();
+//!
+//! assert!(matches!(error, Error::Missing(_)));
+//! assert_eq!(error.name(), "code_challenge");
+//! ```
+//!
+//! [standard]: https://datatracker.ietf.org/doc/html/rfc6749#section-3.1
+//! [`Challenge::from_query`]: crate::challenge::Challenge::from_query
+//! [`Verifier::from_form`]: crate::verifier::Verifier::from_form
+//! [`Method::Plain`]: crate::method::Method::Plain
+
+use std::borrow::Cow;
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::{self, CODE_CHALLENGE, CODE_CHALLENGE_METHOD},
+    error::ErrorKind,
+    method,
+    verifier::{self, CODE_VERIFIER},
+};
+
+/// Represents errors that can occur when parsing PKCE parameters.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The parameter is missing.
+    #[error("missing `{0}` parameter")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::query::missing),
+            help("make sure the parameter is provided")
+        )
+    )]
+    Missing(&'static str),
+
+    /// The parameter is included more than once.
+    #[error("duplicate `{0}` parameter")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::query::duplicate),
+            help("make sure the parameter is provided only once")
+        )
+    )]
+    Duplicate(&'static str),
+
+    /// The `code_challenge` parameter is invalid.
+    #[error("invalid `code_challenge` parameter")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::query::challenge), help("check the challenge"))
+    )]
+    Challenge(#[from] challenge::Error),
+
+    /// The `code_challenge_method` parameter is invalid.
+    #[error("invalid `code_challenge_method` parameter")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::query::method), help("check the method"))
+    )]
+    Method(#[from] method::Error),
+
+    /// The `code_verifier` parameter is invalid.
+    #[error("invalid `code_verifier` parameter")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::query::verifier), help("check the verifier"))
+    )]
+    Verifier(#[from] verifier::Error),
+}
+
+impl Error {
+    /// Constructs [`Self::Missing`].
+    pub const fn missing(name: &'static str) -> Self {
+        Self::Missing(name)
+    }
+
+    /// Constructs [`Self::Duplicate`].
+    pub const fn duplicate(name: &'static str) -> Self {
+        Self::Duplicate(name)
+    }
+
+    /// Returns the name of the parameter that caused this error.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Missing(name) | Self::Duplicate(name) => name,
+            Self::Challenge(_) => CODE_CHALLENGE,
+            Self::Method(_) => CODE_CHALLENGE_METHOD,
+            Self::Verifier(_) => CODE_VERIFIER,
+        }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Missing(_) => ErrorKind::MissingParameter,
+            Self::Duplicate(_) => ErrorKind::DuplicateParameter,
+            Self::Challenge(error) => error.kind(),
+            Self::Method(error) => error.kind(),
+            Self::Verifier(error) => error.kind(),
+        }
+    }
+}
+
+/// Finds the values of the given parameters in the form-encoded string.
+///
+/// # Errors
+///
+/// Returns [`Error::Duplicate`] if any of the parameters is included more than once.
+pub(crate) fn find<'s, const N: usize>(
+    string: &'s str,
+    names: [&'static str; N],
+) -> Result<[Option<Cow<'s, str>>; N], Error> {
+    let mut values = [const { None }; N];
+
+    for (key, value) in form_urlencoded::parse(string.as_bytes()) {
+        let Some(index) = names.iter().position(|name| *name == key) else {
+            continue;
+        };
+
+        if values[index].is_some() {
+            return Err(Error::duplicate(names[index]));
+        }
+
+        values[index] = Some(value);
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        challenge::Challenge,
+        error::ErrorKind,
+        method::Method,
+        query::{Error, find},
+        verifier::Verifier,
+    };
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    #[test]
+    fn find_values() {
+        let [a, b, c] = find("a=1&b=%20&d=3", ["a", "b", "c"]).unwrap();
+
+        assert_eq!(a.as_deref(), Some("1"));
+        assert_eq!(b.as_deref(), Some(" "));
+        assert!(c.is_none());
+    }
+
+    #[test]
+    fn find_duplicate() {
+        let error = find("a=1&a=2", ["a"]).unwrap_err();
+
+        assert!(matches!(error, Error::Duplicate("a")));
+        assert_eq!(error.kind(), ErrorKind::DuplicateParameter);
+    }
+
+    #[test]
+    fn challenge_method() {
+        let query = format!("code_challenge={VERIFIER}&code_challenge_method=S1024");
+
+        let error = Challenge::from_query(&query).unwrap_err();
+
+        assert!(matches!(error, Error::Method(_)));
+        assert_eq!(error.name(), "code_challenge_method");
+
+        let query = format!("code_challenge={VERIFIER}&code_challenge_method=plain");
+
+        assert_eq!(
+            Challenge::from_query(&query).unwrap().method(),
+            Method::Plain
+        );
+    }
+
+    #[test]
+    fn verifier() {
+        let form = format!("grant_type=authorization_code&code_verifier={VERIFIER}");
+
+        assert_eq!(Verifier::from_form(&form).unwrap().get(), VERIFIER);
+
+        let error = Verifier::from_form("code_verifier=short").unwrap_err();
+
+        assert!(matches!(error, Error::Verifier(_)));
+        assert_eq!(error.name(), "code_verifier");
+
+        let error = Verifier::from_form("").unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::MissingParameter);
+    }
+}
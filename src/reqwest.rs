@@ -0,0 +1,111 @@
+//! Attaching PKCE parameters to [`reqwest`] requests.
+//!
+//! The [`RequestBuilderExt`] trait extends [`RequestBuilder`] with methods that attach
+//! the `code_challenge` and `code_challenge_method` query parameters to authorization requests,
+//! and the `code_verifier` form parameter to token requests.
+//!
+//! Since forms replace the request body, token requests should be built via
+//! [`pkce_token_form`], which merges the `code_verifier` with the regular parameters.
+//!
+//! This module requires the `reqwest` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{reqwest::RequestBuilderExt, verifier::Verifier};
+//! use reqwest::Client;
+//!
+//! let verifier = Verifier::borrowed("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ").unwrap();
+//!
+//! let challenge = verifier.challenge();
+//!
+//! let client = Client::new();
+//!
+//! let authorization = client
+//!     .get("https://example.com/authorize?client_id=client")
+//!     .pkce_challenge(&challenge)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(
+//!     authorization.url().query(),
+//!     Some(
+//!         "client_id=client\
+//!         &code_challenge=i5ws67KoNWO9H0IZ1vpKrfzT4Zfy2BgrRswaSJHNEqk\
+//!         &code_challenge_method=S256"
+//!     ),
+//! );
+//!
+//! let token = client
+//!     .post("https://example.com/token")
+//!     .pkce_token_form(&verifier, [("grant_type", "authorization_code"), ("code", "code")])
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(
+//!     token.body().and_then(|body| body.as_bytes()),
+//!     Some(
+//!         "grant_type=authorization_code&code=code\
+//!         &code_verifier=dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ"
+//!             .as_bytes()
+//!     ),
+//! );
+//! ```
+//!
+//! [`reqwest`]: https://docs.rs/reqwest
+//! [`pkce_token_form`]: RequestBuilderExt::pkce_token_form
+
+use ::reqwest::{RequestBuilder, header};
+
+use crate::{
+    challenge::Challenge,
+    http::{CONTENT_TYPE, token_body},
+    verifier::Verifier,
+};
+
+/// Extends [`RequestBuilder`] with methods attaching PKCE parameters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+pub trait RequestBuilderExt: Sized {
+    /// Appends `code_challenge` and `code_challenge_method` query parameters.
+    #[must_use]
+    fn pkce_challenge(self, challenge: &Challenge) -> Self;
+
+    /// Sets the form body containing the `code_verifier` parameter only.
+    ///
+    /// This replaces the body; see [`pkce_token_form`] for merging with other parameters.
+    ///
+    /// [`pkce_token_form`]: Self::pkce_token_form
+    #[must_use]
+    fn pkce_verifier(self, verifier: &Verifier<'_>) -> Self {
+        self.pkce_token_form(verifier, [] as [(&str, &str); 0])
+    }
+
+    /// Sets the form body containing the given fields merged with the `code_verifier` parameter.
+    ///
+    /// Any `code_verifier` contained in the fields is replaced by the given verifier.
+    #[must_use]
+    fn pkce_token_form<I, K, V>(self, verifier: &Verifier<'_>, fields: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>;
+}
+
+impl RequestBuilderExt for RequestBuilder {
+    fn pkce_challenge(self, challenge: &Challenge) -> Self {
+        self.query(&challenge.query_pairs())
+    }
+
+    fn pkce_token_form<I, K, V>(self, verifier: &Verifier<'_>, fields: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.header(header::CONTENT_TYPE, CONTENT_TYPE)
+            .body(token_body(verifier, fields))
+    }
+}
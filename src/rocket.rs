@@ -0,0 +1,171 @@
+//! Accepting PKCE parameters in [`rocket`] applications.
+//!
+//! This module implements form guards, which are used both for forms and query parameters:
+//!
+//! - [`Verifier<'v>`] and [`Method`] implement [`FromFormField`], borrowing from the form
+//!   whenever possible;
+//! - [`Challenge`] implements [`FromForm`], reading `code_challenge` and `code_challenge_method`
+//!   fields. Missing `code_challenge_method` defaults to [`Method::Plain`].
+//!
+//! Invalid values are reported as validation errors.
+//!
+//! This module requires the `rocket` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{challenge::Challenge, verifier::Verifier};
+//! use rocket::{FromForm, form::Form, get, post, routes};
+//!
+//! #[get("/authorize?<client_id>&<challenge..>")]
+//! fn authorize(client_id: &str, challenge: Challenge) -> String {
+//!     format!("{client_id}: {}", challenge.method().static_str())
+//! }
+//!
+//! #[derive(FromForm)]
+//! struct TokenRequest<'r> {
+//!     code: &'r str,
+//!     code_verifier: Verifier<'r>,
+//! }
+//!
+//! #[post("/token", data = "<request>")]
+//! fn token(request: Form<TokenRequest<'_>>) -> String {
+//!     request.code_verifier.fingerprint().to_string()
+//! }
+//!
+//! let rocket = rocket::build().mount("/", routes![authorize, token]);
+//! ```
+//!
+//! [`rocket`]: https://docs.rs/rocket
+//! [`Verifier<'v>`]: Verifier
+
+use ::rocket::{
+    async_trait,
+    form::{self, DataField, FromForm, FromFormField, Options, ValueField},
+};
+
+use crate::{challenge::Challenge, method::Method, verifier::Verifier};
+
+#[async_trait]
+impl<'v> FromFormField<'v> for Verifier<'v> {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Self::borrowed(field.value)
+            .map_err(|error| form::Error::validation(error.to_string()).into())
+    }
+}
+
+#[async_trait]
+impl<'v> FromFormField<'v> for Method {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        field.value.parse().map_err(|error: crate::method::Error| {
+            form::Error::validation(error.to_string()).into()
+        })
+    }
+}
+
+/// Represents unvalidated `code_challenge` and `code_challenge_method` fields.
+///
+/// This type is used to parse [`Challenge`], which validates the secret against the method.
+#[derive(Debug, ::rocket::FromForm)]
+pub struct Params<'r> {
+    // `rocket` requires literals, which are checked against the constants in tests
+    /// The `code_challenge` field.
+    #[field(name = "code_challenge")]
+    pub secret: &'r str,
+    /// The `code_challenge_method` field.
+    #[field(name = "code_challenge_method", default = Method::Plain)]
+    pub method: Method,
+}
+
+/// Represents the parsing context of [`Challenge`].
+pub type Context<'r> = <Params<'r> as FromForm<'r>>::Context;
+
+#[async_trait]
+impl<'r> FromForm<'r> for Challenge {
+    type Context = Context<'r>;
+
+    fn init(options: Options) -> Self::Context {
+        Params::init(options)
+    }
+
+    fn push_value(context: &mut Self::Context, field: ValueField<'r>) {
+        Params::push_value(context, field);
+    }
+
+    async fn push_data(context: &mut Self::Context, field: DataField<'r, '_>) {
+        Params::push_data(context, field).await;
+    }
+
+    fn push_error(context: &mut Self::Context, error: form::Error<'r>) {
+        Params::push_error(context, error);
+    }
+
+    fn finalize(context: Self::Context) -> form::Result<'r, Self> {
+        let params = Params::finalize(context)?;
+
+        Self::new(params.secret.to_owned(), params.method)
+            .map_err(|error| form::Error::validation(error.to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::{FromForm, form::Form};
+
+    use crate::{
+        challenge::{CODE_CHALLENGE, CODE_CHALLENGE_METHOD, Challenge},
+        method::Method,
+        verifier::Verifier,
+    };
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    #[derive(FromForm)]
+    struct TokenRequest<'r> {
+        code_verifier: Verifier<'r>,
+    }
+
+    #[test]
+    fn verifier() {
+        let string = format!("code=code&code_verifier={VERIFIER}");
+
+        let request: TokenRequest<'_> = Form::parse(&string).unwrap();
+
+        assert_eq!(request.code_verifier.get(), VERIFIER);
+    }
+
+    #[test]
+    fn verifier_invalid() {
+        let result: Result<TokenRequest<'_>, _> = Form::parse("code_verifier=invalid");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn challenge() {
+        let string = format!("code_challenge={VERIFIER}");
+
+        let challenge: Challenge = Form::parse(&string).unwrap();
+
+        assert_eq!(challenge.secret(), VERIFIER);
+        assert_eq!(challenge.method(), Method::Plain);
+    }
+
+    #[test]
+    fn challenge_names() {
+        let string = format!("{CODE_CHALLENGE}={VERIFIER}&{CODE_CHALLENGE_METHOD}=plain");
+
+        let challenge: Challenge = Form::parse(&string).unwrap();
+
+        assert_eq!(challenge.secret(), VERIFIER);
+        assert_eq!(challenge.method(), Method::Plain);
+    }
+
+    #[test]
+    fn challenge_invalid() {
+        let result: Result<Challenge, _> =
+            Form::parse("code_challenge=invalid&code_challenge_method=S256");
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,524 @@
+//! Sealing codes for stateless storage.
+//!
+//! Web applications need to keep verifiers between redirecting to authorization servers
+//! and exchanging codes for tokens. Instead of keeping them in server-side sessions,
+//! codes can be sealed using [`Code::seal`], stashed in cookies or `state` parameters,
+//! and later opened using [`Code::unseal`].
+//!
+//! Sealing uses the XChaCha20-Poly1305 AEAD with random nonces, so sealed codes are both
+//! confidential and tamper-proof. The result is URL-safe Base64 without padding.
+//!
+//! Sealed codes include the time of sealing, and unsealing refuses codes older than
+//! the given time-to-live, so that leaked cookies can not be replayed indefinitely.
+//! Both [`Code::seal_using`] and [`Code::unseal_using`] accept any [`Clock`].
+//!
+//! Keys and nonces are drawn directly from the operating system via [`getrandom`].
+//!
+//! This module requires the `seal` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use pkce_std::{code::Code, seal::Key};
+//!
+//! const TTL: Duration = Duration::from_secs(600);
+//!
+//! let key = Key::generate();
+//!
+//! let code = Code::generate_default();
+//!
+//! let sealed = code.seal(&key);
+//!
+//! let unsealed = Code::unseal(&key, &sealed, TTL).unwrap();
+//!
+//! assert_eq!(unsealed.challenge(), code.challenge());
+//!
+//! assert!(Code::unseal(&Key::generate(), &sealed, TTL).is_err());
+//! ```
+//!
+//! [`getrandom`]: https://docs.rs/getrandom
+
+use std::{
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use chacha20poly1305::{
+    KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, Payload},
+};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    clock::{Clock, SystemClock},
+    code::{self, Code},
+    encoding::{self, DecodeError},
+    error::ErrorKind,
+    generate::ENTROPY,
+    method,
+    tagged::SEPARATOR,
+    verifier::Verifier,
+};
+
+/// The length of keys, in bytes.
+pub const KEY_LENGTH: usize = 32;
+
+/// The length of nonces, in bytes.
+pub const NONCE_LENGTH: usize = 24;
+
+/// The associated data, binding sealed data to its purpose.
+pub const ASSOCIATED_DATA: &[u8] = b"pkce-std/code";
+
+/// Represents sealing keys.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Clone)]
+pub struct Key {
+    bytes: [u8; KEY_LENGTH],
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("Key").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Key {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Key {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Key {}
+
+impl From<[u8; KEY_LENGTH]> for Key {
+    fn from(bytes: [u8; KEY_LENGTH]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl Key {
+    /// Constructs [`Self`] from the given bytes.
+    pub const fn new(bytes: [u8; KEY_LENGTH]) -> Self {
+        Self { bytes }
+    }
+
+    /// Generates random [`Self`], drawing entropy directly from the operating system.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    pub fn generate() -> Self {
+        let mut bytes = [0; KEY_LENGTH];
+
+        getrandom::fill(&mut bytes).expect(ENTROPY);
+
+        Self::new(bytes)
+    }
+
+    /// Returns the bytes of the key.
+    pub const fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+        &self.bytes
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.bytes.into())
+    }
+}
+
+/// Represents errors that can occur when unsealing codes.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The sealed data is not valid Base64.
+    #[error("invalid encoding")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Decode(#[from] DecodeError),
+
+    /// The sealed data is too short to contain the nonce.
+    #[error("sealed data is too short")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::seal::truncated),
+            help("make sure the sealed data is not truncated")
+        )
+    )]
+    Truncated,
+
+    /// The sealed data failed authentication.
+    #[error("failed to authenticate sealed data")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::seal::unauthenticated),
+            help("make sure the key is correct; this may also indicate tampering")
+        )
+    )]
+    Unauthenticated,
+
+    /// The unsealed data is malformed.
+    #[error("malformed unsealed data")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::seal::format),
+            help("make sure the data was sealed by this crate")
+        )
+    )]
+    Format,
+
+    /// The sealed data has expired.
+    #[error("sealed data has expired")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::seal::expired),
+            help("make sure to unseal the data within its time-to-live")
+        )
+    )]
+    Expired,
+
+    /// The unsealed method is unknown.
+    #[error("invalid method")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Method(#[from] method::Error),
+
+    /// The unsealed code is invalid.
+    #[error("invalid code")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Code(#[from] code::Error),
+}
+
+impl Error {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Decode(error) => error.kind(),
+            Self::Truncated => ErrorKind::InvalidLength,
+            Self::Unauthenticated => ErrorKind::Unauthenticated,
+            Self::Format => ErrorKind::InvalidEncoding,
+            Self::Expired => ErrorKind::Expired,
+            Self::Method(error) => error.kind(),
+            Self::Code(error) => error.kind(),
+        }
+    }
+}
+
+/// Returns the time elapsed since the Unix epoch at the given time.
+///
+/// Times before the epoch are treated as the epoch itself.
+fn since_epoch(time: SystemTime) -> Duration {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Seals the given code using the key, recording the time of sealing according to the clock.
+///
+/// See [`Code::seal_using`] for more information.
+///
+/// # Panics
+///
+/// Panics if the operating system fails to provide entropy.
+pub fn seal_using<C: Clock + ?Sized>(key: &Key, code: &Code<'_>, clock: &C) -> String {
+    let mut nonce = [0; NONCE_LENGTH];
+
+    getrandom::fill(&mut nonce).expect(ENTROPY);
+
+    let plaintext = format!(
+        "{timestamp}{SEPARATOR}{method}{SEPARATOR}{verifier}",
+        timestamp = since_epoch(clock.now()).as_secs(),
+        method = code.challenge().method().static_str(),
+        verifier = code.verifier().get(),
+    );
+
+    let payload = Payload {
+        msg: plaintext.as_bytes(),
+        aad: ASSOCIATED_DATA,
+    };
+
+    // encryption only fails if the plaintext is astronomically large
+    let ciphertext = key
+        .cipher()
+        .encrypt(XNonce::from_slice(&nonce), payload)
+        .expect("the plaintext is too large");
+
+    #[cfg(feature = "zeroize")]
+    let mut plaintext = plaintext;
+
+    #[cfg(feature = "zeroize")]
+    plaintext.zeroize();
+
+    let mut sealed = Vec::with_capacity(NONCE_LENGTH + ciphertext.len());
+
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+
+    encoding::encode(sealed)
+}
+
+/// Seals the given code using the key.
+///
+/// See [`Code::seal`] for more information.
+///
+/// # Panics
+///
+/// Panics if the operating system fails to provide entropy.
+pub fn seal(key: &Key, code: &Code<'_>) -> String {
+    seal_using(key, code, &SystemClock)
+}
+
+/// Parses the unsealed plaintext, checking its age according to the given clock.
+fn open<C: Clock + ?Sized>(
+    plaintext: &[u8],
+    ttl: Duration,
+    clock: &C,
+) -> Result<Code<'static>, Error> {
+    let string = str::from_utf8(plaintext).map_err(|_| Error::Format)?;
+
+    let mut parts = string.splitn(3, SEPARATOR);
+
+    let (Some(timestamp), Some(method), Some(verifier)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Error::Format);
+    };
+
+    let timestamp: u64 = timestamp.parse().map_err(|_| Error::Format)?;
+
+    // overflowing deadlines are never reached
+    let expired = Duration::from_secs(timestamp)
+        .checked_add(ttl)
+        .is_some_and(|deadline| since_epoch(clock.now()) >= deadline);
+
+    if expired {
+        return Err(Error::Expired);
+    }
+
+    let method = method.parse()?;
+
+    let verifier = Verifier::owned(verifier.to_owned()).map_err(code::Error::from)?;
+
+    let challenge = verifier
+        .try_challenge_using(method)
+        .map_err(code::Error::from)?;
+
+    Ok(Code::new(verifier, challenge))
+}
+
+/// Unseals the code using the key, refusing codes sealed more than `ttl` ago
+/// according to the given clock.
+///
+/// See [`Code::unseal_using`] for more information.
+///
+/// # Errors
+///
+/// Returns [`enum@Error`] if the sealed data is malformed, fails authentication,
+/// has expired or contains invalid codes.
+pub fn unseal_using<C: Clock + ?Sized>(
+    key: &Key,
+    sealed: &str,
+    ttl: Duration,
+    clock: &C,
+) -> Result<Code<'static>, Error> {
+    let data = encoding::decode(sealed)?;
+
+    if data.len() < NONCE_LENGTH {
+        return Err(Error::Truncated);
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+
+    let payload = Payload {
+        msg: ciphertext,
+        aad: ASSOCIATED_DATA,
+    };
+
+    let plaintext = key
+        .cipher()
+        .decrypt(XNonce::from_slice(nonce), payload)
+        .map_err(|_| Error::Unauthenticated)?;
+
+    let result = open(&plaintext, ttl, clock);
+
+    #[cfg(feature = "zeroize")]
+    let mut plaintext = plaintext;
+
+    #[cfg(feature = "zeroize")]
+    plaintext.zeroize();
+
+    result
+}
+
+/// Unseals the code using the key, refusing codes sealed more than `ttl` ago.
+///
+/// See [`Code::unseal`] for more information.
+///
+/// # Errors
+///
+/// Returns [`enum@Error`] if the sealed data is malformed, fails authentication,
+/// has expired or contains invalid codes.
+pub fn unseal(key: &Key, sealed: &str, ttl: Duration) -> Result<Code<'static>, Error> {
+    unseal_using(key, sealed, ttl, &SystemClock)
+}
+
+impl Code<'_> {
+    /// Seals [`Self`] using the given key, returning URL-safe Base64 string.
+    ///
+    /// The time of sealing is read from the given clock.
+    ///
+    /// Refer to the [`seal`] module documentation for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    ///
+    /// [`seal`]: self
+    pub fn seal_using<C: Clock + ?Sized>(&self, key: &Key, clock: &C) -> String {
+        seal_using(key, self, clock)
+    }
+
+    /// Seals [`Self`] using the given key, returning URL-safe Base64 string.
+    ///
+    /// Refer to the [`seal`] module documentation for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    ///
+    /// [`seal`]: self
+    pub fn seal(&self, key: &Key) -> String {
+        seal(key, self)
+    }
+}
+
+impl Code<'static> {
+    /// Unseals [`Self`] sealed using the given key, refusing codes sealed more than `ttl` ago
+    /// according to the given clock.
+    ///
+    /// Refer to the [`seal`] module documentation for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`enum@Error`] if the sealed data is malformed, fails authentication,
+    /// has expired or contains invalid codes.
+    ///
+    /// [`seal`]: self
+    pub fn unseal_using<C: Clock + ?Sized>(
+        key: &Key,
+        sealed: &str,
+        ttl: Duration,
+        clock: &C,
+    ) -> Result<Self, Error> {
+        unseal_using(key, sealed, ttl, clock)
+    }
+
+    /// Unseals [`Self`] sealed using the given key, refusing codes sealed more than `ttl` ago.
+    ///
+    /// Refer to the [`seal`] module documentation for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`enum@Error`] if the sealed data is malformed, fails authentication,
+    /// has expired or contains invalid codes.
+    ///
+    /// [`seal`]: self
+    pub fn unseal(key: &Key, sealed: &str, ttl: Duration) -> Result<Self, Error> {
+        unseal(key, sealed, ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        clock::ManualClock,
+        code::Code,
+        encoding,
+        error::ErrorKind,
+        seal::{Error, Key},
+    };
+
+    const KEY: Key = Key::new([7; 32]);
+
+    const TTL: Duration = Duration::from_secs(600);
+
+    #[test]
+    fn round_trip() {
+        let code = Code::generate_default();
+
+        let unsealed = Code::unseal(&KEY, &code.seal(&KEY), TTL).unwrap();
+
+        assert_eq!(unsealed.verifier(), code.verifier());
+        assert_eq!(unsealed.challenge(), code.challenge());
+    }
+
+    #[test]
+    fn nonces() {
+        let code = Code::generate_default();
+
+        assert_ne!(code.seal(&KEY), code.seal(&KEY));
+    }
+
+    #[test]
+    fn expiry() {
+        let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+
+        let sealed = Code::generate_default().seal_using(&KEY, &clock);
+
+        clock.advance(TTL - Duration::from_secs(1));
+
+        assert!(Code::unseal_using(&KEY, &sealed, TTL, &clock).is_ok());
+
+        clock.advance(Duration::from_secs(1));
+
+        let error = Code::unseal_using(&KEY, &sealed, TTL, &clock).unwrap_err();
+
+        assert!(matches!(error, Error::Expired));
+        assert_eq!(error.kind(), ErrorKind::Expired);
+
+        assert!(Code::unseal_using(&KEY, &sealed, Duration::MAX, &clock).is_ok());
+    }
+
+    #[test]
+    fn tampered() {
+        let sealed = Code::generate_default().seal(&KEY);
+
+        let mut data = encoding::decode(&sealed).unwrap();
+
+        *data.last_mut().unwrap() ^= 1;
+
+        let error = Code::unseal(&KEY, &encoding::encode(data), TTL).unwrap_err();
+
+        assert!(matches!(error, Error::Unauthenticated));
+        assert_eq!(error.kind(), ErrorKind::Unauthenticated);
+    }
+
+    #[test]
+    fn truncated() {
+        let error = Code::unseal(&KEY, "AAAA", TTL).unwrap_err();
+
+        assert!(matches!(error, Error::Truncated));
+    }
+}
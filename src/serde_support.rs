@@ -0,0 +1,168 @@
+//! Serde adapters using RFC parameter names.
+//!
+//! Each module here is meant to be used with `#[serde(flatten, with = "...")]`, so that
+//! the field is (de)serialized using the exact parameter names defined in the [standard],
+//! regardless of the field name itself:
+//!
+//! - [`code_verifier`] handles [`Verifier`] as the `code_verifier` parameter;
+//! - [`code_challenge`] handles [`Challenge`] as the `code_challenge` parameter, along with
+//!   the `code_challenge_method` one, since the secret can not be validated without the method;
+//! - [`code_challenge_method`] handles [`Method`] as the `code_challenge_method` parameter.
+//!
+//! Values are validated when deserializing.
+//!
+//! This module requires the `serde` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{Code, serde_support, verifier::Verifier};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct TokenRequest<'t> {
+//!     code: String,
+//!     #[serde(flatten, with = "serde_support::code_verifier")]
+//!     verifier: Verifier<'t>,
+//! }
+//!
+//! let verifier = Code::generate_default().into_pair().0;
+//!
+//! let request = TokenRequest {
+//!     code: "code".to_owned(),
+//!     verifier: verifier.clone(),
+//! };
+//!
+//! let string = serde_json::to_string(&request).unwrap();
+//!
+//! assert!(string.contains(r#""code_verifier":"#));
+//!
+//! let parsed: TokenRequest<'_> = serde_json::from_str(&string).unwrap();
+//!
+//! assert_eq!(parsed.verifier, verifier);
+//!
+//! let invalid = r#"{"code": "code", "code_verifier": "invalid"}"#;
+//!
+//! assert!(serde_json::from_str::<TokenRequest<'_>>(invalid).is_err());
+//! ```
+//!
+//! [standard]: https://datatracker.ietf.org/doc/html/rfc7636#section-4
+//! [`Verifier`]: crate::verifier::Verifier
+//! [`Challenge`]: crate::challenge::Challenge
+//! [`Method`]: crate::method::Method
+
+pub mod code_verifier {
+    //! Handling verifiers as the `code_verifier` parameter.
+    //!
+    //! Refer to the [parent](super) module for more information.
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use crate::verifier::Verifier;
+
+    #[derive(Serialize)]
+    struct Params<'p, 'v> {
+        code_verifier: &'p Verifier<'v>,
+    }
+
+    #[derive(Deserialize)]
+    struct OwnedParams {
+        code_verifier: String,
+    }
+
+    /// Serializes the verifier as the `code_verifier` parameter.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(
+        verifier: &Verifier<'_>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let params = Params {
+            code_verifier: verifier,
+        };
+
+        params.serialize(serializer)
+    }
+
+    /// Deserializes the verifier from the `code_verifier` parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer error if the parameter is missing or the verifier is invalid.
+    pub fn deserialize<'de, 'v, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Verifier<'v>, D::Error> {
+        let params = OwnedParams::deserialize(deserializer)?;
+
+        Verifier::owned(params.code_verifier).map_err(de::Error::custom)
+    }
+}
+
+pub mod code_challenge {
+    //! Handling challenges as `code_challenge` and `code_challenge_method` parameters.
+    //!
+    //! When deserializing, missing `code_challenge_method` defaults to [`Method::Plain`].
+    //!
+    //! This module is an alias of [`challenge::serde::flattened_params`].
+    //!
+    //! Refer to the [parent](super) module for more information.
+    //!
+    //! [`Method::Plain`]: crate::method::Method::Plain
+    //! [`challenge::serde::flattened_params`]: crate::challenge::serde::flattened_params
+
+    pub use crate::challenge::serde::flattened_params::{deserialize, serialize};
+}
+
+pub mod code_challenge_method {
+    //! Handling methods as the `code_challenge_method` parameter.
+    //!
+    //! When deserializing, missing `code_challenge_method` defaults to [`Method::Plain`],
+    //! as defined in the standard.
+    //!
+    //! Refer to the [parent](super) module for more information.
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::method::Method;
+
+    #[derive(Serialize)]
+    struct Params<'p> {
+        code_challenge_method: &'p Method,
+    }
+
+    #[derive(Deserialize)]
+    struct OwnedParams {
+        #[serde(default = "plain")]
+        code_challenge_method: Method,
+    }
+
+    const fn plain() -> Method {
+        Method::Plain
+    }
+
+    /// Serializes the method as the `code_challenge_method` parameter.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(method: &Method, serializer: S) -> Result<S::Ok, S::Error> {
+        let params = Params {
+            code_challenge_method: method,
+        };
+
+        params.serialize(serializer)
+    }
+
+    /// Deserializes the method from the `code_challenge_method` parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer error if the method is unknown.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Method, D::Error> {
+        let params = OwnedParams::deserialize(deserializer)?;
+
+        Ok(params.code_challenge_method)
+    }
+}
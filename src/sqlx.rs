@@ -0,0 +1,290 @@
+//! Reading and writing PKCE types with [`sqlx`].
+//!
+//! This module implements [`Type`], [`Encode`] and [`Decode`] for any database that supports
+//! strings, storing values as text:
+//!
+//! - [`Verifier`] is stored as-is, without copying borrowed strings when binding;
+//! - [`Method`] is stored as its string representation, for instance, `S256`;
+//! - [`Challenge`] is stored in the `method:secret` format (see [`tagged`]).
+//!
+//! Alternatively, [`Challenge`] implements [`FromRow`], reading separate `code_challenge`
+//! and `code_challenge_method` columns. Missing (`NULL`) methods default to [`Method::Plain`].
+//!
+//! Values are validated when decoding, so invalid rows result in decoding errors.
+//!
+//! This module requires the `sqlx` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{challenge::Challenge, code::Code};
+//! use sqlx::{Connection, SqliteConnection};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> sqlx::Result<()> {
+//! let mut connection = SqliteConnection::connect("sqlite::memory:").await?;
+//!
+//! sqlx::query("CREATE TABLE challenges (code TEXT PRIMARY KEY, challenge TEXT NOT NULL)")
+//!     .execute(&mut connection)
+//!     .await?;
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! sqlx::query("INSERT INTO challenges VALUES (?, ?)")
+//!     .bind("code")
+//!     .bind(&challenge)
+//!     .execute(&mut connection)
+//!     .await?;
+//!
+//! let stored: Challenge = sqlx::query_scalar("SELECT challenge FROM challenges WHERE code = ?")
+//!     .bind("code")
+//!     .fetch_one(&mut connection)
+//!     .await?;
+//!
+//! assert!(verifier.verify(&stored));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`sqlx`]: https://docs.rs/sqlx
+//! [`tagged`]: crate::tagged
+
+use std::borrow::Cow;
+
+use ::sqlx::{
+    ColumnIndex, Database, Decode, Encode, FromRow, Row, Type, encode::IsNull, error::BoxDynError,
+};
+
+use crate::{
+    challenge::{CODE_CHALLENGE, CODE_CHALLENGE_METHOD, Challenge},
+    method::Method,
+    verifier::Verifier,
+};
+
+impl<DB: Database> Type<DB> for Verifier<'_>
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(type_info: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(type_info)
+    }
+}
+
+impl<'q, 'v: 'q, DB: Database> Encode<'q, DB> for Verifier<'v>
+where
+    Cow<'q, str>: Encode<'q, DB>,
+{
+    fn encode(
+        self,
+        buffer: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        let value: Cow<'q, str> = self.take();
+
+        value.encode(buffer)
+    }
+
+    fn encode_by_ref(
+        &self,
+        buffer: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        let value: &Cow<'q, str> = self.as_cow();
+
+        value.encode_by_ref(buffer)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Verifier<'_>
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let string = <String as Decode<DB>>::decode(value)?;
+
+        let verifier = Self::owned(string)?;
+
+        Ok(verifier)
+    }
+}
+
+impl<DB: Database> Type<DB> for Method
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(type_info: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(type_info)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for Method
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buffer: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.static_str().to_owned().encode(buffer)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Method
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let string = <String as Decode<DB>>::decode(value)?;
+
+        let method = string.parse()?;
+
+        Ok(method)
+    }
+}
+
+impl<DB: Database> Type<DB> for Challenge
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    fn compatible(type_info: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(type_info)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for Challenge
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buffer: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.to_tagged_string().encode(buffer)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for Challenge
+where
+    String: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let string = <String as Decode<DB>>::decode(value)?;
+
+        let challenge = Self::from_tagged_string(&string)?;
+
+        Ok(challenge)
+    }
+}
+
+impl<'r, R: Row> FromRow<'r, R> for Challenge
+where
+    &'r str: ColumnIndex<R>,
+    String: Type<R::Database> + Decode<'r, R::Database>,
+    Option<Method>: Type<R::Database> + Decode<'r, R::Database>,
+{
+    fn from_row(row: &'r R) -> Result<Self, ::sqlx::Error> {
+        let secret: String = row.try_get(CODE_CHALLENGE)?;
+
+        let method: Option<Method> = row.try_get(CODE_CHALLENGE_METHOD)?;
+
+        Self::new(secret, method.unwrap_or(Method::Plain)).map_err(|error| {
+            ::sqlx::Error::ColumnDecode {
+                index: CODE_CHALLENGE.to_owned(),
+                source: Box::new(error),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{Connection, SqliteConnection};
+
+    use crate::{challenge::Challenge, method::Method, verifier::Verifier};
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    async fn connect() -> SqliteConnection {
+        SqliteConnection::connect("sqlite::memory:").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn verifier() {
+        let mut connection = connect().await;
+
+        let verifier = Verifier::borrowed(VERIFIER).unwrap();
+
+        let decoded: Verifier<'static> = sqlx::query_scalar("SELECT ?")
+            .bind(&verifier)
+            .fetch_one(&mut connection)
+            .await
+            .unwrap();
+
+        assert_eq!(decoded, verifier);
+    }
+
+    #[tokio::test]
+    async fn verifier_invalid() {
+        let mut connection = connect().await;
+
+        let result: Result<Verifier<'static>, _> = sqlx::query_scalar("SELECT 'invalid'")
+            .fetch_one(&mut connection)
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn challenge_tagged() {
+        let mut connection = connect().await;
+
+        let challenge = Verifier::borrowed(VERIFIER).unwrap().challenge();
+
+        let (string, decoded): (String, Challenge) = sqlx::query_as("SELECT ?, ?")
+            .bind(&challenge)
+            .bind(&challenge)
+            .fetch_one(&mut connection)
+            .await
+            .unwrap();
+
+        assert_eq!(string, challenge.to_tagged_string());
+        assert_eq!(decoded, challenge);
+    }
+
+    #[tokio::test]
+    async fn challenge_row() {
+        let mut connection = connect().await;
+
+        let challenge: Challenge =
+            sqlx::query_as("SELECT ? AS code_challenge, NULL AS code_challenge_method")
+                .bind(VERIFIER)
+                .fetch_one(&mut connection)
+                .await
+                .unwrap();
+
+        assert_eq!(challenge.secret(), VERIFIER);
+        assert_eq!(challenge.method(), Method::Plain);
+    }
+
+    #[tokio::test]
+    async fn challenge_row_invalid() {
+        let mut connection = connect().await;
+
+        let result: Result<Challenge, _> =
+            sqlx::query_as("SELECT 'invalid' AS code_challenge, 'S256' AS code_challenge_method")
+                .fetch_one(&mut connection)
+                .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Counting verification failures by cause.
+//!
+//! The [`Stats`] type is a lightweight handle of atomic counters, which applications
+//! can share between requests, read, and export to whichever monitoring system they use.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{
+//!     code::Code,
+//!     stats::{Cause, Stats},
+//! };
+//!
+//! let stats = Stats::new();
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! assert!(stats.verify(verifier.get(), &challenge));
+//! assert!(!stats.verify("too short", &challenge));
+//!
+//! assert_eq!(stats.get(Cause::Length), 1);
+//!
+//! let snapshot = stats.snapshot();
+//!
+//! assert_eq!(snapshot.total(), 1);
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    challenge::Challenge,
+    outcome::{Check, VerifyOutcome},
+    verifier::{self, Verifier},
+};
+
+/// Represents causes of verification failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cause {
+    /// The verifier has invalid length.
+    Length,
+    /// The verifier contains invalid characters.
+    Charset,
+    /// The verifier does not match the challenge.
+    Mismatch,
+    /// The authorization code has expired.
+    Expired,
+}
+
+impl Cause {
+    /// Returns the [`Cause`] corresponding to the given [`verifier::Error`].
+    pub const fn of(error: &verifier::Error) -> Self {
+        match error {
+            verifier::Error::Length(_) => Self::Length,
+            verifier::Error::String(_) => Self::Charset,
+        }
+    }
+}
+
+/// Represents point-in-time copies of [`Stats`] counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Snapshot {
+    /// The amount of [`Cause::Length`] failures.
+    pub length: u64,
+    /// The amount of [`Cause::Charset`] failures.
+    pub charset: u64,
+    /// The amount of [`Cause::Mismatch`] failures.
+    pub mismatch: u64,
+    /// The amount of [`Cause::Expired`] failures.
+    pub expired: u64,
+}
+
+impl Snapshot {
+    /// Returns the total amount of failures.
+    pub const fn total(&self) -> u64 {
+        self.length + self.charset + self.mismatch + self.expired
+    }
+}
+
+/// Represents verification failure counters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Default)]
+pub struct Stats {
+    length: AtomicU64,
+    charset: AtomicU64,
+    mismatch: AtomicU64,
+    expired: AtomicU64,
+}
+
+impl Stats {
+    /// Constructs [`Self`] with all counters set to zero.
+    pub const fn new() -> Self {
+        Self {
+            length: AtomicU64::new(0),
+            charset: AtomicU64::new(0),
+            mismatch: AtomicU64::new(0),
+            expired: AtomicU64::new(0),
+        }
+    }
+
+    const fn counter(&self, cause: Cause) -> &AtomicU64 {
+        match cause {
+            Cause::Length => &self.length,
+            Cause::Charset => &self.charset,
+            Cause::Mismatch => &self.mismatch,
+            Cause::Expired => &self.expired,
+        }
+    }
+
+    /// Records the failure with the given cause.
+    pub fn record(&self, cause: Cause) {
+        self.counter(cause).fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the failure corresponding to the given [`verifier::Error`].
+    pub fn record_error(&self, error: &verifier::Error) {
+        self.record(Cause::of(error));
+    }
+
+    /// Records the failures of the given [`VerifyOutcome`].
+    ///
+    /// Failed [`Check::Verifier`] checks are recorded as [`Cause::Mismatch`],
+    /// and failed [`Check::Expiry`] checks as [`Cause::Expired`].
+    pub fn record_outcome(&self, outcome: VerifyOutcome) {
+        if outcome.has_failed(Check::Verifier) {
+            self.record(Cause::Mismatch);
+        }
+
+        if outcome.has_failed(Check::Expiry) {
+            self.record(Cause::Expired);
+        }
+    }
+
+    /// Parses the given string as [`Verifier`] and verifies it against the [`Challenge`],
+    /// recording the failure, if any.
+    pub fn verify(&self, string: &str, challenge: &Challenge) -> bool {
+        let verifier = match Verifier::borrowed(string) {
+            Ok(verifier) => verifier,
+            Err(error) => {
+                self.record_error(&error);
+
+                return false;
+            }
+        };
+
+        let verified = verifier.verify(challenge);
+
+        if !verified {
+            self.record(Cause::Mismatch);
+        }
+
+        verified
+    }
+
+    /// Returns the current amount of failures with the given cause.
+    pub fn get(&self, cause: Cause) -> u64 {
+        self.counter(cause).load(Ordering::Relaxed)
+    }
+
+    /// Returns the [`Snapshot`] of all counters.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            length: self.get(Cause::Length),
+            charset: self.get(Cause::Charset),
+            mismatch: self.get(Cause::Mismatch),
+            expired: self.get(Cause::Expired),
+        }
+    }
+
+    /// Resets all counters to zero, returning their last values.
+    pub fn reset(&self) -> Snapshot {
+        Snapshot {
+            length: self.length.swap(0, Ordering::Relaxed),
+            charset: self.charset.swap(0, Ordering::Relaxed),
+            mismatch: self.mismatch.swap(0, Ordering::Relaxed),
+            expired: self.expired.swap(0, Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Storing PKCE code challenges between authorization and token requests.
+//!
+//! Authorization servers receive challenges in authorization requests and need them back
+//! when the corresponding token requests arrive. The [`ChallengeStore`] trait abstracts
+//! over places to keep challenges in, keyed by authorization codes (or any other keys).
+//!
+//! Challenges are *taken* out of stores, that is, each stored challenge can be retrieved
+//! at most once, which prevents authorization codes from being redeemed several times.
+//!
+//! The [`MemoryStore`] type implements the trait in memory, expiring challenges after
+//! the configured time-to-live, as measured by its [`Clock`]. The trait is also implemented
+//! by [`KvChallengeStore`] on top of any key-value store.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use pkce_std::{
+//!     code::Code,
+//!     store::{ChallengeStore, MemoryStore},
+//! };
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let store = MemoryStore::new(Duration::from_secs(600));
+//!
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! store.store("code".to_owned(), challenge).await.unwrap();
+//!
+//! let stored = store.take("code").await.unwrap().unwrap();
+//!
+//! assert!(verifier.verify(&stored));
+//!
+//! assert!(store.take("code").await.unwrap().is_none());
+//! # }
+//! ```
+//!
+//! [`KvChallengeStore`]: crate::kv::KvChallengeStore
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    error::Error as StdError,
+    future::{self, Future},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    challenge::Challenge,
+    clock::{Clock, SystemClock},
+};
+
+/// Represents stores of challenges, keyed by strings.
+///
+/// Implementations are expected to use interior mutability, as all methods take `&self`.
+pub trait ChallengeStore {
+    /// The error type returned by the store.
+    type Error: StdError + 'static;
+
+    /// Stores the challenge under the given key, replacing any previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the store fails.
+    fn store(
+        &self,
+        key: String,
+        challenge: Challenge,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Removes and returns the challenge stored under the given key, if any.
+    ///
+    /// Expired challenges are never returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Self::Error`] if the store fails.
+    fn take(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = Result<Option<Challenge>, Self::Error>> + Send;
+}
+
+impl<S: ChallengeStore + ?Sized> ChallengeStore for &S {
+    type Error = S::Error;
+
+    fn store(
+        &self,
+        key: String,
+        challenge: Challenge,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        S::store(self, key, challenge)
+    }
+
+    fn take(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = Result<Option<Challenge>, Self::Error>> + Send {
+        S::take(self, key)
+    }
+}
+
+impl<S: ChallengeStore + ?Sized> ChallengeStore for Arc<S> {
+    type Error = S::Error;
+
+    fn store(
+        &self,
+        key: String,
+        challenge: Challenge,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        S::store(self, key, challenge)
+    }
+
+    fn take(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = Result<Option<Challenge>, Self::Error>> + Send {
+        S::take(self, key)
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    challenge: Challenge,
+    /// The expiry time, if any; overflowing expiry times are never reached.
+    expires: Option<SystemTime>,
+}
+
+impl Entry {
+    fn is_live(&self, now: SystemTime) -> bool {
+        self.expires.is_none_or(|expires| expires > now)
+    }
+}
+
+/// Represents in-memory challenge stores with expiry.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug)]
+pub struct MemoryStore<C: Clock = SystemClock> {
+    entries: Mutex<HashMap<String, Entry>>,
+    ttl: Duration,
+    clock: C,
+}
+
+impl MemoryStore {
+    /// Constructs [`Self`] expiring challenges after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_clock(ttl, SystemClock)
+    }
+}
+
+impl<C: Clock> MemoryStore<C> {
+    /// Constructs [`Self`] expiring challenges after `ttl`, as measured by the given clock.
+    pub fn with_clock(ttl: Duration, clock: C) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+            clock,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the time-to-live of stored challenges.
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the clock used to expire challenges.
+    pub const fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the number of stored challenges, including expired ones not yet purged.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Checks whether the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes all expired challenges.
+    pub fn purge(&self) {
+        let now = self.clock.now();
+
+        self.lock().retain(|_, entry| entry.is_live(now));
+    }
+
+    /// Synchronous version of [`ChallengeStore::store`].
+    ///
+    /// Expired challenges are purged when storing, but only if the store would otherwise
+    /// need to grow, so that storing takes amortized constant time.
+    pub fn insert(&self, key: String, challenge: Challenge) {
+        let now = self.clock.now();
+
+        let expires = now.checked_add(self.ttl);
+
+        let mut entries = self.lock();
+
+        if entries.len() == entries.capacity() {
+            entries.retain(|_, entry| entry.is_live(now));
+        }
+
+        entries.insert(key, Entry { challenge, expires });
+    }
+
+    /// Synchronous version of [`ChallengeStore::take`].
+    pub fn remove(&self, key: &str) -> Option<Challenge> {
+        let entry = self.lock().remove(key)?;
+
+        entry.is_live(self.clock.now()).then_some(entry.challenge)
+    }
+}
+
+impl<C: Clock + Sync> ChallengeStore for MemoryStore<C> {
+    type Error = Infallible;
+
+    fn store(
+        &self,
+        key: String,
+        challenge: Challenge,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        self.insert(key, challenge);
+
+        future::ready(Ok(()))
+    }
+
+    fn take(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = Result<Option<Challenge>, Self::Error>> + Send {
+        future::ready(Ok(self.remove(key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, SystemTime},
+    };
+
+    use crate::{
+        challenge::Challenge,
+        clock::ManualClock,
+        method::Method,
+        store::{ChallengeStore, MemoryStore},
+    };
+
+    const SECRET: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn challenge() -> Challenge {
+        Challenge::new(SECRET.to_owned(), Method::Plain).unwrap()
+    }
+
+    fn store() -> MemoryStore<Arc<ManualClock>> {
+        MemoryStore::with_clock(TTL, Arc::new(ManualClock::new(SystemTime::UNIX_EPOCH)))
+    }
+
+    #[tokio::test]
+    async fn take_once() {
+        let store = store();
+
+        store.store("code".to_owned(), challenge()).await.unwrap();
+
+        let taken = store.take("code").await.unwrap().unwrap();
+
+        assert_eq!(taken.secret(), SECRET);
+
+        assert!(store.take("code").await.unwrap().is_none());
+        assert!(store.is_empty());
+    }
+
+    #[tokio::test]
+    async fn expiry() {
+        let store = store();
+
+        store.store("code".to_owned(), challenge()).await.unwrap();
+
+        store.clock().advance(TTL);
+
+        assert!(store.take("code").await.unwrap().is_none());
+    }
+
+    #[test]
+    fn overflow() {
+        let store = MemoryStore::with_clock(
+            Duration::MAX,
+            Arc::new(ManualClock::new(SystemTime::UNIX_EPOCH)),
+        );
+
+        store.insert("code".to_owned(), challenge());
+
+        store.clock().advance(TTL);
+
+        assert!(store.remove("code").is_some());
+    }
+
+    #[test]
+    fn purge() {
+        let store = store();
+
+        store.insert("old".to_owned(), challenge());
+
+        store.clock().advance(TTL / 2);
+
+        store.insert("new".to_owned(), challenge());
+
+        store.clock().advance(TTL / 2);
+
+        store.purge();
+
+        assert_eq!(store.len(), 1);
+        assert!(store.remove("new").is_some());
+    }
+}
@@ -0,0 +1,128 @@
+//! Method-tagged challenge strings.
+//!
+//! Storing challenges often requires keeping both the secret and the method in one field.
+//! This module defines the simple `method:secret` format for that purpose, for instance,
+//! `S256:E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM`.
+//!
+//! Parsing is strict: the method must be known and the secret must be valid for it.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{challenge::Challenge, verifier::Verifier};
+//!
+//! let challenge = Verifier::generate_default().challenge();
+//!
+//! let string = challenge.to_tagged_string();
+//!
+//! assert!(string.starts_with("S256:"));
+//!
+//! assert_eq!(Challenge::from_tagged_string(&string).unwrap(), challenge);
+//! ```
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{challenge, error::ErrorKind, method};
+
+/// The separator between the method and the secret.
+pub const SEPARATOR: char = ':';
+
+/// Represents errors that can occur when parsing tagged strings.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The separator is missing.
+    #[error("missing `{SEPARATOR}` separator")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::tagged::format),
+            help("expected the string in `method{SEPARATOR}secret` format")
+        )
+    )]
+    Format,
+
+    /// The method is unknown.
+    #[error("invalid method")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Method(#[from] method::Error),
+
+    /// The secret is invalid.
+    #[error("invalid secret")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Challenge(#[from] challenge::Error),
+}
+
+impl Error {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Format => ErrorKind::InvalidEncoding,
+            Self::Method(error) => error.kind(),
+            Self::Challenge(error) => error.kind(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        challenge::Challenge,
+        length::{self, Length},
+        method::Method,
+        tagged::Error,
+        verifier::Verifier,
+    };
+
+    const SECRET: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+    #[test]
+    fn format() {
+        let challenge = Challenge::new(SECRET.to_owned(), Method::Sha256).unwrap();
+
+        assert_eq!(challenge.to_tagged_string(), format!("S256:{SECRET}"));
+    }
+
+    #[test]
+    fn strict() {
+        assert!(matches!(
+            Challenge::from_tagged_string(SECRET),
+            Err(Error::Format)
+        ));
+
+        assert!(matches!(
+            Challenge::from_tagged_string(&format!("s256:{SECRET}")),
+            Err(Error::Method(_))
+        ));
+
+        assert!(matches!(
+            Challenge::from_tagged_string(&format!("S256:{SECRET}~")),
+            Err(Error::Challenge(_))
+        ));
+
+        assert!(matches!(
+            Challenge::from_tagged_string(&format!("S256::{SECRET}")),
+            Err(Error::Method(_) | Error::Challenge(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(value in length::MIN..=length::MAX, plain in any::<bool>()) {
+            let verifier = Verifier::generate(Length::new(value).unwrap());
+
+            let method = if plain { Method::Plain } else { Method::Sha256 };
+
+            let challenge = verifier.challenge_using(method);
+
+            let parsed = Challenge::from_tagged_string(&challenge.to_tagged_string()).unwrap();
+
+            prop_assert_eq!(parsed, challenge);
+        }
+    }
+}
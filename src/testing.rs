@@ -0,0 +1,36 @@
+//! Deterministic randomness for tests.
+//!
+//! Fixtures generated from random values should be reproducible, regardless of the platform
+//! and the version of `rand` in use. The [`test_rng`] function returns [`TestRng`] values,
+//! which are ChaCha-based generators seeded from the given integer, to be passed to
+//! the generation functions accepting generators, such as [`Verifier::generate_with_rng`].
+//!
+//! The ChaCha output is fully specified, so the same seed always produces the same values.
+//!
+//! **Never** use these generators outside of tests, as the seeds are trivially guessable.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{length::Length, testing::test_rng, verifier::Verifier};
+//!
+//! let length = Length::default();
+//!
+//! let verifier = Verifier::generate_with_rng(&mut test_rng(13), length);
+//! let other = Verifier::generate_with_rng(&mut test_rng(13), length);
+//!
+//! assert_eq!(verifier, other);
+//! ```
+//!
+//! [`Verifier::generate_with_rng`]: crate::verifier::Verifier::generate_with_rng
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Represents deterministic generators used in tests.
+pub type TestRng = ChaCha8Rng;
+
+/// Returns [`TestRng`] seeded with the given `seed`.
+pub fn test_rng(seed: u64) -> TestRng {
+    TestRng::seed_from_u64(seed)
+}
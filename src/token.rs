@@ -0,0 +1,104 @@
+//! Enforcing PKCE on token requests.
+//!
+//! This module contains parts shared by token endpoint middleware (see [`tower`] and
+//! [`actix_web`]), namely the [`enforce`] function, which checks form-encoded token request
+//! bodies against stored challenges. Failures are responded to with [`OAuthError::body`].
+//!
+//! This module requires either the `tower` or the `actix-web` feature.
+//!
+//! [`tower`]: crate::tower
+//! [`actix_web`]: crate::actix_web
+
+use std::{future::Future, str};
+
+use crate::{
+    challenge::Challenge,
+    error::OAuthError,
+    query,
+    verifier::{CODE_VERIFIER, Verifier},
+};
+
+/// The `code` parameter name.
+pub const CODE: &str = "code";
+
+/// The default limit of token request bodies, in bytes.
+pub const LIMIT: usize = 16 * 1024;
+
+/// Checks the form-encoded token request body, looking up the challenge stored for the `code`.
+///
+/// # Errors
+///
+/// Returns [`OAuthError::InvalidRequest`] if the body is not valid UTF-8, the `code` is missing,
+/// or either of the parameters is repeated, and
+/// [`OAuthError::InvalidGrant`] if no challenge is stored for the code,
+/// the `code_verifier` is missing or invalid, or it does not match.
+pub async fn enforce<L, F>(lookup: L, body: &[u8]) -> Result<(), OAuthError>
+where
+    L: FnOnce(String) -> F,
+    F: Future<Output = Option<Challenge>>,
+{
+    let string = str::from_utf8(body).map_err(|_| OAuthError::InvalidRequest)?;
+
+    let [code, verifier] =
+        query::find(string, [CODE, CODE_VERIFIER]).map_err(|_| OAuthError::InvalidRequest)?;
+
+    let code = code.ok_or(OAuthError::InvalidRequest)?;
+
+    let challenge = lookup(code.into_owned())
+        .await
+        .ok_or(OAuthError::InvalidGrant)?;
+
+    let valid = verifier
+        .as_deref()
+        .and_then(|verifier| Verifier::borrowed(verifier).ok())
+        .is_some_and(|verifier| verifier.verify(&challenge));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(OAuthError::InvalidGrant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{challenge::Challenge, error::OAuthError, method::Method, token::enforce};
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    async fn lookup(code: String) -> Option<Challenge> {
+        (code == "a b").then(|| Challenge::new(VERIFIER.to_owned(), Method::Plain).unwrap())
+    }
+
+    async fn check(body: &str) -> Result<(), OAuthError> {
+        enforce(lookup, body.as_bytes()).await
+    }
+
+    #[tokio::test]
+    async fn accepted() {
+        let body = format!("grant_type=authorization_code&code=a%20b&code_verifier={VERIFIER}");
+
+        assert_eq!(check(&body).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn missing() {
+        assert_eq!(
+            check("grant_type=authorization_code").await,
+            Err(OAuthError::InvalidRequest)
+        );
+
+        assert_eq!(check("code=a%20b").await, Err(OAuthError::InvalidGrant));
+    }
+
+    #[tokio::test]
+    async fn duplicate() {
+        let body = format!("code=a%20b&code_verifier={VERIFIER}&code_verifier=invalid");
+
+        assert_eq!(check(&body).await, Err(OAuthError::InvalidRequest));
+
+        let body = format!("code=a%20b&code=c&code_verifier={VERIFIER}");
+
+        assert_eq!(check(&body).await, Err(OAuthError::InvalidRequest));
+    }
+}
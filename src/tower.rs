@@ -0,0 +1,283 @@
+//! Enforcing PKCE on token endpoints with [`tower`].
+//!
+//! The [`PkceLayer`] wraps token endpoint services, buffering form-encoded token requests
+//! to find the authorization `code` and the `code_verifier` in them. The challenge stored
+//! for the code is then looked up using the given callback, and the verifier is checked
+//! against it, before the inner service runs.
+//!
+//! Requests are rejected with `400 Bad Request` and the OAuth error response, namely
+//! `invalid_request` if the body can not be read, or the error returned by [`enforce`].
+//!
+//! Bodies are buffered up to the limit, [`LIMIT`] by default, which can be configured
+//! via [`PkceLayer::with_limit`]. Larger bodies are rejected with `413 Payload Too Large`
+//! and the `invalid_request` error response.
+//!
+//! Since the body is buffered, the inner service receives requests with [`Full<Bytes>`] bodies.
+//!
+//! This module requires the `tower` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::{collections::HashMap, convert::Infallible, sync::Arc};
+//!
+//! use bytes::Bytes;
+//! use http::{Request, Response, StatusCode, header};
+//! use http_body_util::Full;
+//! use pkce_std::{Code, tower::PkceLayer};
+//! use tower::{Layer, ServiceExt, service_fn};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let (verifier, challenge) = Code::generate_default().into_pair();
+//!
+//! let challenges = Arc::new(HashMap::from([("code".to_owned(), challenge)]));
+//!
+//! let layer = PkceLayer::new(move |code: String| {
+//!     let challenges = challenges.clone();
+//!
+//!     async move { challenges.get(&code).cloned() }
+//! });
+//!
+//! let service = layer.layer(service_fn(|_: Request<Full<Bytes>>| async {
+//!     Ok::<_, Infallible>(Response::new(Full::<Bytes>::from("token")))
+//! }));
+//!
+//! let request = |verifier: &str| {
+//!     Request::post("/token")
+//!         .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+//!         .body(Full::<Bytes>::from(format!("code=code&code_verifier={verifier}")))
+//!         .unwrap()
+//! };
+//!
+//! let response = service.clone().oneshot(request(verifier.get())).await.unwrap();
+//!
+//! assert_eq!(response.status(), StatusCode::OK);
+//!
+//! let other = Code::generate_default().into_pair().0;
+//!
+//! let response = service.oneshot(request(other.get())).await.unwrap();
+//!
+//! assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+//! # }
+//! ```
+//!
+//! [`tower`]: https://docs.rs/tower
+//! [`Full<Bytes>`]: Full
+//! [`enforce`]: crate::token::enforce
+//! [`LIMIT`]: crate::token::LIMIT
+
+use std::{
+    error::Error as StdError,
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ::http::{HeaderValue, Request, Response, StatusCode, header};
+use ::tower::{Layer, Service};
+use bytes::Bytes;
+use http_body::Body;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+
+use crate::{
+    challenge::Challenge,
+    error::{self, OAuthError},
+    token,
+};
+
+/// Represents boxed response futures of [`PkceService`].
+pub type ResponseFuture<R, E> = Pin<Box<dyn Future<Output = Result<R, E>> + Send>>;
+
+/// Represents layers enforcing PKCE on token endpoints.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct PkceLayer<L> {
+    lookup: L,
+    limit: usize,
+}
+
+impl<L> PkceLayer<L> {
+    /// Constructs [`Self`] with the given challenge lookup callback.
+    ///
+    /// The callback is called with the authorization `code` and should return the challenge
+    /// stored for it, if any.
+    pub const fn new(lookup: L) -> Self {
+        Self {
+            lookup,
+            limit: token::LIMIT,
+        }
+    }
+
+    /// Sets the limit of request bodies, in bytes.
+    #[must_use]
+    pub const fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+
+        self
+    }
+
+    /// Returns the limit of request bodies, in bytes.
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl<S, L: Clone> Layer<S> for PkceLayer<L> {
+    type Service = PkceService<S, L>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PkceService::new(inner, self.lookup.clone()).with_limit(self.limit)
+    }
+}
+
+/// Represents services enforcing PKCE on token endpoints.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct PkceService<S, L> {
+    inner: S,
+    lookup: L,
+    limit: usize,
+}
+
+impl<S, L> PkceService<S, L> {
+    /// Constructs [`Self`] wrapping the given service, with the given challenge lookup callback.
+    pub const fn new(inner: S, lookup: L) -> Self {
+        Self {
+            inner,
+            lookup,
+            limit: token::LIMIT,
+        }
+    }
+
+    /// Sets the limit of request bodies, in bytes.
+    #[must_use]
+    pub const fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+
+        self
+    }
+
+    /// Returns the limit of request bodies, in bytes.
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the wrapped service.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+/// Constructs the OAuth error response with the given status and error code.
+fn reject<B: From<String>>(status: StatusCode, error: OAuthError) -> Response<B> {
+    let mut response = Response::new(B::from(error.body(None)));
+
+    *response.status_mut() = status;
+
+    let headers = response.headers_mut();
+
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(error::CONTENT_TYPE),
+    );
+
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(error::CACHE_CONTROL),
+    );
+
+    response
+}
+
+impl<S, L, F, B, R> Service<Request<B>> for PkceService<S, L>
+where
+    S: Service<Request<Full<Bytes>>, Response = Response<R>> + Clone + Send + 'static,
+    S::Future: Send,
+    L: Fn(String) -> F + Clone + Send + 'static,
+    F: Future<Output = Option<Challenge>> + Send,
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+    R: From<String>,
+{
+    type Response = Response<R>;
+    type Error = S::Error;
+    type Future = ResponseFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, context: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(context)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // take the service that was polled ready, leaving the fresh clone behind
+        let clone = self.inner.clone();
+        let mut inner = mem::replace(&mut self.inner, clone);
+
+        let lookup = self.lookup.clone();
+        let limit = self.limit;
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+
+            let collected = match Limited::new(body, limit).collect().await {
+                Ok(collected) => collected,
+                Err(error) => {
+                    let status = if error.is::<LengthLimitError>() {
+                        StatusCode::PAYLOAD_TOO_LARGE
+                    } else {
+                        StatusCode::BAD_REQUEST
+                    };
+
+                    return Ok(reject(status, OAuthError::InvalidRequest));
+                }
+            };
+
+            let bytes = collected.to_bytes();
+
+            if let Err(error) = token::enforce(lookup, &bytes).await {
+                return Ok(reject(StatusCode::BAD_REQUEST, error));
+            }
+
+            inner
+                .call(Request::from_parts(parts, Full::new(bytes)))
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http::{Request, Response, StatusCode};
+    use http_body_util::Full;
+    use tower::{Layer, ServiceExt, service_fn};
+
+    use crate::{challenge::Challenge, tower::PkceLayer};
+
+    #[tokio::test]
+    async fn limit() {
+        let layer = PkceLayer::new(|_: String| async { None::<Challenge> }).with_limit(16);
+
+        let service = layer.layer(service_fn(|_: Request<Full<Bytes>>| async {
+            Ok::<_, Infallible>(Response::new(Full::<Bytes>::default()))
+        }));
+
+        let request = Request::post("/token")
+            .body(Full::<Bytes>::from("code=code&code_verifier=verifier"))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
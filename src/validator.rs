@@ -0,0 +1,359 @@
+//! Validating PKCE code verifiers against custom policies.
+//!
+//! Servers may want to impose stricter requirements on verifiers than the [standard] does,
+//! for instance, accepting only longer verifiers or restricting the character set.
+//!
+//! The [`VerifierValidator`] type is constructed once using the [`Builder`]
+//! (see [`Verifier::validator`]) and then reused to validate every incoming verifier.
+//!
+//! Validators always enforce the [standard] requirements on top of the configured policy.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::{length::Length, verifier::Verifier};
+//!
+//! let validator = Verifier::validator()
+//!     .min_length(Length::new(64).unwrap())
+//!     .build()
+//!     .unwrap();
+//!
+//! let verifier = Verifier::generate(Length::new(64).unwrap());
+//! let short = Verifier::generate(Length::new(43).unwrap());
+//!
+//! assert!(validator.validate(verifier.get()).is_ok());
+//! assert!(validator.validate(short.get()).is_err());
+//! ```
+//!
+//! Restricting the character set and trimming surrounding whitespace:
+//!
+//! ```
+//! use pkce_std::verifier::Verifier;
+//!
+//! let validator = Verifier::validator()
+//!     .chars("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_")
+//!     .strict(false)
+//!     .build()
+//!     .unwrap();
+//!
+//! let string = " dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ\n";
+//!
+//! assert!(validator.validate(string).is_ok());
+//! assert!(validator.validate("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ~").is_err());
+//! ```
+//!
+//! [standard]: https://datatracker.ietf.org/doc/html/rfc7636#section-4.1
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    charset,
+    error::ErrorKind,
+    length::Length,
+    verifier::{self, Verifier},
+};
+
+/// The size of character tables.
+const TABLE: usize = 128;
+
+/// Represents errors that can occur when building validators.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum BuildError {
+    /// The minimum length is greater than the maximum length.
+    #[error("minimum length `{min}` is greater than maximum length `{max}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::validator::build::range),
+            help("make sure the minimum length does not exceed the maximum length")
+        )
+    )]
+    Range {
+        /// The minimum length.
+        min: Length,
+        /// The maximum length.
+        max: Length,
+    },
+
+    /// The character is not allowed by the standard.
+    #[error("character `{character}` is not allowed in verifiers")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::validator::build::character),
+            help("make sure to only allow characters from `{}`", charset::STRING),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
+        )
+    )]
+    Character {
+        /// The invalid character.
+        character: char,
+    },
+}
+
+impl BuildError {
+    /// Constructs [`Self::Range`].
+    pub const fn range(min: Length, max: Length) -> Self {
+        Self::Range { min, max }
+    }
+
+    /// Constructs [`Self::Character`].
+    pub const fn character(character: char) -> Self {
+        Self::Character { character }
+    }
+}
+
+/// Represents errors that can occur when validating verifiers.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Error {
+    /// The verifier does not meet the standard requirements.
+    #[error("invalid verifier")]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
+    Verifier(#[from] verifier::Error),
+
+    /// The verifier length is out of the configured range.
+    #[error("expected verifier length in `[{min}, {max}]` range, got `{value}`")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::validator::length),
+            help("make sure the verifier is at least `{min}` and at most `{max}` long")
+        )
+    )]
+    Length {
+        /// The verifier length.
+        value: usize,
+        /// The minimum length.
+        min: Length,
+        /// The maximum length.
+        max: Length,
+    },
+
+    /// The verifier contains the disallowed character.
+    #[error("character `{character}` is not allowed")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(
+            code(pkce_std::validator::character),
+            help("make sure the verifier only contains allowed characters")
+        )
+    )]
+    Character {
+        /// The disallowed character.
+        character: char,
+        /// The index of the disallowed character.
+        #[cfg_attr(feature = "diagnostics", label("disallowed character"))]
+        index: usize,
+    },
+}
+
+impl Error {
+    /// Constructs [`Self::Length`].
+    pub const fn length(value: usize, min: Length, max: Length) -> Self {
+        Self::Length { value, min, max }
+    }
+
+    /// Constructs [`Self::Character`].
+    pub const fn character(character: char, index: usize) -> Self {
+        Self::Character { character, index }
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Verifier(error) => error.kind(),
+            Self::Length { .. } => ErrorKind::InvalidLength,
+            Self::Character { .. } => ErrorKind::InvalidCharacter,
+        }
+    }
+}
+
+/// Represents builders of [`VerifierValidator`] values.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct Builder {
+    min: Length,
+    max: Length,
+    chars: Option<String>,
+    strict: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// Constructs [`Self`] with the standard policy.
+    pub const fn new() -> Self {
+        Self {
+            min: Length::MIN,
+            max: Length::MAX,
+            chars: None,
+            strict: true,
+        }
+    }
+
+    /// Sets the minimum verifier length.
+    #[must_use]
+    pub const fn min_length(mut self, min: Length) -> Self {
+        self.min = min;
+
+        self
+    }
+
+    /// Sets the maximum verifier length.
+    #[must_use]
+    pub const fn max_length(mut self, max: Length) -> Self {
+        self.max = max;
+
+        self
+    }
+
+    /// Restricts the allowed characters to the given ones.
+    ///
+    /// The characters must be a subset of the [`charset::STRING`] set.
+    #[must_use]
+    pub fn chars<S: AsRef<str>>(mut self, chars: S) -> Self {
+        self.chars = Some(chars.as_ref().to_owned());
+
+        self
+    }
+
+    /// Sets whether to reject surrounding whitespace instead of trimming it.
+    ///
+    /// Validators are strict by default.
+    #[must_use]
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+
+        self
+    }
+
+    /// Builds [`VerifierValidator`] from the configured policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::Range`] if the minimum length is greater than the maximum length,
+    /// and [`BuildError::Character`] if any of the characters is not allowed by the standard.
+    pub fn build(self) -> Result<VerifierValidator, BuildError> {
+        if self.min > self.max {
+            return Err(BuildError::range(self.min, self.max));
+        }
+
+        let chars = self.chars.as_deref().unwrap_or(charset::STRING);
+
+        let mut table = [false; TABLE];
+
+        for character in chars.chars() {
+            if !charset::is_valid_char(character) {
+                return Err(BuildError::character(character));
+            }
+
+            table[character as usize] = true;
+        }
+
+        let validator = VerifierValidator {
+            min: self.min,
+            max: self.max,
+            table,
+            strict: self.strict,
+        };
+
+        Ok(validator)
+    }
+}
+
+/// Represents reusable verifier validators.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone)]
+pub struct VerifierValidator {
+    min: Length,
+    max: Length,
+    table: [bool; TABLE],
+    strict: bool,
+}
+
+impl Default for VerifierValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VerifierValidator {
+    /// Constructs [`Self`] with the standard policy.
+    pub fn new() -> Self {
+        Builder::new()
+            .build()
+            .expect("the standard policy is always valid")
+    }
+
+    /// Returns the [`Builder`] used to construct [`Self`].
+    pub const fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Returns the minimum verifier length.
+    pub const fn min_length(&self) -> Length {
+        self.min
+    }
+
+    /// Returns the maximum verifier length.
+    pub const fn max_length(&self) -> Length {
+        self.max
+    }
+
+    /// Checks whether the given character is allowed.
+    pub const fn allows(&self, character: char) -> bool {
+        character.is_ascii() && self.table[character as usize]
+    }
+
+    /// Checks whether surrounding whitespace is rejected.
+    pub const fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Validates the given string, returning the borrowed [`Verifier<'_>`] on success.
+    ///
+    /// Unless [`Self`] is strict, surrounding whitespace is trimmed beforehand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Verifier`] if the string does not meet the standard requirements,
+    /// [`Error::Length`] if its length is out of the configured range,
+    /// and [`Error::Character`] if it contains disallowed characters.
+    ///
+    /// [`Verifier<'_>`]: Verifier
+    pub fn validate<'s>(&self, string: &'s str) -> Result<Verifier<'s>, Error> {
+        let string = if self.strict { string } else { string.trim() };
+
+        let verifier = Verifier::borrowed(string)?;
+
+        let value = string.len();
+
+        if value < self.min.get() || value > self.max.get() {
+            return Err(Error::length(value, self.min, self.max));
+        }
+
+        if let Some((index, character)) = string
+            .char_indices()
+            .find(|&(_, character)| !self.allows(character))
+        {
+            return Err(Error::character(character, index));
+        }
+
+        Ok(verifier)
+    }
+}
@@ -32,16 +32,45 @@
 //! assert_ne!(verifier, other);
 //! ```
 //!
+//! Verifiers are redacted when formatted with [`Debug`], unless the `expose-secrets` feature
+//! is enabled. Note that [`Display`] always shows the verifier.
+//!
+//! ```
+//! # #[cfg(not(feature = "expose-secrets"))]
+//! # {
+//! use pkce_std::verifier::Verifier;
+//!
+//! let verifier = Verifier::generate_default();
+//!
+//! assert!(!format!("{verifier:?}").contains(verifier.get()));
+//! # }
+//! ```
+//!
+//! With the `serde-redacted` feature enabled, verifiers can be serialized as their
+//! [`Fingerprint`] instead, so that the secrets never leave the process.
+//! This is opt-in per field, see the [`redacted`] module for more information.
+//!
+//! Deserializing allocates owned strings, so that [`Verifier<'static>`] can be deserialized
+//! from any input. Use the [`borrowed`] module to borrow strings from the input instead.
+//!
+//! With the `zeroize` feature enabled, owned verifier strings are zeroed out on drop.
+//!
 //! [`Verifier<'_>`]: Verifier
+//! [`Debug`]: fmt::Debug
+//! [`Display`]: fmt::Display
+//! [`redacted`]: self::serde::redacted
+//! [`borrowed`]: self::serde::borrowed
+//! [`Verifier<'static>`]: Verifier
 
 use std::{
-    borrow::Cow,
+    borrow::{Borrow, Cow},
     fmt,
     hash::{Hash, Hasher},
+    mem,
+    ops::Deref,
 };
 
 use const_macros::{const_map_err, const_none, const_ok, const_try};
-use constant_time_eq::constant_time_eq;
 
 #[cfg(feature = "static")]
 use into_static::IntoStatic;
@@ -50,28 +79,82 @@ use into_static::IntoStatic;
 use miette::Diagnostic;
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+#[cfg(feature = "rand")]
+use rand::{CryptoRng, Rng, TryRngCore, distr::Distribution, rngs::OsRng};
 
 use thiserror::Error;
 
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[cfg(feature = "events")]
+use crate::events;
+
+#[cfg(feature = "extended-methods")]
+use crate::hash::{sha384, sha512};
+
+#[cfg(feature = "urlencoded")]
+use crate::query;
+
+#[cfg(feature = "testing")]
+use crate::testing::test_rng;
+
+#[cfg(feature = "rand")]
+use crate::distr::VerifierDistribution;
+
 use crate::{
-    challenge::Challenge,
+    challenge::{self, Challenge},
     check::string::{self, const_check_str},
     count::{self, Count},
-    encoding, generate,
+    ct, encoding,
+    error::ErrorKind,
+    fingerprint::Fingerprint,
+    generate::{self, EntropyError},
+    hash::{MAX_LENGTH, sha256},
     length::{self, Length},
     method::Method,
+    protected,
+    validator::Builder,
 };
 
+pub mod owned;
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub use owned::OwnedVerifier;
+
 /// Represents the error message for invalid verifiers.
 pub const ERROR: &str = "invalid verifier; check the length and characters";
 
+/// The `code_verifier` parameter name.
+pub const CODE_VERIFIER: &str = "code_verifier";
+
 /// Represents errors that can occur when constructing verifiers.
 ///
 /// There are two cases when constructing can fail:
 ///
 /// - [`Length::check`] fails, which means that the length of the string is invalid;
 /// - [`string::check`] fails, which means the string contains invalid characters.
+///
+/// With the `diagnostics` feature enabled, attaching the checked string as the source code
+/// to the report highlights the offending character (see [`OwnedError`] for owned strings):
+///
+/// ```
+/// # #[cfg(feature = "diagnostics")]
+/// # {
+/// use miette::Report;
+/// use pkce_std::verifier::Verifier;
+///
+/// let string = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraX+";
+///
+/// let error = Verifier::borrowed(string).unwrap_err();
+///
+/// let report = Report::new(error).with_source_code(string);
+/// # }
+/// ```
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
 pub enum Error {
@@ -81,23 +164,133 @@ pub enum Error {
         feature = "diagnostics",
         diagnostic(
             code(pkce_std::verifier::length),
-            help("check the length of the verifier")
+            help("check the length of the verifier"),
+            url("https://datatracker.ietf.org/doc/html/rfc7636#section-4.1")
         )
     )]
     Length(#[from] length::Error),
 
     /// Invalid character(s) in verifier.
+    ///
+    /// The diagnostic is forwarded to the [`string::Error`], labeling the offending character.
     #[error("verifier contains invalid character(s)")]
-    #[cfg_attr(
-        feature = "diagnostics",
-        diagnostic(
-            code(pkce_std::verifier::check),
-            help("make sure the verifier is composed of valid characters only")
-        )
-    )]
+    #[cfg_attr(feature = "diagnostics", diagnostic(transparent))]
     String(#[from] string::Error),
 }
 
+impl Error {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Length(error) => error.kind(),
+            Self::String(error) => error.kind(),
+        }
+    }
+}
+
+/// Represents errors that can occur when constructing verifiers from owned strings.
+///
+/// Similar to [`FromUtf8Error`], this error contains the original string,
+/// which can be recovered using [`into_value`].
+///
+/// With the `diagnostics` feature enabled, the original string is used as the source code,
+/// meaning that reports highlight the offending character without attaching it manually:
+///
+/// ```
+/// # #[cfg(feature = "diagnostics")]
+/// # {
+/// use miette::Diagnostic;
+/// use pkce_std::verifier::Verifier;
+///
+/// let string = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraX+";
+///
+/// let error = Verifier::try_owned(string.to_owned()).unwrap_err();
+///
+/// assert!(error.source_code().is_some());
+///
+/// let label = error.labels().unwrap().next().unwrap();
+///
+/// assert_eq!((label.offset(), label.len()), (42, 1));
+/// # }
+/// ```
+///
+/// [`FromUtf8Error`]: std::string::FromUtf8Error
+/// [`into_value`]: Self::into_value
+#[derive(Debug, Error)]
+#[error("{error}")]
+#[cfg_attr(
+    feature = "diagnostics",
+    derive(Diagnostic),
+    diagnostic(forward(error))
+)]
+pub struct OwnedError {
+    error: Error,
+    #[cfg_attr(feature = "diagnostics", source_code)]
+    value: String,
+}
+
+impl OwnedError {
+    /// Constructs [`Self`].
+    pub const fn new(error: Error, value: String) -> Self {
+        Self { error, value }
+    }
+
+    /// Returns the underlying [`enum@Error`].
+    pub const fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the borrowed original string.
+    pub const fn value(&self) -> &str {
+        self.value.as_str()
+    }
+
+    /// Consumes [`Self`] and returns the original string.
+    pub fn into_value(self) -> String {
+        self.value
+    }
+
+    /// Consumes [`Self`] and returns the `(error, value)` parts.
+    pub fn into_parts(self) -> (Error, String) {
+        (self.error, self.value)
+    }
+
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        self.error.kind()
+    }
+}
+
+/// Represents the origins of generated verifiers.
+///
+/// Verifiers created via [`Verifier::generate`] and [`Verifier::generate_encode`]
+/// record the [`Length`] and [`Count`] used respectively, see [`Verifier::origin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// The verifier was generated as the random string of the given length.
+    String(Length),
+    /// The verifier was generated by encoding the given count of random bytes.
+    Bytes(Count),
+}
+
+impl Origin {
+    /// Returns the length of the generated verifier.
+    pub fn length(self) -> Length {
+        match self {
+            Self::String(length) => length,
+            Self::Bytes(count) => count.into(),
+        }
+    }
+
+    /// Returns the count of random bytes, if the verifier was generated from them.
+    pub const fn count(self) -> Option<Count> {
+        match self {
+            Self::String(_) => None,
+            Self::Bytes(count) => Some(count),
+        }
+    }
+}
+
 /// Represents PKCE code verifiers.
 ///
 /// Refer to the [module] documentation for more information.
@@ -120,9 +313,11 @@ pub enum Error {
 /// ```
 ///
 /// [module]: self
-#[derive(Debug, Clone)]
+#[derive(Clone)]
+#[cfg_attr(feature = "expose-secrets", derive(Debug))]
 pub struct Verifier<'v> {
     value: Cow<'v, str>,
+    origin: Option<Origin>,
 }
 
 #[cfg(feature = "serde")]
@@ -135,9 +330,40 @@ impl Serialize for Verifier<'_> {
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Verifier<'_> {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let value = Cow::deserialize(deserializer)?;
+        let value = String::deserialize(deserializer)?;
+
+        Self::owned(value).map_err(de::Error::custom)
+    }
+}
 
-        Self::new(value).map_err(de::Error::custom)
+// borrowed strings can not be zeroed out, so only owned ones are
+#[cfg(feature = "zeroize")]
+impl Drop for Verifier<'_> {
+    fn drop(&mut self) {
+        if let Cow::Owned(value) = &mut self.value {
+            value.zeroize();
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Verifier<'_> {}
+
+#[cfg(not(feature = "expose-secrets"))]
+impl fmt::Debug for Verifier<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Verifier")
+            .field("length", &self.get().len())
+            .field("origin", &self.origin)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Verifier<'static>> for VerifierDistribution {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Verifier<'static> {
+        Verifier::sample(rng, self.length())
     }
 }
 
@@ -152,6 +378,115 @@ impl Verifier<'_> {
     pub fn get(&self) -> &str {
         self.value.as_ref()
     }
+
+    /// Returns the `code_verifier` form pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::verifier::Verifier;
+    ///
+    /// let string = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+    ///
+    /// let verifier = Verifier::borrowed(string).unwrap();
+    ///
+    /// assert_eq!(verifier.form_pair(), ("code_verifier", string));
+    /// ```
+    pub fn form_pair(&self) -> (&'static str, &str) {
+        (CODE_VERIFIER, self.get())
+    }
+
+    /// Parses [`Self`] from the `code_verifier` form parameter.
+    ///
+    /// Refer to the [`query`] module documentation for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`query::Error`] if the parameter is missing, duplicated or invalid.
+    ///
+    /// [`query`]: crate::query
+    #[cfg(feature = "urlencoded")]
+    pub fn from_form(form: &str) -> Result<Verifier<'static>, query::Error> {
+        let [verifier] = query::find(form, [CODE_VERIFIER])?;
+
+        let verifier = verifier.ok_or(query::Error::missing(CODE_VERIFIER))?;
+
+        let verifier = Verifier::owned(verifier.into_owned())?;
+
+        Ok(verifier)
+    }
+
+    /// Similar to [`get`], but can be used in `const` contexts.
+    ///
+    /// [`get`]: Self::get
+    pub const fn const_get(&self) -> &str {
+        match &self.value {
+            Cow::Borrowed(value) => value,
+            Cow::Owned(value) => value.as_str(),
+        }
+    }
+
+    /// Returns the borrowed string as bytes.
+    ///
+    /// This is handy when feeding verifiers into hashing or transport code,
+    /// which is also possible via the [`AsRef<[u8]>`] implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{hash::sha256, verifier::Verifier};
+    ///
+    /// let verifier = Verifier::generate_default();
+    ///
+    /// assert_eq!(verifier.as_bytes(), verifier.get().as_bytes());
+    ///
+    /// assert_eq!(sha256(&verifier).as_ref(), sha256(verifier.as_bytes()).as_ref());
+    /// ```
+    ///
+    /// [`AsRef<[u8]>`]: AsRef
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.const_get().as_bytes()
+    }
+
+    /// Returns the [`Length`] of [`Self`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{length::Length, verifier::Verifier};
+    ///
+    /// let verifier = Verifier::generate(Length::MAX);
+    ///
+    /// assert_eq!(verifier.length(), Length::MAX);
+    /// ```
+    pub const fn length(&self) -> Length {
+        // SAFETY: the length of valid verifiers is always in the valid range
+        unsafe { Length::new_unchecked(self.const_get().len()) }
+    }
+
+    /// Returns the [`Count`] of random bytes [`Self`] was generated from, if any.
+    ///
+    /// This is only known for verifiers generated by encoding random bytes, see [`Origin`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{count::Count, verifier::Verifier};
+    ///
+    /// let verifier = Verifier::generate_encode(Count::MAX);
+    ///
+    /// assert_eq!(verifier.count(), Some(Count::MAX));
+    ///
+    /// let other = Verifier::generate_default();
+    ///
+    /// assert_eq!(other.count(), None);
+    /// ```
+    pub const fn count(&self) -> Option<Count> {
+        match self.origin {
+            Some(origin) => origin.count(),
+            None => None,
+        }
+    }
 }
 
 impl AsRef<str> for Verifier<'_> {
@@ -160,9 +495,49 @@ impl AsRef<str> for Verifier<'_> {
     }
 }
 
+impl AsRef<[u8]> for Verifier<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Deref for Verifier<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+/// Verifiers are hashed and compared exactly like their strings,
+/// so they can be looked up by string slices in keyed collections.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use pkce_std::verifier::Verifier;
+///
+/// let verifier = Verifier::generate_default();
+///
+/// let string = verifier.get().to_owned();
+///
+/// let mut map = HashMap::new();
+///
+/// map.insert(verifier, "state");
+///
+/// assert_eq!(map.get(string.as_str()), Some(&"state"));
+/// ```
+impl Borrow<str> for Verifier<'_> {
+    fn borrow(&self) -> &str {
+        self.get()
+    }
+}
+
 impl PartialEq for Verifier<'_> {
     fn eq(&self, other: &Self) -> bool {
-        constant_time_eq(self.get().as_bytes(), other.get().as_bytes())
+        ct::eq(self.get(), other.get())
     }
 }
 
@@ -179,7 +554,50 @@ impl Verifier<'_> {
     pub fn generate(length: Length) -> Self {
         // SAFETY: `generate::string(length)` creates valid values for `Self`,
         // meaning that their length is exactly `length` and they consist of valid characters.
-        unsafe { Self::owned_unchecked(generate::string(length)) }
+        let verifier = unsafe { Self::owned_unchecked(generate::string(length)) };
+
+        verifier.with_origin(Origin::String(length))
+    }
+
+    /// Generates random [`Self`] with specified length, drawing entropy directly
+    /// from the operating system.
+    ///
+    /// Unlike [`generate`], this function does not panic on entropy failures.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EntropyError`] if the operating system fails to provide entropy.
+    ///
+    /// [`generate`]: Self::generate
+    pub fn try_generate(length: Length) -> Result<Self, EntropyError> {
+        // SAFETY: `generate::try_string(length)` creates valid values for `Self`,
+        // meaning that their length is exactly `length` and they consist of valid characters.
+        let verifier = unsafe { Self::owned_unchecked(generate::try_string(length)?) };
+
+        Ok(verifier.with_origin(Origin::String(length)))
+    }
+
+    /// Generates random [`Self`] with specified length using the given generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{length::Length, verifier::Verifier};
+    ///
+    /// let verifier = Verifier::generate_with_rng(&mut rand::rng(), Length::default());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn generate_with_rng<R: CryptoRng + Rng + ?Sized>(rng: &mut R, length: Length) -> Self {
+        Self::sample(rng, length)
+    }
+
+    #[cfg(feature = "rand")]
+    pub(crate) fn sample<R: Rng + ?Sized>(rng: &mut R, length: Length) -> Self {
+        // SAFETY: `generate::sample_string(rng, length)` creates valid values for `Self`,
+        // meaning that their length is exactly `length` and they consist of valid characters.
+        let verifier = unsafe { Self::owned_unchecked(generate::sample_string(rng, length)) };
+
+        verifier.with_origin(Origin::String(length))
     }
 
     /// Generates random [`Self`] with default length.
@@ -187,38 +605,383 @@ impl Verifier<'_> {
         Self::generate(Length::default())
     }
 
+    /// Generates random [`Self`] with the maximum length ([`Length::MAX`]).
+    ///
+    /// This is the strongest verifier allowed by the standard.
+    pub fn generate_max() -> Self {
+        Self::generate(Length::MAX)
+    }
+
+    /// Generates reproducible [`Self`] with specified length from the given `seed`.
+    ///
+    /// This is intended for tests and fixtures only, see [`testing`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{length::Length, verifier::Verifier};
+    ///
+    /// let length = Length::default();
+    ///
+    /// assert_eq!(
+    ///     Verifier::generate_seeded(42, length),
+    ///     Verifier::generate_seeded(42, length),
+    /// );
+    /// ```
+    ///
+    /// [`testing`]: crate::testing
+    #[cfg(feature = "testing")]
+    pub fn generate_seeded(seed: u64, length: Length) -> Self {
+        Self::generate_with_rng(&mut test_rng(seed), length)
+    }
+
     /// Generates `count` random bytes length and encodes them into [`Self`].
     pub fn generate_encode(count: Count) -> Self {
         // SAFETY: `generate::bytes(count)` creates valid values for `Self::encode_unchecked`,
         // meaning that their length is exactly `count`.
-        unsafe { Self::encode_unchecked(generate::bytes(count)) }
+        let verifier = unsafe { Self::encode_unchecked(generate::bytes(count)) };
+
+        verifier.with_origin(Origin::Bytes(count))
+    }
+
+    /// Generates `count` random bytes using the given generator and encodes them into [`Self`].
+    #[cfg(feature = "rand")]
+    pub fn generate_encode_with_rng<R: CryptoRng + Rng + ?Sized>(
+        rng: &mut R,
+        count: Count,
+    ) -> Self {
+        // SAFETY: `generate::bytes_with(rng, count)` creates valid values for `Self::encode_unchecked`,
+        // meaning that their length is exactly `count`.
+        let verifier = unsafe { Self::encode_unchecked(generate::bytes_with(rng, count)) };
+
+        verifier.with_origin(Origin::Bytes(count))
     }
 
     /// Generates random bytes of default length and encodes them into [`Self`].
     pub fn generate_encode_default() -> Self {
         Self::generate_encode(Count::default())
     }
+
+    /// Generates the maximum amount of random bytes ([`Count::MAX`]) and encodes them into [`Self`].
+    pub fn generate_encode_max() -> Self {
+        Self::generate_encode(Count::MAX)
+    }
+
+    /// Generates random [`Self`] with specified length, drawing entropy directly
+    /// from the operating system via [`OsRng`] instead of the thread-local generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{length::Length, verifier::Verifier};
+    ///
+    /// let verifier = Verifier::generate_secure(Length::default());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn generate_secure(length: Length) -> Self {
+        Self::generate_with_rng(&mut OsRng.unwrap_err(), length)
+    }
+
+    /// Generates `count` random bytes from the operating system via [`OsRng`]
+    /// and encodes them into [`Self`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide entropy.
+    #[cfg(feature = "rand")]
+    pub fn generate_encode_secure(count: Count) -> Self {
+        Self::generate_encode_with_rng(&mut OsRng.unwrap_err(), count)
+    }
+
+    /// Returns the [`Builder`] of [`VerifierValidator`] values.
+    ///
+    /// Refer to the [`validator`] module documentation for more information.
+    ///
+    /// [`validator`]: crate::validator
+    /// [`VerifierValidator`]: crate::validator::VerifierValidator
+    pub const fn validator() -> Builder {
+        Builder::new()
+    }
+
+    /// Returns the [`Origin`] of [`Self`], provided it was generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{
+    ///     count::Count,
+    ///     verifier::{Origin, Verifier},
+    /// };
+    ///
+    /// let count = Count::default();
+    ///
+    /// let verifier = Verifier::generate_encode(count);
+    ///
+    /// assert_eq!(verifier.origin(), Some(Origin::Bytes(count)));
+    ///
+    /// let string = verifier.get().to_owned();
+    ///
+    /// let parsed = Verifier::owned(string).unwrap();
+    ///
+    /// assert_eq!(parsed.origin(), None);
+    /// ```
+    pub const fn origin(&self) -> Option<Origin> {
+        self.origin
+    }
+
+    /// Checks whether [`Self`] is the encoding of the given bytes in constant time.
+    ///
+    /// This is useful for clients that retained the original random bytes
+    /// (see [`generate_encode`]) rather than the encoded string. This method does not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::verifier::Verifier;
+    ///
+    /// let bytes = [42; 32];
+    ///
+    /// let verifier = Verifier::encode(bytes).unwrap();
+    ///
+    /// assert!(verifier.matches_bytes(&bytes));
+    /// assert!(!verifier.matches_bytes(&[13; 32]));
+    /// ```
+    ///
+    /// [`generate_encode`]: Self::generate_encode
+    pub fn matches_bytes(&self, bytes: &[u8]) -> bool {
+        let mut buffer = [0; length::MAX];
+
+        let Ok(written) = encoding::encode_into_slice(bytes, &mut buffer) else {
+            return false;
+        };
+
+        ct::eq_bytes(self.get().as_bytes(), &buffer[..written])
+    }
+
+    /// Computes the log-safe [`Fingerprint`] of [`Self`].
+    ///
+    /// Refer to the [`fingerprint`] module documentation for more information.
+    ///
+    /// [`fingerprint`]: crate::fingerprint
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::compute(self)
+    }
+
+    /// Encodes [`Self`] into the canonical length-prefixed bytes.
+    ///
+    /// Refer to the [`protected`] module documentation for more information.
+    ///
+    /// [`protected`]: crate::protected
+    pub fn to_protected_bytes(&self) -> Vec<u8> {
+        protected::encode(self.get())
+    }
+
+    /// Decodes [`Self`] from the canonical length-prefixed bytes, validating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`protected::Error`] if the bytes are malformed or the verifier is invalid.
+    pub fn from_protected_bytes(bytes: &[u8]) -> Result<Self, protected::Error> {
+        let string = protected::decode(bytes)?;
+
+        let verifier = Self::owned(string.to_owned())?;
+
+        Ok(verifier)
+    }
+
+    pub(crate) fn with_origin(mut self, origin: Origin) -> Self {
+        #[cfg(feature = "log")]
+        log::debug!(
+            "generated verifier `{}` from {origin:?}",
+            self.fingerprint()
+        );
+
+        #[cfg(feature = "events")]
+        events::verifier(origin);
+
+        self.origin = Some(origin);
+
+        self
+    }
 }
 
 impl Verifier<'_> {
     /// Computes the [`Challenge`] of [`Self`] with the given [`Method`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the custom method derives invalid secret, see [`try_challenge_using`].
+    ///
+    /// [`try_challenge_using`]: Self::try_challenge_using
     pub fn challenge_using(&self, method: Method) -> Challenge {
         Challenge::create_using(method, self)
     }
 
+    /// Computes the [`Challenge`] of [`Self`] with the given [`Method`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`challenge::Error`] if the method fails to derive valid challenge,
+    /// see [`Challenge::try_create_using`].
+    pub fn try_challenge_using(&self, method: Method) -> Result<Challenge, challenge::Error> {
+        Challenge::try_create_using(method, self)
+    }
+
     /// Computes the [`Challenge`] of [`Self`] with the default [`Method`].
     pub fn challenge(&self) -> Challenge {
         self.challenge_using(Method::default())
     }
 
     /// Verifies the given [`Challenge`] against [`Self`].
+    ///
+    /// Challenges using methods that fail to derive valid challenges are never verified,
+    /// see [`try_verify`].
+    ///
+    /// [`try_verify`]: Self::try_verify
     pub fn verify(&self, challenge: &Challenge) -> bool {
-        let expected = self.challenge_using(challenge.method());
+        self.try_verify(challenge).unwrap_or(false)
+    }
+
+    /// Similar to [`verify`], but accepts anything that can be referenced as [`Challenge`].
+    ///
+    /// This allows verifying challenges held behind smart pointers without cloning them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use pkce_std::code::Code;
+    ///
+    /// let (verifier, challenge) = Code::generate_default().into_pair();
+    ///
+    /// let shared = Arc::new(challenge);
+    ///
+    /// assert!(verifier.verify_ref(&shared));
+    /// assert!(verifier.verify_ref(shared));
+    /// ```
+    ///
+    /// [`verify`]: Self::verify
+    pub fn verify_ref<C: AsRef<Challenge>>(&self, challenge: C) -> bool {
+        self.verify(challenge.as_ref())
+    }
 
-        challenge == &expected
+    /// Verifies the given [`Challenge`] against [`Self`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`challenge::Error`] if the method of the challenge fails to derive
+    /// valid challenge, see [`Challenge::try_create_using`].
+    pub fn try_verify(&self, challenge: &Challenge) -> Result<bool, challenge::Error> {
+        let method = challenge.method();
+
+        // plain challenges are compared directly, without constructing the expected one
+        let verified = if matches!(method, Method::Plain) {
+            self.verify_plain(challenge.secret())
+        } else {
+            let expected = match self.try_challenge_using(method) {
+                Ok(expected) => expected,
+                Err(error) => {
+                    #[cfg(feature = "log")]
+                    log::warn!("can not verify using method `{}`", method.static_str());
+
+                    return Err(error);
+                }
+            };
+
+            challenge == &expected
+        };
+
+        #[cfg(feature = "log")]
+        log::debug!(
+            "verifier `{}` {} challenge using `{}`",
+            self.fingerprint(),
+            if verified {
+                "matches"
+            } else {
+                "does not match"
+            },
+            method.static_str(),
+        );
+
+        Ok(verified)
+    }
+
+    /// Verifies the given [`Method::Plain`] challenge secret against [`Self`] in constant time.
+    ///
+    /// This does not construct the intermediate [`Challenge`], and is used by [`verify`]
+    /// automatically. Servers that already know the method is [`Method::Plain`] can call
+    /// this directly with the secret received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::verifier::Verifier;
+    ///
+    /// let verifier = Verifier::generate_default();
+    ///
+    /// assert!(verifier.verify_plain(verifier.get()));
+    /// assert!(!verifier.verify_plain(Verifier::generate_default().get()));
+    /// ```
+    ///
+    /// [`verify`]: Self::verify
+    pub fn verify_plain(&self, secret: &str) -> bool {
+        ct::eq(self.get(), secret)
+    }
+
+    /// Verifies the given [`Challenge`] against [`Self`] without allocating.
+    ///
+    /// Instead of recomputing and encoding the challenge like [`verify`] does,
+    /// the secret of [`Method::Sha256`] challenges is decoded into the stack buffer
+    /// and compared against the digest of [`Self`] directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::{code::Code, verifier::Verifier};
+    ///
+    /// let (verifier, challenge) = Code::generate_default().into_pair();
+    ///
+    /// assert!(verifier.verify_decode(&challenge));
+    ///
+    /// let other = Verifier::generate_default();
+    ///
+    /// assert!(!other.verify_decode(&challenge));
+    /// ```
+    ///
+    /// [`verify`]: Self::verify
+    pub fn verify_decode(&self, challenge: &Challenge) -> bool {
+        let secret = challenge.secret();
+
+        let string = self.get();
+
+        match challenge.method() {
+            Method::Plain => self.verify_plain(secret),
+            Method::Sha256 => verify_digest(secret, sha256(string)),
+            #[cfg(feature = "extended-methods")]
+            Method::Sha384 => verify_digest(secret, sha384(string)),
+            #[cfg(feature = "extended-methods")]
+            Method::Sha512 => verify_digest(secret, sha512(string)),
+            Method::Custom(_) => self.verify(challenge),
+        }
     }
 }
 
+/// Decodes the given secret into the stack buffer and compares it against the digest.
+fn verify_digest<D: AsRef<[u8]>>(secret: &str, digest: D) -> bool {
+    let mut expected = [0; MAX_LENGTH];
+
+    let Ok(written) = encoding::decode_slice(secret, &mut expected) else {
+        return false;
+    };
+
+    ct::eq_bytes(digest.as_ref(), &expected[..written])
+}
+
 impl<'v> Verifier<'v> {
     /// Constructs [`Self`], provided that the given value is valid.
     ///
@@ -241,7 +1004,10 @@ impl<'v> Verifier<'v> {
     ///
     /// The value can be checked using [`Self::check`].
     pub const unsafe fn new_unchecked(value: Cow<'v, str>) -> Self {
-        Self { value }
+        Self {
+            value,
+            origin: None,
+        }
     }
 
     /// Constructs [`Self`] from borrowed `value`, provided it is valid.
@@ -273,6 +1039,34 @@ impl<'v> Verifier<'v> {
         Self::new(Cow::Owned(value))
     }
 
+    /// Similar to [`owned`], but the original string is returned back on failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OwnedError`] containing both the [`enum@Error`] and `value`
+    /// if the latter is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pkce_std::verifier::Verifier;
+    ///
+    /// let value = "invalid".to_owned();
+    ///
+    /// let error = Verifier::try_owned(value).unwrap_err();
+    ///
+    /// assert_eq!(error.into_value(), "invalid");
+    /// ```
+    ///
+    /// [`owned`]: Self::owned
+    pub fn try_owned(value: String) -> Result<Self, OwnedError> {
+        match Self::check_str(&value) {
+            // SAFETY: `value` is valid for `Self` here
+            Ok(()) => Ok(unsafe { Self::owned_unchecked(value) }),
+            Err(error) => Err(OwnedError::new(error, value)),
+        }
+    }
+
     /// Constructs [`Self`] from owned `value` without checking it.
     ///
     /// # Safety
@@ -286,16 +1080,6 @@ impl<'v> Verifier<'v> {
 
     /// Similar to [`borrowed`], but can be used in `const` contexts.
     ///
-    /// # Note
-    ///
-    /// One may need to increase the recursion limit when using longer strings.
-    ///
-    /// This is done via applying the `recursion_limit` attribute to the crate:
-    ///
-    /// ```
-    /// #![recursion_limit = "256"]
-    /// ```
-    ///
     /// # Errors
     ///
     /// See [`const_check_str`] for more information.
@@ -321,16 +1105,6 @@ impl<'v> Verifier<'v> {
 
     /// Constantly checks if the given string is valid for [`Self`].
     ///
-    /// # Note
-    ///
-    /// One may need to increase the recursion limit when checking longer strings.
-    ///
-    /// This is done via applying the `recursion_limit` attribute to the crate:
-    ///
-    /// ```
-    /// #![recursion_limit = "256"]
-    /// ```
-    ///
     /// # Errors
     ///
     /// Returns [`enum@Error`] if the string is invalid, which means either:
@@ -372,9 +1146,31 @@ impl<'v> Verifier<'v> {
         Self::check_str(value.as_ref())
     }
 
+    /// Returns the contained string, preserving whether it is borrowed.
+    #[cfg(feature = "sqlx")]
+    pub(crate) const fn as_cow(&self) -> &Cow<'v, str> {
+        &self.value
+    }
+
     /// Consumes [`Self`] and returns the contained string.
-    pub fn take(self) -> Cow<'v, str> {
-        self.value
+    pub fn take(mut self) -> Cow<'v, str> {
+        mem::take(&mut self.value)
+    }
+
+    /// Converts [`Self`] into [`Verifier<'static>`], preserving its origin.
+    ///
+    /// This only allocates if [`Self`] borrows its string.
+    ///
+    /// [`Verifier<'static>`]: Verifier
+    pub fn into_owned(self) -> Verifier<'static> {
+        let origin = self.origin;
+
+        // SAFETY: converting the string into the owned one does not change its validity
+        let mut verifier = unsafe { Verifier::new_unchecked(Cow::Owned(self.take().into_owned())) };
+
+        verifier.origin = origin;
+
+        verifier
     }
 }
 
@@ -415,6 +1211,35 @@ macro_rules! const_borrowed_verifier {
     };
 }
 
+/// Constructs the `(verifier, challenge)` pair from `value` in `const` contexts,
+/// panicking if it is invalid.
+///
+/// The challenge is [`ConstChallenge`], derived using [`Method::Sha256`].
+///
+/// # Examples
+///
+/// ```
+/// use pkce_std::{challenge::ConstChallenge, const_code, verifier::Verifier};
+///
+/// const CODE: (Verifier<'static>, ConstChallenge) =
+///     const_code!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+///
+/// let (verifier, challenge) = CODE;
+///
+/// assert!(verifier.verify(&challenge.to_challenge()));
+/// ```
+///
+/// [`ConstChallenge`]: crate::challenge::ConstChallenge
+#[macro_export]
+macro_rules! const_code {
+    ($value: expr) => {{
+        let verifier = $crate::const_borrowed_verifier!($value);
+        let challenge = $crate::challenge::ConstChallenge::derive(&verifier);
+
+        (verifier, challenge)
+    }};
+}
+
 /// An alias for [`Verifier<'static>`].
 #[cfg(feature = "static")]
 pub type StaticVerifier = Verifier<'static>;
@@ -424,7 +1249,81 @@ impl IntoStatic for Verifier<'_> {
     type Static = StaticVerifier;
 
     fn into_static(self) -> Self::Static {
+        let origin = self.origin;
+
         // SAFETY: calling `into_static` does not change `value` validity
-        unsafe { Self::Static::new_unchecked(self.value.into_static()) }
+        let mut verifier = unsafe { Self::Static::new_unchecked(self.take().into_static()) };
+
+        verifier.origin = origin;
+
+        verifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{
+        count::{self, Count},
+        encoding,
+        length::{self, Length},
+        verifier::{OwnedVerifier, Verifier},
+    };
+
+    const LONGEST: &str = concat!(
+        "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQdGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ",
+        "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraX",
+    );
+
+    // checked in `const` contexts without raising the recursion limit
+    const VERIFIER: Option<Verifier<'static>> = Verifier::const_borrowed_ok(LONGEST);
+
+    #[test]
+    fn const_longest() {
+        assert_eq!(LONGEST.len(), length::MAX);
+
+        assert!(VERIFIER.is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_owned() {
+        let input = r#""dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ""#;
+
+        let verifier: Verifier<'static> = serde_json::from_reader(input.as_bytes()).unwrap();
+
+        assert_eq!(verifier.get(), &input[1..input.len() - 1]);
+    }
+
+    proptest! {
+        #[test]
+        fn generate_round_trip(value in length::MIN..=length::MAX) {
+            let verifier = Verifier::generate(Length::new(value).unwrap());
+
+            prop_assert_eq!(verifier.get().len(), value);
+
+            prop_assert!(Verifier::check(verifier.get()).is_ok());
+        }
+
+        #[test]
+        fn generate_encode_round_trip(value in count::MIN..=count::MAX) {
+            let verifier = Verifier::generate_encode(Count::new(value).unwrap());
+
+            prop_assert_eq!(verifier.get().len(), encoding::length(value));
+
+            prop_assert!(Verifier::check(verifier.get()).is_ok());
+        }
+
+        #[test]
+        fn owned_round_trip(value in count::MIN..=count::MAX) {
+            let verifier = Verifier::generate_encode(Count::new(value).unwrap());
+
+            let owned = OwnedVerifier::from(verifier.clone());
+
+            prop_assert_eq!(owned.origin(), verifier.origin());
+
+            prop_assert_eq!(Verifier::from(owned), verifier);
+        }
     }
 }
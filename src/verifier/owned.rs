@@ -0,0 +1,153 @@
+//! Lifetime-free verifiers.
+//!
+//! Embedding [`Verifier<'v>`] into long-lived structures (for instance, sessions) forces
+//! lifetime parameters onto them, or requires the `static` feature. The [`OwnedVerifier`] type
+//! always owns its string, and therefore has no lifetime parameter.
+//!
+//! Converting owned [`Verifier<'_>`] values into [`OwnedVerifier`] does not allocate,
+//! and neither does converting back into [`Verifier<'static>`].
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::verifier::{OwnedVerifier, Verifier};
+//!
+//! struct Session {
+//!     verifier: OwnedVerifier,
+//! }
+//!
+//! let string = String::from("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+//!
+//! let session = Session {
+//!     verifier: Verifier::borrowed(&string).unwrap().into(),
+//! };
+//!
+//! drop(string);
+//!
+//! let verifier: Verifier<'static> = session.verifier.into();
+//!
+//! assert_eq!(verifier.get(), "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+//! ```
+//!
+//! [`Verifier<'v>`]: Verifier
+//! [`Verifier<'_>`]: Verifier
+//! [`Verifier<'static>`]: Verifier
+
+use std::{fmt, ops::Deref, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::verifier::{Error, Verifier};
+
+/// Represents verifiers that own their strings.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedVerifier {
+    verifier: Verifier<'static>,
+}
+
+impl OwnedVerifier {
+    /// Constructs [`Self`], provided that the given string is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`enum@Error`] if the string is invalid, see [`Verifier::check`].
+    pub fn new(value: String) -> Result<Self, Error> {
+        Verifier::owned(value).map(Self::from)
+    }
+
+    /// Returns the borrowed string.
+    pub fn get(&self) -> &str {
+        self.verifier.get()
+    }
+
+    /// Returns the contained [`Verifier<'static>`].
+    ///
+    /// [`Verifier<'static>`]: Verifier
+    pub const fn as_verifier(&self) -> &Verifier<'static> {
+        &self.verifier
+    }
+
+    /// Consumes [`Self`], returning the contained [`Verifier<'static>`].
+    ///
+    /// [`Verifier<'static>`]: Verifier
+    pub fn into_verifier(self) -> Verifier<'static> {
+        self.verifier
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for OwnedVerifier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.verifier.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for OwnedVerifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        Self::new(value).map_err(de::Error::custom)
+    }
+}
+
+impl From<Verifier<'_>> for OwnedVerifier {
+    fn from(verifier: Verifier<'_>) -> Self {
+        Self {
+            verifier: verifier.into_owned(),
+        }
+    }
+}
+
+impl From<OwnedVerifier> for Verifier<'static> {
+    fn from(owned: OwnedVerifier) -> Self {
+        owned.into_verifier()
+    }
+}
+
+impl TryFrom<String> for OwnedVerifier {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl FromStr for OwnedVerifier {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Self::new(string.to_owned())
+    }
+}
+
+impl Deref for OwnedVerifier {
+    type Target = Verifier<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_verifier()
+    }
+}
+
+impl AsRef<Verifier<'static>> for OwnedVerifier {
+    fn as_ref(&self) -> &Verifier<'static> {
+        self.as_verifier()
+    }
+}
+
+impl AsRef<str> for OwnedVerifier {
+    fn as_ref(&self) -> &str {
+        self.get()
+    }
+}
+
+impl fmt::Display for OwnedVerifier {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.verifier.fmt(formatter)
+    }
+}
@@ -0,0 +1,204 @@
+//! Serde adapters for embedding verifiers into user-defined types.
+//!
+//! Refer to the [`option`] module for optional verifiers, and to the [`borrowed`] module
+//! for borrowing verifiers from the input.
+//!
+//! With the `serde-redacted` feature enabled, the [`redacted`] module serializes
+//! verifiers as their fingerprints.
+
+pub mod option {
+    //! Handling optional verifiers.
+    //!
+    //! Missing, `null` and empty values are all deserialized as [`None`],
+    //! which is convenient for form-encoded requests. Invalid verifiers are still rejected.
+    //!
+    //! # Examples
+    //!
+    //! ```
+    //! use pkce_std::verifier::{self, Verifier};
+    //! use serde::{Deserialize, Serialize};
+    //!
+    //! #[derive(Serialize, Deserialize)]
+    //! struct TokenRequest<'t> {
+    //!     code: String,
+    //!     #[serde(default, with = "verifier::serde::option")]
+    //!     code_verifier: Option<Verifier<'t>>,
+    //! }
+    //!
+    //! let request: TokenRequest<'_> =
+    //!     serde_json::from_str(r#"{"code": "code", "code_verifier": ""}"#).unwrap();
+    //!
+    //! assert!(request.code_verifier.is_none());
+    //!
+    //! let request: TokenRequest<'_> = serde_json::from_str(r#"{"code": "code"}"#).unwrap();
+    //!
+    //! assert!(request.code_verifier.is_none());
+    //! ```
+
+    use std::borrow::Cow;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use crate::verifier::Verifier;
+
+    /// Serializes the optional verifier.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(
+        verifier: &Option<Verifier<'_>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        verifier.serialize(serializer)
+    }
+
+    /// Deserializes the optional verifier, treating empty values as [`None`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer error if the non-empty value is not a valid verifier.
+    pub fn deserialize<'de, 'v, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Verifier<'v>>, D::Error> {
+        let Some(value) = Option::<Cow<'_, str>>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        let verifier = Verifier::owned(value.into_owned()).map_err(de::Error::custom)?;
+
+        Ok(Some(verifier))
+    }
+}
+
+pub mod borrowed {
+    //! Borrowing verifiers from the input.
+    //!
+    //! Regular deserialization allocates owned strings, so that [`Verifier<'static>`]
+    //! can be deserialized from any input. This module borrows strings from the input instead,
+    //! whenever the format allows it, avoiding allocations. Otherwise, owned strings are used.
+    //!
+    //! # Examples
+    //!
+    //! ```
+    //! use std::borrow::Cow;
+    //!
+    //! use pkce_std::verifier::{self, Verifier};
+    //! use serde::Deserialize;
+    //!
+    //! #[derive(Deserialize)]
+    //! struct Stored<'s> {
+    //!     #[serde(borrow, with = "verifier::serde::borrowed")]
+    //!     verifier: Verifier<'s>,
+    //! }
+    //!
+    //! let input = r#"{"verifier": "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ"}"#;
+    //!
+    //! let stored: Stored<'_> = serde_json::from_str(input).unwrap();
+    //!
+    //! assert!(matches!(stored.verifier.take(), Cow::Borrowed(_)));
+    //! ```
+    //!
+    //! [`Verifier<'static>`]: crate::verifier::Verifier
+
+    use std::fmt;
+
+    use serde::{Deserializer, Serialize, Serializer, de};
+
+    use crate::verifier::Verifier;
+
+    struct Visitor;
+
+    impl<'de> de::Visitor<'de> for Visitor {
+        type Value = Verifier<'de>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("verifier string")
+        }
+
+        fn visit_borrowed_str<E: de::Error>(self, value: &'de str) -> Result<Self::Value, E> {
+            Verifier::borrowed(value).map_err(E::custom)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            Verifier::owned(value.to_owned()).map_err(E::custom)
+        }
+
+        fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+            Verifier::owned(value).map_err(E::custom)
+        }
+    }
+
+    /// Serializes the verifier.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(
+        verifier: &Verifier<'_>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        verifier.serialize(serializer)
+    }
+
+    /// Deserializes the verifier, borrowing the string from the input if possible.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer error if the value is not a valid verifier.
+    pub fn deserialize<'de: 'v, 'v, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Verifier<'v>, D::Error> {
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+#[cfg(feature = "serde-redacted")]
+pub mod redacted {
+    //! Serializing verifiers as their fingerprints.
+    //!
+    //! This is intended for debug snapshots and telemetry payloads, where the secrets
+    //! must never leave the process. Since fingerprints are not reversible, this module
+    //! only provides serialization.
+    //!
+    //! # Examples
+    //!
+    //! ```
+    //! use pkce_std::verifier::{self, Verifier};
+    //! use serde::Serialize;
+    //!
+    //! #[derive(Serialize)]
+    //! struct Event<'e> {
+    //!     #[serde(with = "verifier::serde::redacted")]
+    //!     verifier: Verifier<'e>,
+    //! }
+    //!
+    //! let verifier = Verifier::generate_default();
+    //!
+    //! let fingerprint = verifier.fingerprint();
+    //!
+    //! let string = serde_json::to_string(&Event { verifier }).unwrap();
+    //!
+    //! assert_eq!(string, format!(r#"{{"verifier":"{fingerprint}"}}"#));
+    //! ```
+
+    use serde::{Serialize, Serializer};
+
+    use crate::verifier::Verifier;
+
+    /// Serializes the fingerprint of the verifier.
+    ///
+    /// # Errors
+    ///
+    /// Any error returned by the serializer is propagated.
+    pub fn serialize<S: Serializer>(
+        verifier: &Verifier<'_>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        verifier.fingerprint().serialize(serializer)
+    }
+}
@@ -0,0 +1,212 @@
+//! Extracting PKCE parameters with [`warp`] filters.
+//!
+//! - [`challenge`] extracts the validated [`Challenge`] from `code_challenge` and
+//!   `code_challenge_method` query parameters of authorization requests;
+//! - [`verifier`] extracts the validated [`Verifier`] from the `code_verifier` form
+//!   parameter of token requests.
+//!
+//! When deserializing, missing `code_challenge_method` defaults to [`Method::Plain`].
+//!
+//! Invalid values are rejected with [`Invalid`], which can be turned into the
+//! `400 Bad Request` OAuth error response via [`recover`].
+//!
+//! This module requires the `warp` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use pkce_std::warp as pkce;
+//! use warp::{Filter, http::StatusCode};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let authorize = warp::path("authorize")
+//!     .and(pkce::challenge())
+//!     .map(|challenge: pkce_std::Challenge| challenge.method().static_str().to_owned())
+//!     .recover(pkce::recover);
+//!
+//! let response = warp::test::request()
+//!     .path("/authorize?code_challenge=invalid&code_challenge_method=S256")
+//!     .reply(&authorize)
+//!     .await;
+//!
+//! assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+//! assert_eq!(
+//!     response.body(),
+//!     r#"{"error":"invalid_request","error_description":"invalid challenge"}"#,
+//! );
+//! # }
+//! ```
+//!
+//! [`warp`]: https://docs.rs/warp
+//! [`Method::Plain`]: crate::method::Method::Plain
+
+use ::warp::{
+    Filter, Rejection, Reply,
+    http::{StatusCode, header},
+    reject::{self, Reject},
+    reply::{self, Response},
+};
+
+#[cfg(feature = "diagnostics")]
+use miette::Diagnostic;
+
+use thiserror::Error;
+
+use crate::{
+    challenge::{self, Challenge},
+    error::{self, ErrorKind},
+    params::{AuthorizationParams, TokenParams},
+    verifier::{self, Verifier},
+};
+
+/// Represents invalid PKCE parameters.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "diagnostics", derive(Diagnostic))]
+pub enum Invalid {
+    /// The challenge is invalid.
+    #[error("invalid challenge")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::warp::challenge), help("check the challenge"))
+    )]
+    Challenge(#[from] challenge::Error),
+    /// The verifier is invalid.
+    #[error("invalid verifier")]
+    #[cfg_attr(
+        feature = "diagnostics",
+        diagnostic(code(pkce_std::warp::verifier), help("check the verifier"))
+    )]
+    Verifier(#[from] verifier::Error),
+}
+
+impl Invalid {
+    /// Returns the [`ErrorKind`] of this error.
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Challenge(error) => error.kind(),
+            Self::Verifier(error) => error.kind(),
+        }
+    }
+}
+
+impl Reject for Invalid {}
+
+/// Constructs the OAuth error response describing the given invalid parameters.
+fn respond(invalid: &Invalid) -> Response {
+    let body = invalid
+        .kind()
+        .oauth_error()
+        .body(Some(&invalid.to_string()));
+
+    let reply = reply::with_header(body, header::CONTENT_TYPE, error::CONTENT_TYPE);
+    let reply = reply::with_header(reply, header::CACHE_CONTROL, error::CACHE_CONTROL);
+
+    reply::with_status(reply, StatusCode::BAD_REQUEST).into_response()
+}
+
+/// Extracts [`Challenge`] from `code_challenge` and `code_challenge_method` query parameters.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+pub fn challenge() -> impl Filter<Extract = (Challenge,), Error = Rejection> + Clone {
+    ::warp::query::<AuthorizationParams>().and_then(|params: AuthorizationParams| async move {
+        Challenge::try_from(params).map_err(|error| reject::custom(Invalid::from(error)))
+    })
+}
+
+/// Extracts [`Verifier`] from the `code_verifier` form parameter.
+///
+/// Note that this filter consumes the request body.
+///
+/// Refer to the [module] documentation for more information.
+///
+/// [module]: self
+pub fn verifier() -> impl Filter<Extract = (Verifier<'static>,), Error = Rejection> + Clone {
+    ::warp::body::form::<TokenParams<'static>>().and_then(
+        |params: TokenParams<'static>| async move {
+            Verifier::try_from(params).map_err(|error| reject::custom(Invalid::from(error)))
+        },
+    )
+}
+
+/// Recovers from [`Invalid`] rejections, replying with the OAuth error response.
+///
+/// Other rejections are passed through.
+///
+/// # Errors
+///
+/// Returns the given rejection unless it is [`Invalid`].
+pub async fn recover(rejection: Rejection) -> Result<Response, Rejection> {
+    rejection.find::<Invalid>().map(respond).ok_or(rejection)
+}
+
+#[cfg(test)]
+mod tests {
+    use warp::{Filter, http::StatusCode, test};
+
+    use crate::{method::Method, warp as pkce};
+
+    const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+    #[tokio::test]
+    async fn challenge() {
+        let challenge = test::request()
+            .path(&format!("/?code_challenge={VERIFIER}"))
+            .filter(&pkce::challenge())
+            .await
+            .unwrap();
+
+        assert_eq!(challenge.secret(), VERIFIER);
+        assert_eq!(challenge.method(), Method::Plain);
+    }
+
+    #[tokio::test]
+    async fn challenge_unknown_method() {
+        let filter = pkce::challenge().map(|_| "").recover(pkce::recover);
+
+        for method in ["foo", "s256"] {
+            let response = test::request()
+                .path(&format!(
+                    "/?code_challenge={VERIFIER}&code_challenge_method={method}"
+                ))
+                .reply(&filter)
+                .await;
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert!(
+                response
+                    .body()
+                    .starts_with(br#"{"error":"invalid_request""#)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn verifier() {
+        let verifier = test::request()
+            .method("POST")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(format!("code=code&code_verifier={VERIFIER}"))
+            .filter(&pkce::verifier())
+            .await
+            .unwrap();
+
+        assert_eq!(verifier.get(), VERIFIER);
+    }
+
+    #[tokio::test]
+    async fn verifier_invalid() {
+        let filter = pkce::verifier().map(|_| "").recover(pkce::recover);
+
+        let response = test::request()
+            .method("POST")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body("code_verifier=invalid")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
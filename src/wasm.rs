@@ -0,0 +1,83 @@
+//! WebAssembly bindings.
+//!
+//! This module exposes a small [`wasm_bindgen`] API, so that single-page applications
+//! can use this crate compiled to WebAssembly:
+//!
+//! - [`generatePair`] generates the `code_verifier` and the `code_challenge`;
+//! - [`verify`] verifies the `code_verifier` against the `code_challenge`.
+//!
+//! The `wasm` feature enables the `wasm_js` backend of [`getrandom`], which draws entropy
+//! from `crypto.getRandomValues`. On `wasm32-unknown-unknown`, the backend also has to be
+//! selected via `RUSTFLAGS='--cfg getrandom_backend="wasm_js"'`.
+//!
+//! [`wasm_bindgen`]: https://docs.rs/wasm-bindgen
+//! [`getrandom`]: https://docs.rs/getrandom
+//! [`generatePair`]: generate_pair
+
+use wasm_bindgen::prelude::{JsError, wasm_bindgen};
+
+use crate::api;
+
+/// Represents generated `(verifier, challenge)` pairs exposed to JavaScript.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Pair {
+    verifier: String,
+    challenge: String,
+    method: String,
+}
+
+#[wasm_bindgen]
+impl Pair {
+    /// Returns the `code_verifier`.
+    #[wasm_bindgen(getter)]
+    pub fn verifier(&self) -> String {
+        self.verifier.clone()
+    }
+
+    /// Returns the `code_challenge`.
+    #[wasm_bindgen(getter)]
+    pub fn challenge(&self) -> String {
+        self.challenge.clone()
+    }
+
+    /// Returns the `code_challenge_method`.
+    #[wasm_bindgen(getter)]
+    pub fn method(&self) -> String {
+        self.method.clone()
+    }
+}
+
+/// Generates the `(verifier, challenge)` pair using the default method and length.
+///
+/// # Errors
+///
+/// Returns [`JsError`] if the environment fails to provide entropy.
+#[wasm_bindgen(js_name = generatePair)]
+pub fn generate_pair() -> Result<Pair, JsError> {
+    let api::Pair {
+        verifier,
+        challenge,
+        method,
+    } = api::generate_pair()?;
+
+    let pair = Pair {
+        verifier,
+        challenge,
+        method,
+    };
+
+    Ok(pair)
+}
+
+/// Verifies the given `code_verifier` against the `code_challenge` using the `method`.
+///
+/// # Errors
+///
+/// Returns [`JsError`] if any of the arguments is invalid or the method is unknown.
+#[wasm_bindgen]
+pub fn verify(verifier: &str, challenge: &str, method: &str) -> Result<bool, JsError> {
+    let verified = api::verify(verifier, challenge, method)?;
+
+    Ok(verified)
+}
@@ -0,0 +1,84 @@
+//! Tests for the `pkce` command-line tool.
+
+#![cfg(feature = "cli")]
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+const VERIFIER: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+const CHALLENGE: &str = "i5ws67KoNWO9H0IZ1vpKrfzT4Zfy2BgrRswaSJHNEqk";
+
+fn run(arguments: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_pkce"))
+        .args(arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn challenge_stdin_json() {
+    let output = run(&["challenge", "--json"], &format!("{VERIFIER}\n"));
+
+    assert!(output.status.success());
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        format!(r#"{{"code_challenge":"{CHALLENGE}","code_challenge_method":"S256"}}"#),
+    );
+}
+
+#[test]
+fn generate_json() {
+    let output = run(&["generate", "--json", "--method", "plain"], "");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with(r#"{"code_verifier":""#));
+    assert!(stdout.contains(r#""code_challenge_method":"plain""#));
+}
+
+#[test]
+fn verify() {
+    let output = run(&["verify", "--challenge", CHALLENGE, VERIFIER], "");
+
+    assert!(output.status.success());
+
+    let output = run(
+        &["verify", "--challenge", CHALLENGE, "--method", "plain", "-"],
+        VERIFIER,
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn invalid_verifier() {
+    let output = run(&["challenge", "invalid"], "");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn completions() {
+    let output = run(&["completions", "bash"], "");
+
+    assert!(output.status.success());
+    assert!(!output.stdout.is_empty());
+}
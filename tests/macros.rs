@@ -0,0 +1,48 @@
+//! Tests for the compile-time validating macros.
+
+#![cfg(feature = "macros")]
+
+use pkce_std::{challenge, method::Method, verifier};
+
+const STRING: &str = "dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ";
+
+#[test]
+fn verifier() {
+    let verifier = verifier!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+
+    assert_eq!(verifier.get(), STRING);
+}
+
+#[test]
+fn challenge() {
+    let verifier = verifier!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+
+    let challenge = challenge!("i5ws67KoNWO9H0IZ1vpKrfzT4Zfy2BgrRswaSJHNEqk");
+
+    assert_eq!(challenge.method(), Method::Sha256);
+    assert_eq!(challenge, verifier.challenge());
+}
+
+#[test]
+fn plain() {
+    let verifier = verifier!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+
+    let challenge = challenge!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ", "plain");
+
+    assert_eq!(challenge.method(), Method::Plain);
+    assert!(verifier.verify(&challenge));
+}
+
+#[cfg(feature = "extended-methods")]
+#[test]
+fn extended() {
+    let verifier = verifier!("dGhhbmtzIGZvciByZWFkaW5nIGRvY3MhIH4gbmVraXQ");
+
+    let challenge = challenge!(
+        "T0izP2agxrqIEQB_Afd6xG18i3h-6SMtzhGitJxodvUeozB07ME8rPxbCaGFi8Dl",
+        "S384"
+    );
+
+    assert_eq!(challenge.method(), Method::Sha384);
+    assert!(verifier.verify(&challenge));
+}
@@ -0,0 +1,151 @@
+//! Statistical timing tests for constant-time comparisons.
+//!
+//! Each test measures the given operation on two classes of inputs (equal and unequal ones),
+//! interleaving them randomly, and then applies Welch's t-test to the measurements.
+//!
+//! These tests are ignored by default, as they are slow and sensitive to noise.
+//! They are best run in release mode on an otherwise idle machine:
+//!
+//! ```console
+//! $ cargo test --release --test timing -- --ignored --test-threads 1
+//! ```
+
+#![cfg(feature = "rand")]
+
+use std::{hint::black_box, time::Instant};
+
+use pkce_std::{challenge::Challenge, code::Code, verifier::Verifier};
+use rand::{Rng, rng};
+
+/// The number of samples to collect per class.
+const SAMPLES: usize = 100_000;
+
+/// The fraction of the slowest samples to discard per class.
+const CROP: f64 = 0.1;
+
+/// The maximum absolute value of the t-statistic considered insignificant.
+const THRESHOLD: f64 = 10.0;
+
+fn crop(mut samples: Vec<f64>) -> Vec<f64> {
+    samples.sort_by(f64::total_cmp);
+
+    let keep = ((samples.len() as f64) * (1.0 - CROP)) as usize;
+
+    samples.truncate(keep);
+
+    samples
+}
+
+fn mean_variance(samples: &[f64]) -> (f64, f64) {
+    let count = samples.len() as f64;
+
+    let mean = samples.iter().sum::<f64>() / count;
+
+    let variance = samples
+        .iter()
+        .map(|sample| (sample - mean).powi(2))
+        .sum::<f64>()
+        / (count - 1.0);
+
+    (mean, variance)
+}
+
+fn welch(left: &[f64], right: &[f64]) -> f64 {
+    let (left_mean, left_variance) = mean_variance(left);
+    let (right_mean, right_variance) = mean_variance(right);
+
+    let error = (left_variance / left.len() as f64 + right_variance / right.len() as f64).sqrt();
+
+    (left_mean - right_mean) / error
+}
+
+/// Measures `function` on both classes and returns the resulting t-statistic.
+fn measure<F: FnMut(bool) -> bool>(mut function: F) -> f64 {
+    let mut rng = rng();
+
+    let mut equal = Vec::with_capacity(SAMPLES);
+    let mut unequal = Vec::with_capacity(SAMPLES);
+
+    while equal.len() < SAMPLES || unequal.len() < SAMPLES {
+        let class = rng.random();
+
+        let start = Instant::now();
+
+        black_box(function(black_box(class)));
+
+        let elapsed = start.elapsed().as_nanos() as f64;
+
+        if class {
+            equal.push(elapsed);
+        } else {
+            unequal.push(elapsed);
+        }
+    }
+
+    welch(&crop(equal), &crop(unequal))
+}
+
+fn assert_insignificant(statistic: f64) {
+    assert!(
+        statistic.abs() < THRESHOLD,
+        "timing difference is significant: |t| = {} >= {THRESHOLD}",
+        statistic.abs()
+    );
+}
+
+/// Replaces the first character of the given string, keeping it valid.
+fn tamper(string: &str) -> String {
+    let replacement = if string.starts_with('A') { "B" } else { "A" };
+
+    replacement.to_owned() + &string[1..]
+}
+
+#[test]
+#[ignore = "statistical timing test"]
+fn verifier_equality() {
+    let verifier = Verifier::generate_default();
+
+    let same = verifier.clone();
+    let other = Verifier::owned(tamper(verifier.get())).unwrap();
+
+    let statistic = measure(|class| {
+        let against = if class { &same } else { &other };
+
+        verifier == *against
+    });
+
+    assert_insignificant(statistic);
+}
+
+#[test]
+#[ignore = "statistical timing test"]
+fn challenge_equality() {
+    let challenge = Verifier::generate_default().challenge();
+
+    let same = challenge.clone();
+    let other = Verifier::generate_default().challenge();
+
+    let statistic = measure(|class| {
+        let against: &Challenge = if class { &same } else { &other };
+
+        challenge == *against
+    });
+
+    assert_insignificant(statistic);
+}
+
+#[test]
+#[ignore = "statistical timing test"]
+fn verify() {
+    let (verifier, challenge) = Code::generate_default().into_pair();
+
+    let other = Verifier::generate_default().challenge();
+
+    let statistic = measure(|class| {
+        let against = if class { &challenge } else { &other };
+
+        verifier.verify(against)
+    });
+
+    assert_insignificant(statistic);
+}